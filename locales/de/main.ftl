@@ -0,0 +1,311 @@
+# Pixel Peeker: deutsche Übersetzung.
+widget-mode = Widget-Modus
+always-on-top = Immer im Vordergrund
+no-preview = Keine Vorschau verfügbar – Bildschirme werden geprüft…
+exit-widget = Widget verlassen
+command-placeholder = Befehl eingeben…
+undo = Rückgängig
+clipboard-load = In Pixel Peeker laden
+use-as-reference = Als Referenz verwenden
+workspace = Arbeitsbereich:
+new-workspace = Neuer Arbeitsbereich
+add = Hinzufügen
+theme = Design:
+preferences = Einstellungen…
+theme-default = Design-Standard
+mouse-position-heading = Mausposition:
+picked-color = Gewählte Farbe:
+load-design-tokens = Design-Tokens laden…
+measure-region = Bereich messen
+snapshot-before = Aufnahme vorher
+snapshot-after = Aufnahme nachher
+keep-in-slot = In Platz behalten (Umschalt+1–3 friert dort ein):
+slot-empty = Leer
+difference = Unterschied
+clear-reference = Referenz entfernen
+brand-check = Markenprüfung:
+pick-from-image = Aus Bild wählen…
+drop-image = oder ein PNG/JPEG ins Fenster ziehen
+back-to-screen = Zurück zum Bildschirm
+notify-on-pick = Beim Wählen benachrichtigen
+sound = Ton
+log-picks = Protokollieren
+pick-command-placeholder = Beim Wählen: Shell-Befehl mit $PP_HEX
+measure = Messen
+watch = Beobachten
+battery-saver = Energiesparen
+clipboard-colors = Farben aus der Zwischenablage
+record = Aufzeichnen
+ruler-overlay = Lineal einblenden
+sample-line = Linie abtasten
+snapshots-size-mismatch = Die Aufnahmen sind unterschiedlich groß
+watch-target-placeholder = Bei Treffer melden: #rrggbb
+watch-command-placeholder = Bei Änderung: Shell-Befehl mit $PP_HEX
+stop = Stopp
+label = Bezeichnung
+no-duplicates = Keine Duplikate
+export = Exportieren…
+copy-sheet = Farbtafel kopieren
+copy-coolors-link = coolors-Link kopieren
+pinned = Angeheftet:
+color-history = Verlauf:
+undated = Ohne Datum
+history-filter = Filter: Hex, Bezeichnung, Variablenname oder #rrggbb zum Sortieren nach ΔE
+swatch-size = Feldgröße
+tailwind-scales = Abstufungen 50–900 erzeugen
+no-palette = Keine Palette
+new = Neu
+palette-name = Palettenname
+add-current-color = Aktuelle Farbe hinzufügen
+move-to = Verschieben nach…
+remove = Entfernen
+import = Importieren…
+paste-coolors-link = coolors-Link einfügen
+export-history = Verlauf exportieren…
+clear = Leeren
+copy = Kopieren
+palettes = Paletten:
+paste-image = Bild einfügen
+copy-gradient = Farbverlauf kopieren
+copy-as-image = Als Bild kopieren
+rename = Umbenennen
+delete = Löschen
+off = Aus
+pin = Anheften
+unpin = Lösen
+set-reference = Als Referenz für ΔE/Kontrast setzen
+close = Schließen
+copy-as = Als { $format } kopieren
+mouse-position = Maus: ({ $x }, { $y })
+clipboard-copied = { $color } kopiert
+ui-scale = UI-Skalierung: { $scale }×
+font-size = Schriftgröße: { $size } (nach Neustart)
+preview-aspect = Vorschaubereich
+preview-style = Vorschaustil
+smooth-preview = Vergrößerte Vorschau glätten
+nearest-token = Nächstes Token: { $name } (ΔE { $distance })
+off-brand = ✖ nicht markenkonform, nächste { $name } (ΔE { $distance })
+preferred-format = Wird mit der Taste C kopiert
+make-preferred-format = Für die Taste C verwenden
+copy-shortcut = Taste: { $key }
+zoom = Zoom: { $zoom }×
+zoom-anchor = Zoomen auf
+picking-from = Farben aus { $name }
+log-appending = Gewählte Farben werden an { $path } angehängt
+log-hint = Jede eingefrorene Farbe an eine CSV- oder JSON-Lines-Datei anhängen
+record-interval = alle { $ms } ms
+api-on = API auf localhost:{ $port }
+api-token = /pick benötigt das Token aus { $path }
+dbus-on = D-Bus-Dienst
+line-stops = { $count } Farbstopps
+snapshot-not-stored = { $name }: nicht gespeichert
+pixels-changed = { $changed } von { $total } Pixeln geändert, max. ΔE { $max }
+watching = Beobachte ({ $x }, { $y })
+watch-changed = ({ $x }, { $y }) wurde zu { $color }
+watch-matched = ({ $x }, { $y }) entspricht { $color }
+recording = ● { $count } Messwerte werden in { $path } aufgezeichnet
+measure-point-unset = { $name }: LEERTASTE zum Markieren
+picked = { $color } gewählt
+status-paused = Pausiert (Strg+Umschalt+P zum Fortsetzen)
+status-frozen = Eingefroren (ESC zum Lösen)
+status-live = Live (LEERTASTE zum Einfrieren)
+resume = Fortsetzen
+pause = Pausieren
+history-cleared = Verlauf geleert
+color-deleted = Farbe gelöscht
+command-enable = { $name } einschalten
+command-disable = { $name } ausschalten
+export-palette-as = Palette als { $format } exportieren
+resume-picking = Wählen fortsetzen
+pause-picking = Wählen pausieren
+show-preferences = Einstellungen anzeigen
+hide-preferences = Einstellungen ausblenden
+open-image = Bild öffnen…
+import-palette = Palette importieren…
+new-palette = Neue Palette
+add-to-palette = Farbe zur Palette hinzufügen
+clear-history = Verlauf leeren
+save-settings = Einstellungen speichern
+theme-command = Design: { $theme }
+language = Sprache:
+language-system = System
+update-available = Pixel Peeker { $version } ist verfügbar
+release-notes = Versionshinweise
+up-to-date = Pixel Peeker ist auf dem neuesten Stand
+imported-colors = { $count } Farben in { $palette } importiert
+check-updates-at-startup = Beim Start nach Updates suchen
+check-now = Jetzt prüfen
+check-for-updates = Nach Updates suchen
+preferences-title = Pixel Peeker – Einstellungen
+loupe-title = Pixel Peeker – Lupe
+loupe = Lupe
+pick-anywhere = Überall wählen…
+pick-overlay-hint = Klicken zum Wählen · Rechtsklick oder ESC zum Abbrechen
+display-no-monitors = Keine Monitore gefunden. Entfernte und Headless-Sitzungen haben eventuell nichts aufzunehmen.
+display-odd-bounds = Monitor { $name } meldet eine ungewöhnliche Größe, wie virtuelle Displays; gewählte Pixel passen eventuell nicht zum Mauszeiger.
+display-black-frames = Die Aufnahme liefert nur Schwarz, wie bei vielen Remote-Desktop-Sitzungen. Wählen Sie einen Monitor.
+capture-monitor = Monitor:
+monitor-automatic = Unter dem Mauszeiger
+color-management = Farbmanagement
+color-managed = Aufnahmen mit Monitorprofilen nach sRGB umrechnen (aus zeigt native Werte)
+profile-system = Systemprofil
+profile-none = kein Profil, als sRGB behandelt
+choose-profile = Profil wählen…
+use-system-profile = Systemprofil verwenden
+compare-raw = Rohwerte und korrigierte Werte
+compare-raw-unavailable = Kein Monitorprofil, Roh- und korrigierte Werte sind gleich
+raw-value = Roh { $color }
+corrected-value = sRGB { $color }
+deep-channels = { $bits } Bit: { $values }
+format-options = Farbformate
+shown-formats = Angezeigte Formate
+hex-shorthand = Hex wenn möglich als #ABC kürzen
+hex-without-hash = # in Hex weglassen
+channel-style = { $format }-Kanäle
+nearest-named = Nächste { $palette }-Farbe: { $name } (ΔE { $distance })
+linearize = { $format }-Farben in lineares Licht umrechnen
+shader-decimals = Nachkommastellen in vec3 und vec4
+named-colors = Benannte Farben
+show-flutter = Nächste Flutter-Farbe
+show-x11 = Nächster X11-Farbname
+show-ansi = Nächste Terminalfarbe
+show-web-safe = Nächste websichere Farbe
+nearest-web-safe = Nächste websichere Farbe: { $color } (ΔE { $distance })
+show-ral = Nächste RAL-Classic-Farbe
+color-description = Beschreibung: { $name }
+color-modified = { $modifier } { $name }
+color-word-white = Weiß
+color-word-light-gray = Hellgrau
+color-word-gray = Grau
+color-word-charcoal = Anthrazit
+color-word-black = Schwarz
+color-word-wine = Weinrot
+color-word-rose = Rosé
+color-word-pink = Rosa
+color-word-maroon = Kastanienbraun
+color-word-red = Rot
+color-word-salmon = Lachsrosa
+color-word-brown = Braun
+color-word-orange = Orange
+color-word-apricot = Apricot
+color-word-bronze = Bronze
+color-word-amber = Bernstein
+color-word-cream = Creme
+color-word-olive = Oliv
+color-word-yellow = Gelb
+color-word-lemon = Zitronengelb
+color-word-moss = Moosgrün
+color-word-lime = Limettengrün
+color-word-pistachio = Pistaziengrün
+color-word-forest = Waldgrün
+color-word-green = Grün
+color-word-mint = Mintgrün
+color-word-teal = Petrol
+color-word-aqua = Aquamarin
+color-word-navy = Marineblau
+color-word-azure = Azurblau
+color-word-sky = Himmelblau
+color-word-blue = Blau
+color-word-periwinkle = Kornblumenblau
+color-word-indigo = Indigo
+color-word-violet = Violett
+color-word-lavender = Lavendel
+color-word-plum = Pflaume
+color-word-purple = Purpur
+color-word-lilac = Flieder
+color-word-magenta = Magenta
+color-word-orchid = Orchidee
+color-word-pale = blasses
+color-word-dusty = gedecktes
+color-word-deep = tiefes
+color-word-vivid = kräftiges
+auto-label-history = Neue Verlaufsfarben mit ihrer Beschreibung benennen
+auto-export = Automatischer Export
+auto-export-hint = Die aktive Palette, ohne Palette den Verlauf, bei jeder Änderung und beim Beenden in eine Datei schreiben
+choose-file = Datei wählen…
+link-shared-palette = Gemeinsame Palettendatei verknüpfen…
+shared-palette = Geteilt mit { $path }
+unlink = Verknüpfung lösen
+theme-dark = Dunkel
+theme-light = Hell
+theme-system = System
+preview-grid = Raster
+preview-circle = Kreis
+zoom-center = Mitte
+zoom-crosshair = Fadenkreuz
+zoom-cursor = Mauszeiger
+grouping-none = Ohne Gruppen
+grouping-day = Nach Tag
+grouping-session = Nach Sitzung
+custom-accent = Akzent
+custom-background = Hintergrund
+custom-text = Text
+custom-live = Live-Status
+custom-frozen = Eingefroren-Status
+custom-frozen-background = Hintergrund beim Einfrieren
+entry-picked-at = Aufgenommen { $time }
+entry-position = Position ({ $x }, { $y })
+entry-monitor = Monitor: { $name }
+entry-window = Fenster: { $title }
+contrast-ratio = Kontrast { $ratio }:1
+compare-active = Aktiv
+compare-reference = Referenz
+palette-format-aco = Photoshop (.aco)
+palette-format-gpl = GIMP-Palette (.gpl)
+palette-format-plain-text = Klartext (.txt)
+palette-format-css = CSS-Variablen (.css)
+palette-format-scss = SCSS-Variablen (.scss)
+palette-format-tailwind = Tailwind-Konfiguration (.js)
+palette-format-design-tokens = Design-Tokens (.json)
+palette-format-tokens-studio = Tokens Studio für Figma (.json)
+palette-format-sketch = Sketch-Palette (.sketchpalette)
+palette-format-procreate = Procreate-Farbfelder (.swatches)
+palette-format-png-sheet = Farbfeldbogen (.png)
+palette-format-svg-sheet = Farbfeldbogen (.svg)
+color-format-rgb = RGB
+color-format-hex = Hex
+color-format-hsv = HSV
+color-format-hsl = HSL
+color-format-oklch = OKLCH
+color-format-linear = Lineares RGB
+color-format-iced = iced
+color-format-egui = egui
+color-format-ratatui = ratatui
+color-format-swiftui = SwiftUI
+color-format-uicolor = UIColor
+color-format-android-xml = Android-XML
+color-format-compose = Compose
+color-format-flutter = Flutter
+color-format-unity = Unity
+color-format-unreal = Unreal
+color-format-vec3 = vec3
+color-format-vec4 = vec4
+color-format-json = JSON
+filter-csv = CSV
+filter-json = JSON
+filter-json-lines = JSON Lines
+filter-icc-profiles = ICC-Profile
+filter-images = Bilder
+filter-palettes = Paletten
+filter-all-files = Alle Dateien
+filter-design-tokens = Design-Tokens
+clipboard-image = Bild aus der Zwischenablage
+no-coolors-link = Die Zwischenablage enthält keinen coolors.co-Palettenlink
+measure-span = ΔX { $dx }  ΔY { $dy }  Abstand { $distance } px  Winkel { $angle }°
+error-capture = Bildschirmaufnahme fehlgeschlagen: { $reason }
+error-off-screen = ({ $x }, { $y }) liegt außerhalb aller Monitore
+error-permission = Bildschirmaufnahme ist nicht erlaubt ({ $reason }); erlaube Pixel Peeker die Bildschirmaufnahme und starte es neu
+error-read-file = { $path } konnte nicht gelesen werden: { $reason }
+error-write-file = { $path } konnte nicht geschrieben werden: { $reason }
+error-invalid-file = { $path }: { $reason }
+error-export = Der Export konnte nicht kodiert werden: { $reason }
+error-io = E/A-Fehler: { $reason }
+error-invalid-input = { $reason }
+error-ruler = Das Lineal konnte nicht gestartet werden: { $reason }
+error-batch-incomplete = { $failed } von { $total } Punkten konnten nicht gemessen werden
+error-settings-format = Die Einstellungen konnten nicht kodiert werden: { $reason }
+error-no-config-dir = Kein Speicherort für { $what } gefunden
+error-clipboard = Zwischenablage: { $reason }
+error-update-check = Die Suche nach Updates ist fehlgeschlagen: { $reason }
+error-color-profile = Das Farbprofil konnte nicht verwendet werden: { $reason }
+error-plugin = Das Plugin { $name } ist fehlgeschlagen und wurde deaktiviert: { $reason }
@@ -0,0 +1,311 @@
+# Pixel Peeker UI strings.
+widget-mode = Widget mode
+always-on-top = Always on top
+no-preview = No preview available - checking monitors...
+exit-widget = Exit widget
+command-placeholder = Type a command…
+undo = Undo
+clipboard-load = Load into Pixel Peeker
+use-as-reference = Use as reference
+workspace = Workspace:
+new-workspace = New workspace
+add = Add
+theme = Theme:
+preferences = Preferences…
+theme-default = theme default
+mouse-position-heading = Mouse Position:
+picked-color = Picked Color:
+load-design-tokens = Load design tokens…
+measure-region = Measure region
+snapshot-before = Snapshot before
+snapshot-after = Snapshot after
+keep-in-slot = Keep in slot (Shift+1–3 freezes into one):
+slot-empty = Empty
+difference = Difference
+clear-reference = Clear reference
+brand-check = Brand check:
+pick-from-image = Pick from image…
+drop-image = or drop a PNG/JPEG onto the window
+back-to-screen = Back to screen
+notify-on-pick = Notify on pick
+sound = Sound
+log-picks = Log picks
+pick-command-placeholder = On pick: shell command using $PP_HEX
+measure = Measure
+watch = Watch
+battery-saver = Battery saver
+clipboard-colors = Clipboard colors
+record = Record
+ruler-overlay = Ruler overlay
+sample-line = Sample line
+snapshots-size-mismatch = Snapshots have different sizes
+watch-target-placeholder = Alert on match: #rrggbb
+watch-command-placeholder = On change: shell command using $PP_HEX
+stop = Stop
+label = Label
+no-duplicates = No duplicates
+export = Export…
+copy-sheet = Copy sheet
+copy-coolors-link = Copy coolors link
+pinned = Pinned:
+color-history = Color History:
+undated = Undated
+history-filter = Filter: hex, label, variable name, or #rrggbb to sort by ΔE
+swatch-size = Swatch size
+tailwind-scales = Generate 50–900 scales
+no-palette = No palette
+new = New
+palette-name = Palette name
+add-current-color = Add current color
+move-to = Move to…
+remove = Remove
+import = Import…
+paste-coolors-link = Paste coolors link
+export-history = Export history…
+clear = Clear
+copy = Copy
+palettes = Palettes:
+paste-image = Paste image
+copy-gradient = Copy gradient
+copy-as-image = Copy as image
+rename = Rename
+delete = Delete
+off = Off
+pin = Pin
+unpin = Unpin
+set-reference = Set as reference for ΔE/contrast
+close = Close
+copy-as = Copy as { $format }
+mouse-position = Mouse: ({ $x }, { $y })
+clipboard-copied = { $color } copied
+ui-scale = UI scale: { $scale }×
+font-size = Font size: { $size } (after restart)
+preview-aspect = Preview region
+preview-style = Preview style
+smooth-preview = Smooth magnified preview
+nearest-token = Nearest token: { $name } (ΔE { $distance })
+off-brand = ✖ off-brand, nearest { $name } (ΔE { $distance })
+preferred-format = Copied by the C shortcut
+make-preferred-format = Use for the C shortcut
+copy-shortcut = Shortcut: { $key }
+zoom = Zoom: { $zoom }×
+zoom-anchor = Zoom toward
+picking-from = Picking from { $name }
+log-appending = Appending picks to { $path }
+log-hint = Append every frozen color to a CSV or JSON Lines file
+record-interval = every { $ms } ms
+api-on = API on localhost:{ $port }
+api-token = /pick needs the token in { $path }
+dbus-on = D-Bus service
+line-stops = { $count } stops
+snapshot-not-stored = { $name }: not stored
+pixels-changed = { $changed } of { $total } pixels changed, max ΔE { $max }
+watching = Watching ({ $x }, { $y })
+watch-changed = ({ $x }, { $y }) changed to { $color }
+watch-matched = ({ $x }, { $y }) matches { $color }
+recording = ● Recording { $count } samples to { $path }
+measure-point-unset = { $name }: press SPACE to mark
+picked = { $color } picked
+status-paused = Paused (press Ctrl+Shift+P to resume)
+status-frozen = Frozen (press ESC to unfreeze)
+status-live = Live (press SPACE to freeze)
+resume = Resume
+pause = Pause
+history-cleared = History cleared
+color-deleted = Color deleted
+command-enable = Enable { $name }
+command-disable = Disable { $name }
+export-palette-as = Export palette as { $format }
+resume-picking = Resume picking
+pause-picking = Pause picking
+show-preferences = Show preferences
+hide-preferences = Hide preferences
+open-image = Open image…
+import-palette = Import palette…
+new-palette = New palette
+add-to-palette = Add color to palette
+clear-history = Clear history
+save-settings = Save settings
+theme-command = Theme: { $theme }
+language = Language:
+language-system = System
+update-available = Pixel Peeker { $version } is available
+release-notes = Release notes
+up-to-date = Pixel Peeker is up to date
+imported-colors = Imported { $count } colors into { $palette }
+check-updates-at-startup = Check for updates at startup
+check-now = Check now
+check-for-updates = Check for updates
+preferences-title = Pixel Peeker – Preferences
+loupe-title = Pixel Peeker – Loupe
+loupe = Loupe
+pick-anywhere = Pick anywhere…
+pick-overlay-hint = Click to pick · right-click or ESC to cancel
+display-no-monitors = No monitors found. Remote and headless sessions may have nothing to capture.
+display-odd-bounds = Monitor { $name } reports an unusual size, as virtual displays do; picked pixels may not match the cursor.
+display-black-frames = Capture only returns black, as under many remote desktop sessions. Try choosing a monitor.
+capture-monitor = Monitor:
+monitor-automatic = Under the cursor
+color-management = Color management
+color-managed = Convert captures to sRGB using monitor profiles (off shows native values)
+profile-system = system profile
+profile-none = no profile, treated as sRGB
+choose-profile = Choose profile…
+use-system-profile = Use system profile
+compare-raw = Raw vs corrected values
+compare-raw-unavailable = No monitor profile, so raw and corrected values are the same
+raw-value = Raw { $color }
+corrected-value = sRGB { $color }
+deep-channels = { $bits }-bit: { $values }
+format-options = Color formats
+shown-formats = Shown formats
+hex-shorthand = Shorten hex to #ABC when possible
+hex-without-hash = Leave out the # in hex
+channel-style = { $format } channels
+nearest-named = Nearest { $palette } color: { $name } (ΔE { $distance })
+linearize = Convert { $format } colors to linear light
+shader-decimals = Decimal places in vec3 and vec4
+named-colors = Named colors
+show-flutter = Nearest Flutter color
+show-x11 = Nearest X11 color name
+show-ansi = Nearest terminal color
+show-web-safe = Nearest web-safe color
+nearest-web-safe = Nearest web-safe color: { $color } (ΔE { $distance })
+show-ral = Nearest RAL Classic color
+color-description = Looks like: { $name }
+color-modified = { $modifier } { $name }
+color-word-white = white
+color-word-light-gray = light gray
+color-word-gray = gray
+color-word-charcoal = charcoal
+color-word-black = black
+color-word-wine = wine
+color-word-rose = rose
+color-word-pink = pink
+color-word-maroon = maroon
+color-word-red = red
+color-word-salmon = salmon
+color-word-brown = brown
+color-word-orange = orange
+color-word-apricot = apricot
+color-word-bronze = bronze
+color-word-amber = amber
+color-word-cream = cream
+color-word-olive = olive
+color-word-yellow = yellow
+color-word-lemon = lemon
+color-word-moss = moss
+color-word-lime = lime
+color-word-pistachio = pistachio
+color-word-forest = forest
+color-word-green = green
+color-word-mint = mint
+color-word-teal = teal
+color-word-aqua = aqua
+color-word-navy = navy
+color-word-azure = azure
+color-word-sky = sky
+color-word-blue = blue
+color-word-periwinkle = periwinkle
+color-word-indigo = indigo
+color-word-violet = violet
+color-word-lavender = lavender
+color-word-plum = plum
+color-word-purple = purple
+color-word-lilac = lilac
+color-word-magenta = magenta
+color-word-orchid = orchid
+color-word-pale = pale
+color-word-dusty = dusty
+color-word-deep = deep
+color-word-vivid = vivid
+auto-label-history = Label new history colors with their description
+auto-export = Auto-export
+auto-export-hint = Write the active palette, or the history when there is none, to a file whenever it changes and on exit
+choose-file = Choose file…
+link-shared-palette = Link shared palette file…
+shared-palette = Shared with { $path }
+unlink = Unlink
+theme-dark = Dark
+theme-light = Light
+theme-system = System
+preview-grid = Grid
+preview-circle = Circle
+zoom-center = Center
+zoom-crosshair = Crosshair
+zoom-cursor = Cursor
+grouping-none = Ungrouped
+grouping-day = By day
+grouping-session = By session
+custom-accent = Accent
+custom-background = Background
+custom-text = Text
+custom-live = Live status
+custom-frozen = Frozen status
+custom-frozen-background = Frozen background
+entry-picked-at = Picked { $time }
+entry-position = Position ({ $x }, { $y })
+entry-monitor = Monitor: { $name }
+entry-window = Window: { $title }
+contrast-ratio = Contrast { $ratio }:1
+compare-active = Active
+compare-reference = Reference
+palette-format-aco = Photoshop (.aco)
+palette-format-gpl = GIMP palette (.gpl)
+palette-format-plain-text = Plain text (.txt)
+palette-format-css = CSS custom properties (.css)
+palette-format-scss = SCSS variables (.scss)
+palette-format-tailwind = Tailwind config (.js)
+palette-format-design-tokens = Design tokens (.json)
+palette-format-tokens-studio = Tokens Studio for Figma (.json)
+palette-format-sketch = Sketch palette (.sketchpalette)
+palette-format-procreate = Procreate swatches (.swatches)
+palette-format-png-sheet = Swatch sheet (.png)
+palette-format-svg-sheet = Swatch sheet (.svg)
+color-format-rgb = RGB
+color-format-hex = Hex
+color-format-hsv = HSV
+color-format-hsl = HSL
+color-format-oklch = OKLCH
+color-format-linear = Linear RGB
+color-format-iced = iced
+color-format-egui = egui
+color-format-ratatui = ratatui
+color-format-swiftui = SwiftUI
+color-format-uicolor = UIColor
+color-format-android-xml = Android XML
+color-format-compose = Compose
+color-format-flutter = Flutter
+color-format-unity = Unity
+color-format-unreal = Unreal
+color-format-vec3 = vec3
+color-format-vec4 = vec4
+color-format-json = JSON
+filter-csv = CSV
+filter-json = JSON
+filter-json-lines = JSON Lines
+filter-icc-profiles = ICC profiles
+filter-images = Images
+filter-palettes = Palettes
+filter-all-files = All files
+filter-design-tokens = Design tokens
+clipboard-image = clipboard image
+no-coolors-link = The clipboard has no coolors.co palette link
+measure-span = ΔX { $dx }  ΔY { $dy }  distance { $distance }px  angle { $angle }°
+error-capture = Screen capture failed: { $reason }
+error-off-screen = ({ $x }, { $y }) is outside every monitor
+error-permission = Screen capture is not permitted ({ $reason }); allow screen recording for Pixel Peeker and restart it
+error-read-file = Could not read { $path }: { $reason }
+error-write-file = Could not write { $path }: { $reason }
+error-invalid-file = { $path }: { $reason }
+error-export = Could not encode the export: { $reason }
+error-io = I/O error: { $reason }
+error-invalid-input = { $reason }
+error-ruler = Could not start the ruler overlay: { $reason }
+error-batch-incomplete = { $failed } of { $total } points could not be sampled
+error-settings-format = Could not encode settings: { $reason }
+error-no-config-dir = Could not determine where to store { $what }
+error-clipboard = Clipboard: { $reason }
+error-update-check = Could not check for updates: { $reason }
+error-color-profile = Could not use the color profile: { $reason }
+error-plugin = Plugin { $name } failed and was turned off: { $reason }
@@ -0,0 +1,452 @@
+use crate::export::{self, HistoryFormat};
+use crate::{ColorInfo, HistoryEntry, Settings};
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 7331;
+
+/// How long a remote pick waits for the UI thread to capture the pixel.
+pub const PICK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a client may take to send its request or accept the response.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The most a request line and headers may take up.
+const MAX_REQUEST_HEAD: u64 = 8 * 1024;
+
+/// How often the events thread reads from subscribers when nothing is sent.
+const EVENT_POLL: Duration = Duration::from_millis(250);
+
+/// Unsent events a subscriber may fall behind by before it is dropped.
+const MAX_SUBSCRIBER_BACKLOG: usize = 256 * 1024;
+
+/// The color as served by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorJson {
+    hex: String,
+    r: u8,
+    g: u8,
+    b: u8,
+    x: i32,
+    y: i32,
+    frozen: bool,
+}
+
+impl ColorJson {
+    pub fn new(info: &ColorInfo, frozen: bool) -> Self {
+        let [r, g, b, _] = info.color.into_rgba8();
+        Self { hex: export::to_hex(info.color), r, g, b, x: info.position.0, y: info.position.1, frozen }
+    }
+
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+}
+
+/// A remote pick waiting for the UI thread, which owns the capture state.
+/// Without a position the pixel under the cursor is picked.
+pub struct PickRequest {
+    pub position: Option<(i32, i32)>,
+    reply: mpsc::Sender<Option<ColorJson>>,
+}
+
+impl PickRequest {
+    pub fn new(position: Option<(i32, i32)>) -> (Self, mpsc::Receiver<Option<ColorJson>>) {
+        let (reply, response) = mpsc::channel();
+        (Self { position, reply }, response)
+    }
+
+    pub fn respond(self, color: Option<ColorJson>) {
+        let _ = self.reply.send(color);
+    }
+}
+
+/// Sent to every `/events` WebSocket subscriber when a color is frozen.
+#[derive(Serialize)]
+struct PickEvent<'a> {
+    event: &'static str,
+    #[serde(flatten)]
+    color: &'a ColorJson,
+}
+
+type Subscriber = tungstenite::WebSocket<TcpStream>;
+
+/// What the events thread is handed: a new subscriber or a frame for all.
+enum Outgoing {
+    Subscribe(Box<Subscriber>),
+    Event(String),
+}
+
+#[derive(Default)]
+struct Snapshot {
+    current: Option<ColorJson>,
+    history: Vec<u8>,
+}
+
+/// Opt-in automation server bound to localhost. Requests a web page could
+/// forge are refused, and `/pick` needs the token from [`token_path`].
+/// Reads are answered from a snapshot the app publishes; picks are queued
+/// for the app to drain on its next tick. `/events` WebSocket subscribers
+/// are owned by a separate thread over non-blocking sockets, so a slow
+/// client never stalls the UI or the other subscribers.
+pub struct ApiServer {
+    address: SocketAddr,
+    closing: Arc<AtomicBool>,
+    snapshot: Arc<Mutex<Snapshot>>,
+    picks: mpsc::Receiver<PickRequest>,
+    events: mpsc::Sender<Outgoing>,
+}
+
+impl ApiServer {
+    pub fn start(port: u16) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let token = load_or_create_token()?;
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let address = listener.local_addr()?;
+        let closing = Arc::new(AtomicBool::new(false));
+        let snapshot = Arc::new(Mutex::new(Snapshot::default()));
+        let (pick_sender, picks) = mpsc::channel();
+        let (events, outgoing) = mpsc::channel();
+
+        let (thread_closing, thread_snapshot, thread_events) = (closing.clone(), snapshot.clone(), events.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_closing.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let server = Server { port, token: &token, snapshot: &thread_snapshot, picks: &pick_sender };
+                if let Err(e) = server.serve(stream, &thread_events) {
+                    eprintln!("Failed to serve an API request: {}", e);
+                }
+            }
+        });
+
+        std::thread::spawn(move || {
+            let mut subscribers: Vec<Subscriber> = Vec::new();
+            loop {
+                let event = match outgoing.recv_timeout(EVENT_POLL) {
+                    Ok(Outgoing::Subscribe(socket)) => {
+                        subscribers.push(*socket);
+                        None
+                    },
+                    Ok(Outgoing::Event(event)) => Some(event),
+                    Err(mpsc::RecvTimeoutError::Timeout) => None,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                };
+                subscribers.retain_mut(|socket| service(socket, event.as_deref()));
+            }
+        });
+
+        Ok(Self { address, closing, snapshot, picks, events })
+    }
+
+    pub fn broadcast_pick(&self, color: &ColorJson) {
+        match serde_json::to_string(&PickEvent { event: "freeze", color }) {
+            Ok(event) => {
+                let _ = self.events.send(Outgoing::Event(event));
+            },
+            Err(e) => eprintln!("Failed to serialize pick event: {}", e),
+        }
+    }
+
+    pub fn publish_current(&self, current: Option<ColorJson>) {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.current = current;
+        }
+    }
+
+    pub fn publish_history(&self, history: &[HistoryEntry]) {
+        match export::export_history(HistoryFormat::Json, history) {
+            Ok(json) => {
+                if let Ok(mut snapshot) = self.snapshot.lock() {
+                    snapshot.history = json;
+                }
+            },
+            Err(e) => eprintln!("Failed to serialize history for the API: {}", e),
+        }
+    }
+
+    pub fn pending_picks(&self) -> impl Iterator<Item = PickRequest> + '_ {
+        self.picks.try_iter()
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.closing.store(true, Ordering::Relaxed);
+        // Wake the accept loop so it sees the flag and releases the port.
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+/// Reads what a subscriber sent, which answers pings and notices closes,
+/// then sends or flushes `event`. Returns whether to keep the subscriber.
+fn service(socket: &mut Subscriber, event: Option<&str>) -> bool {
+    let would_block = |error: &tungstenite::Error| matches!(error, tungstenite::Error::Io(e) if e.kind() == std::io::ErrorKind::WouldBlock);
+    loop {
+        match socket.read() {
+            Ok(_) => {},
+            Err(e) if would_block(&e) => break,
+            Err(_) => return false,
+        }
+    }
+    let result = match event {
+        Some(event) => socket.send(tungstenite::Message::text(event)),
+        None => socket.flush(),
+    };
+    result.is_ok() || result.as_ref().is_err_and(would_block)
+}
+
+/// A request as far as the API needs it: no endpoint takes a body.
+struct Request {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Reads the request line and headers, stopping at the blank line.
+    fn read(reader: &mut impl BufRead) -> std::io::Result<Self> {
+        let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed HTTP request");
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        let (method, target) = (method.to_string(), target.to_string());
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid());
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                return Ok(Self { method, target, headers });
+            }
+            let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(field, _)| field.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    fn path_and_query(&self) -> (&str, &str) {
+        self.target.split_once('?').unwrap_or((&self.target, ""))
+    }
+}
+
+/// Where the token for `/pick` is kept; callers send it as
+/// `Authorization: Bearer <token>`.
+pub fn token_path() -> Option<std::path::PathBuf> {
+    Settings::get_settings_path().map(|path| path.with_file_name("api-token"))
+}
+
+/// Reads the API token, writing a new random one readable only by the user
+/// on first use.
+fn load_or_create_token() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let path = token_path().ok_or("no configuration directory for the API token")?;
+    if let Ok(token) = std::fs::read_to_string(&path)
+        && !token.trim().is_empty()
+    {
+        return Ok(token.trim().to_string());
+    }
+
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)?;
+    let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&path)?.write_all(token.as_bytes())?;
+    Ok(token)
+}
+
+/// Browsers attach `Origin` to cross-site fetches and WebSocket upgrades, and
+/// a DNS-rebound page still names its own host, so only requests without an
+/// `Origin` that address the loopback port directly are served.
+fn is_local(host: Option<&str>, origin: Option<&str>, port: u16) -> bool {
+    let host_allowed =
+        host.is_some_and(|host| host == format!("127.0.0.1:{}", port) || host == format!("localhost:{}", port));
+    host_allowed && origin.is_none()
+}
+
+/// What the accept loop shares with each request it serves.
+struct Server<'a> {
+    port: u16,
+    token: &'a str,
+    snapshot: &'a Mutex<Snapshot>,
+    picks: &'a mpsc::Sender<PickRequest>,
+}
+
+impl Server<'_> {
+    fn serve(&self, stream: TcpStream, events: &mpsc::Sender<Outgoing>) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_HEAD));
+        let request = Request::read(&mut reader)?;
+        let (path, query) = request.path_and_query();
+
+        if !is_local(request.header("Host"), request.header("Origin"), self.port) {
+            return respond(stream, 403, br#"{"error":"only local clients without an Origin may connect"}"#);
+        }
+        if path == "/events" {
+            let buffered = reader.buffer().to_vec();
+            return subscribe(&request, stream, buffered, events);
+        }
+
+        let (status, body) = self.handle(&request, path, query);
+        respond(stream, status, &body)
+    }
+
+    fn handle(&self, request: &Request, path: &str, query: &str) -> (u16, Vec<u8>) {
+        match (request.method.as_str(), path) {
+            ("GET", "/color/current") => {
+                match self.snapshot.lock().ok().and_then(|snapshot| snapshot.current.clone()) {
+                    Some(color) => json(&color),
+                    None => (404, r#"{"error":"no color captured yet"}"#.into()),
+                }
+            },
+            ("GET", "/history") => {
+                (200, self.snapshot.lock().map(|snapshot| snapshot.history.clone()).unwrap_or_default())
+            },
+            ("POST", "/pick")
+                if request.header("Authorization").and_then(|value| value.strip_prefix("Bearer "))
+                    != Some(self.token) =>
+            {
+                (401, r#"{"error":"expected the token from the settings directory as a Bearer token"}"#.into())
+            },
+            ("POST", "/pick") => match parse_position(query) {
+                Some(position) => {
+                    let (request, response) = PickRequest::new(Some(position));
+                    let _ = self.picks.send(request);
+                    match response.recv_timeout(PICK_TIMEOUT) {
+                        Ok(Some(color)) => json(&color),
+                        Ok(None) => (500, r#"{"error":"capture failed"}"#.into()),
+                        Err(_) => (503, r#"{"error":"picker did not respond"}"#.into()),
+                    }
+                },
+                None => (400, r#"{"error":"expected ?x=<int>&y=<int>"}"#.into()),
+            },
+            _ => (404, r#"{"error":"not found"}"#.into()),
+        }
+    }
+}
+
+fn respond(mut stream: TcpStream, status: u16, body: &[u8]) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        body.len()
+    )?;
+    stream.write_all(body)
+}
+
+/// Completes the WebSocket handshake for `/events` and hands the socket,
+/// now non-blocking, to the events thread. `buffered` holds anything the
+/// client sent after its request head.
+fn subscribe(
+    request: &Request,
+    mut stream: TcpStream,
+    buffered: Vec<u8>,
+    events: &mpsc::Sender<Outgoing>,
+) -> std::io::Result<()> {
+    let Some(key) = request.header("Sec-WebSocket-Key") else {
+        return respond(stream, 400, br#"{"error":"expected a WebSocket upgrade"}"#);
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        tungstenite::handshake::derive_accept_key(key.as_bytes())
+    )?;
+    stream.set_nonblocking(true)?;
+    let config = tungstenite::protocol::WebSocketConfig::default().max_write_buffer_size(MAX_SUBSCRIBER_BACKLOG);
+    let socket = tungstenite::WebSocket::from_partially_read(
+        stream,
+        buffered,
+        tungstenite::protocol::Role::Server,
+        Some(config),
+    );
+    let _ = events.send(Outgoing::Subscribe(Box::new(socket)));
+    Ok(())
+}
+
+fn json(value: &impl Serialize) -> (u16, Vec<u8>) {
+    match serde_json::to_vec(value) {
+        Ok(body) => (200, body),
+        Err(_) => (500, r#"{"error":"serialization failed"}"#.into()),
+    }
+}
+
+fn parse_position(query: &str) -> Option<(i32, i32)> {
+    let (mut x, mut y) = (None, None);
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            Some(("x", value)) => x = value.parse().ok(),
+            Some(("y", value)) => y = value.parse().ok(),
+            _ => {},
+        }
+    }
+    Some((x?, y?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pick_query_needs_both_coordinates() {
+        assert_eq!(parse_position("x=10&y=-20"), Some((10, -20)));
+        assert_eq!(parse_position("y=5&x=3&extra=1"), Some((3, 5)));
+        assert_eq!(parse_position("x=10"), None);
+        assert_eq!(parse_position("x=ten&y=1"), None);
+    }
+
+    #[test]
+    fn request_heads_split_the_query_from_the_path() {
+        let head = "GET /events?client=deck HTTP/1.1\r\nHost: localhost:7331\r\nsec-websocket-key: abc\r\n\r\n";
+        let request = Request::read(&mut head.as_bytes()).unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path_and_query(), ("/events", "client=deck"));
+        assert_eq!(request.header("Sec-WebSocket-Key"), Some("abc"));
+        assert_eq!(request.header("Origin"), None);
+        assert!(Request::read(&mut "GET /history HTTP/1.1\r\nHost: localhost\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn only_direct_loopback_requests_are_local() {
+        assert!(is_local(Some("127.0.0.1:7331"), None, 7331));
+        assert!(is_local(Some("localhost:7331"), None, 7331));
+        assert!(!is_local(Some("localhost:7331"), Some("https://example.com"), 7331));
+        assert!(!is_local(Some("attacker.example:7331"), None, 7331));
+        assert!(!is_local(Some("localhost:8080"), None, 7331));
+        assert!(!is_local(None, None, 7331));
+    }
+}
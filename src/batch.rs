@@ -0,0 +1,115 @@
+use crate::error::PeekerError;
+use crate::{MonitorBounds, Settings, capture, color_manage, export, icc};
+use iced::Color;
+use std::io::Read;
+use xcap::Monitor;
+
+/// Parses one coordinate per line as `x,y` or `x y`. Blank lines and lines
+/// starting with `#` are skipped.
+pub fn parse_coordinates(input: &str) -> Result<Vec<(i32, i32)>, String> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            let mut parts = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty());
+            match (parts.next().map(str::parse), parts.next().map(str::parse), parts.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => Ok((x, y)),
+                _ => Err(format!("line {}: expected 'x,y', got '{}'", index + 1, line.trim())),
+            }
+        })
+        .collect()
+}
+
+/// `pixel-peeker batch <file|->` or `pixel-peeker batch <x,y>...`: samples
+/// every coordinate and prints a CSV table of positions and colors. Each
+/// monitor is captured at most once, so all points come from the same frame.
+/// Points that cannot be sampled get an empty row and make the run fail.
+/// Colors are converted with the monitors' profiles when the app would do so.
+pub fn run(args: &[String]) -> Result<(), PeekerError> {
+    let input = match args {
+        [] => {
+            return Err(PeekerError::InvalidInput(
+                "usage: pixel-peeker batch <file|-> or pixel-peeker batch <x,y>...".to_string(),
+            ));
+        },
+        [path] if path == "-" => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            input
+        },
+        [path] if std::path::Path::new(path).is_file() => {
+            std::fs::read_to_string(path).map_err(|source| PeekerError::ReadFile { path: path.into(), source })?
+        },
+        points => points.join("\n"),
+    };
+    let coordinates = parse_coordinates(&input).map_err(PeekerError::InvalidInput)?;
+
+    let mut monitors: Vec<(Monitor, Option<xcap::image::RgbaImage>)> =
+        Monitor::all()?.into_iter().map(|monitor| (monitor, None)).collect();
+
+    let settings = Settings::load();
+    let mut profiles = icc::Profiles::default();
+
+    println!("x,y,hex,r,g,b");
+    let mut failed = 0;
+    for &(x, y) in &coordinates {
+        let color = sample(&mut monitors, x, y, &settings, &mut profiles);
+        if let Some(e) = profiles.take_error() {
+            eprintln!("{}", e);
+        }
+        match color {
+            Some(color) => {
+                let [r, g, b, _] = color.into_rgba8();
+                println!("{},{},{},{},{},{}", x, y, export::to_hex(color), r, g, b);
+            },
+            None => {
+                println!("{},{},,,,", x, y);
+                failed += 1;
+            },
+        }
+    }
+    if failed > 0 {
+        return Err(PeekerError::BatchIncomplete { failed, total: coordinates.len() });
+    }
+    Ok(())
+}
+
+fn sample(
+    monitors: &mut [(Monitor, Option<xcap::image::RgbaImage>)],
+    x: i32,
+    y: i32,
+    settings: &Settings,
+    profiles: &mut icc::Profiles,
+) -> Option<Color> {
+    let (monitor, image) = monitors
+        .iter_mut()
+        .find(|(monitor, _)| MonitorBounds::from_monitor(monitor).is_some_and(|bounds| bounds.contains(x, y)))?;
+
+    if image.is_none() {
+        *image = capture::capture_monitor(monitor)
+            .inspect_err(|e| eprintln!("Failed to capture monitor: {}", e))
+            .ok()
+            .map(|mut image| {
+                color_manage(&mut image, monitor, settings, profiles);
+                image
+            });
+    }
+    let image = image.as_ref()?;
+    let (left, top) = ((x - monitor.x().ok()?) as u32, (y - monitor.y().ok()?) as u32);
+    let pixel = image.get_pixel_checked(left, top)?;
+    Some(Color::from_rgb8(pixel[0], pixel[1], pixel[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn coordinate_lines() {
+        assert_eq!(parse_coordinates("# header\n10,20\n\n -5 7\n3, 4\n"), Ok(vec![(10, 20), (-5, 7), (3, 4)]));
+        assert_eq!(parse_coordinates("1,2\n1,2,3"), Err("line 2: expected 'x,y', got '1,2,3'".to_string()));
+        assert_eq!(parse_coordinates("a,b"), Err("line 1: expected 'x,y', got 'a,b'".to_string()));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::error::PeekerError;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use xcap::Monitor;
+use xcap::image::{RgbaImage, imageops};
+
+/// Consecutive live-capture failures before the error banner is shown.
+pub const REPORT_AFTER: u32 = 3;
+const FIRST_RETRY_DELAY: Duration = Duration::from_millis(100);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// Completely black captures in a row after which the display is assumed
+/// to be one capture cannot see into, about three seconds of live picking.
+const BLACK_FRAME_LIMIT: u32 = 90;
+/// How many cursor positions, each at least [`BLACK_POSITION_SPACING`]
+/// pixels from the others, those captures must come from.
+const BLACK_POSITIONS: usize = 3;
+const BLACK_POSITION_SPACING: i32 = 64;
+/// Larger than any real monitor in either direction.
+const MAX_PLAUSIBLE_SIZE: u32 = 32_768;
+/// How long a whole-monitor capture is reused for region captures: just under
+/// one live capture tick, so a tick that samples several regions of a rotated
+/// monitor (the preview, a measurement line, a watch) captures it only once.
+const FRAME_REUSE: Duration = Duration::from_millis(30);
+
+struct CachedFrame {
+    monitor: u32,
+    taken: Instant,
+    image: RgbaImage,
+}
+
+/// The last whole-monitor capture made for a region, upright.
+static LAST_FRAME: Mutex<Option<CachedFrame>> = Mutex::new(None);
+
+/// A monitor as offered for manual selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Display {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Why capture is likely to be wrong on this machine, shown in the status line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayIssue {
+    NoMonitors,
+    /// A monitor with an empty or implausible size, as virtual displays can report.
+    OddBounds(String),
+    /// Captures keep coming back black, as under many RDP, VNC and headless sessions.
+    BlackFrames,
+}
+
+pub fn list_displays() -> Vec<Display> {
+    let monitors = Monitor::all().inspect_err(|e| eprintln!("Failed to list monitors: {}", e)).unwrap_or_default();
+    monitors
+        .iter()
+        .filter_map(|monitor| {
+            Some(Display { name: monitor.name().ok()?, width: monitor.width().ok()?, height: monitor.height().ok()? })
+        })
+        .collect()
+}
+
+/// `blank` is whether live captures keep coming back black; see [`BlackFrames`].
+pub fn diagnose(displays: &[Display], blank: bool) -> Option<DisplayIssue> {
+    let odd = |size: u32| size == 0 || size > MAX_PLAUSIBLE_SIZE;
+    if displays.is_empty() {
+        Some(DisplayIssue::NoMonitors)
+    } else if let Some(display) = displays.iter().find(|display| odd(display.width) || odd(display.height)) {
+        Some(DisplayIssue::OddBounds(display.name.clone()))
+    } else {
+        blank.then_some(DisplayIssue::BlackFrames)
+    }
+}
+
+/// Entirely black live captures in a row and where they were taken. A dark
+/// image or a black window under a resting cursor captures black as well,
+/// so the display only counts as blank once black came from several places.
+#[derive(Debug, Default)]
+pub struct BlackFrames {
+    count: u32,
+    positions: Vec<(i32, i32)>,
+}
+
+impl BlackFrames {
+    pub fn record(&mut self, position: (i32, i32), black: bool) {
+        if !black {
+            self.reset();
+            return;
+        }
+        self.count = self.count.saturating_add(1);
+        let apart = |&(x, y): &(i32, i32)| (x - position.0).abs().max((y - position.1).abs()) >= BLACK_POSITION_SPACING;
+        if self.positions.len() < BLACK_POSITIONS && self.positions.iter().all(apart) {
+            self.positions.push(position);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.count >= BLACK_FRAME_LIMIT && self.positions.len() >= BLACK_POSITIONS
+    }
+}
+
+/// Captures a region of `monitor`, given relative to its top-left corner in
+/// the orientation it is displayed in. Some drivers reject region captures
+/// (secure windows, a display being reconfigured), so on failure the whole
+/// monitor is captured and cropped. That capture is shared by the region
+/// captures of the next [`FRAME_REUSE`].
+pub fn capture_region(monitor: &Monitor, x: u32, y: u32, width: u32, height: u32) -> Result<RgbaImage, PeekerError> {
+    let rotation = monitor.rotation().unwrap_or(0.0);
+    // Not every backend maps region coordinates through a quarter-turn
+    // rotation, so rotated monitors are always captured whole.
+    let region_error = if is_quarter_turn(rotation) {
+        None
+    } else {
+        match monitor.capture_region(x, y, width, height) {
+            Ok(image) => return Ok(image),
+            Err(e) => Some(e),
+        }
+    };
+    // Full captures are in physical pixels, region coordinates are logical.
+    let (displayed_width, _) = oriented_size(monitor.width()?, monitor.height()?, rotation);
+    let cropped = with_full_frame(monitor, |full| {
+        let scale = full.width() as f64 / displayed_width.max(1) as f64;
+        crop(full, scale, x, y, width, height)
+    })?;
+    cropped.ok_or_else(|| match region_error {
+        Some(e) => PeekerError::from(e),
+        None => PeekerError::Capture("The region is outside the monitor".into()),
+    })
+}
+
+/// Runs `use_frame` on a whole, upright capture of `monitor`, taking a new
+/// one only if the cached frame is of another monitor or too old.
+fn with_full_frame<T>(monitor: &Monitor, use_frame: impl FnOnce(&RgbaImage) -> T) -> Result<T, PeekerError> {
+    let id = monitor.id()?;
+    let mut cached = LAST_FRAME.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let frame = match cached.take() {
+        Some(frame) if frame.monitor == id && frame.taken.elapsed() < FRAME_REUSE => frame,
+        _ => CachedFrame { monitor: id, taken: Instant::now(), image: capture_monitor(monitor)? },
+    };
+    let result = use_frame(&frame.image);
+    *cached = Some(frame);
+    Ok(result)
+}
+
+/// The whole of `monitor`, turned the way it is displayed.
+pub fn capture_monitor(monitor: &Monitor) -> Result<RgbaImage, PeekerError> {
+    let rotation = monitor.rotation().unwrap_or(0.0);
+    let displayed = oriented_size(monitor.width()?, monitor.height()?, rotation);
+    Ok(upright(monitor.capture_image()?, rotation, displayed))
+}
+
+/// Whether `rotation` (degrees, as xcap reports it) turns the monitor
+/// between landscape and portrait.
+fn is_quarter_turn(rotation: f32) -> bool {
+    matches!(rotation.rem_euclid(360.0).round() as u32, 90 | 270)
+}
+
+/// The size of a monitor as displayed. Some platforms report a rotated
+/// monitor's size in the panel's native landscape orientation.
+pub fn oriented_size(width: u32, height: u32, rotation: f32) -> (u32, u32) {
+    if is_quarter_turn(rotation) && width > height { (height, width) } else { (width, height) }
+}
+
+/// Turns a capture taken in the panel's native orientation the way the
+/// monitor is displayed, so pixels line up with cursor positions.
+fn upright(image: RgbaImage, rotation: f32, displayed: (u32, u32)) -> RgbaImage {
+    let portrait = |(width, height): (u32, u32)| height > width;
+    if !is_quarter_turn(rotation) || portrait(image.dimensions()) == portrait(displayed) {
+        return image;
+    }
+    if rotation.rem_euclid(360.0).round() as u32 == 90 {
+        imageops::rotate90(&image)
+    } else {
+        imageops::rotate270(&image)
+    }
+}
+
+/// The `width` × `height` pixels at logical `(x, y)` of a capture taken at
+/// `scale` physical pixels per logical one.
+fn crop(full: &RgbaImage, scale: f64, x: u32, y: u32, width: u32, height: u32) -> Option<RgbaImage> {
+    let left = (x as f64 * scale).round() as u32;
+    let top = (y as f64 * scale).round() as u32;
+    let fits = |start: u32, len: u32, limit: u32| len > 0 && start.checked_add(len).is_some_and(|end| end <= limit);
+    if !fits(left, width, full.width()) || !fits(top, height, full.height()) {
+        return None;
+    }
+    Some(imageops::crop_imm(full, left, top, width, height).to_image())
+}
+
+/// Live-capture failures in a row and when to try again. The wait doubles
+/// with each failure so a broken capture does not retry on every tick.
+#[derive(Debug, Default)]
+pub struct Backoff {
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl Backoff {
+    pub fn ready(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|at| now >= at)
+    }
+
+    /// Records a failure; returns `true` when it is the one that makes the
+    /// failure persistent and so worth reporting.
+    pub fn failed(&mut self, now: Instant) -> bool {
+        self.failures += 1;
+        self.retry_at = Some(now + retry_delay(self.failures));
+        self.failures == REPORT_AFTER
+    }
+
+    pub fn succeeded(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether capture has failed often enough to have been reported.
+    pub fn is_failing(&self) -> bool {
+        self.failures >= REPORT_AFTER
+    }
+}
+
+fn retry_delay(failures: u32) -> Duration {
+    FIRST_RETRY_DELAY.saturating_mul(1 << failures.saturating_sub(1).min(8)).min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use xcap::image::Rgba;
+
+    #[test]
+    fn retries_back_off_and_report_once() {
+        let delays: Vec<u128> = (1..=7).map(|failures| retry_delay(failures).as_millis()).collect();
+        assert_eq!(delays, [100, 200, 400, 800, 1600, 2000, 2000]);
+
+        let start = Instant::now();
+        let mut backoff = Backoff::default();
+        let reported: Vec<bool> = (0..5).map(|_| backoff.failed(start)).collect();
+        assert_eq!(reported, [false, false, true, false, false]);
+        assert!(backoff.is_failing());
+        assert!(!backoff.ready(start));
+        assert!(backoff.ready(start + MAX_RETRY_DELAY));
+
+        backoff.succeeded();
+        assert!(!backoff.is_failing() && backoff.ready(start));
+    }
+
+    #[test]
+    fn fallback_crops_scale_to_physical_pixels() {
+        let full = RgbaImage::from_fn(8, 8, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+
+        let cropped = crop(&full, 1.0, 2, 3, 4, 4).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (4, 4));
+        assert_eq!(cropped.get_pixel(0, 0), &Rgba([2, 3, 0, 255]));
+
+        assert_eq!(crop(&full, 2.0, 2, 1, 3, 3).unwrap().get_pixel(0, 0), &Rgba([4, 2, 0, 255]));
+        assert!(crop(&full, 2.0, 3, 0, 3, 3).is_none());
+        assert!(crop(&full, 1.0, 0, 0, 0, 4).is_none());
+    }
+
+    #[test]
+    fn virtual_displays_are_diagnosed() {
+        let display = |name: &str, width, height| Display { name: name.into(), width, height };
+        let normal = [display("DP-1", 2560, 1440), display("HDMI-1", 1080, 1920)];
+        assert_eq!(diagnose(&normal, false), None);
+        assert_eq!(diagnose(&normal, true), Some(DisplayIssue::BlackFrames));
+        assert_eq!(diagnose(&[], false), Some(DisplayIssue::NoMonitors));
+        assert_eq!(
+            diagnose(&[display("DP-1", 2560, 1440), display("rdp0", 0, 768)], true),
+            Some(DisplayIssue::OddBounds("rdp0".into()))
+        );
+        assert_eq!(diagnose(&[display("vnc", 65_536, 1024)], false), Some(DisplayIssue::OddBounds("vnc".into())));
+    }
+
+    #[test]
+    fn black_frames_must_come_from_several_places() {
+        let mut frames = BlackFrames::default();
+        for _ in 0..BLACK_FRAME_LIMIT * 2 {
+            frames.record((100, 100), true);
+        }
+        frames.record((120, 130), true);
+        assert!(!frames.is_blank(), "a black window under a resting cursor");
+
+        frames.record((400, 100), true);
+        frames.record((100, 400), true);
+        assert!(frames.is_blank());
+
+        frames.record((100, 400), false);
+        assert!(!frames.is_blank());
+    }
+
+    #[test]
+    fn rotated_monitors_are_read_upright() {
+        assert_eq!(oriented_size(1920, 1080, 90.0), (1080, 1920));
+        assert_eq!(oriented_size(1080, 1920, -90.0), (1080, 1920));
+        assert_eq!(oriented_size(1920, 1080, 180.0), (1920, 1080));
+
+        let native = RgbaImage::from_fn(4, 2, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+        let turned = upright(native.clone(), 90.0, (2, 4));
+        assert_eq!(turned.dimensions(), (2, 4));
+        // A clockwise quarter turn puts the native bottom-left corner at the top left.
+        assert_eq!(turned.get_pixel(0, 0), &Rgba([0, 1, 0, 255]));
+        assert_eq!(upright(native.clone(), 270.0, (2, 4)).get_pixel(0, 0), &Rgba([3, 0, 0, 255]));
+        assert_eq!(upright(native, 90.0, (4, 2)).dimensions(), (4, 2));
+    }
+}
@@ -0,0 +1,69 @@
+/// Scores `candidate` against a fuzzy `query`: every query character must
+/// appear in order, ignoring case. Higher is better; runs of consecutive
+/// characters and matches at the start of a word score extra.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + candidate[next..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous_match == Some(found.wrapping_sub(1)) {
+            score += 4;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(found);
+        next = found + 1;
+    }
+
+    // Prefer shorter labels when the matches are otherwise equal.
+    Some(score * 100 - candidate.len() as i32)
+}
+
+/// Keeps the commands whose label matches `query`, best match first. An
+/// empty query keeps every command in its original order.
+pub fn rank<T>(query: &str, commands: Vec<(String, T)>) -> Vec<(String, T)> {
+    if query.trim().is_empty() {
+        return commands;
+    }
+    let mut scored: Vec<(i32, (String, T))> = commands
+        .into_iter()
+        .filter_map(|command| fuzzy_score(query, &command.0).map(|score| (score, command)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn characters_must_appear_in_order() {
+        assert!(fuzzy_score("cphex", "Copy as Hex").is_some());
+        assert!(fuzzy_score("CAH", "Copy as Hex").is_some());
+        assert_eq!(fuzzy_score("xeh", "Copy as Hex"), None);
+        assert_eq!(fuzzy_score("hexx", "Copy as Hex"), None);
+    }
+
+    #[test]
+    fn word_starts_and_runs_rank_first() {
+        let commands = vec![
+            ("Show appearance settings".to_string(), 0),
+            ("Copy as HSL".to_string(), 1),
+            ("Copy as Hex".to_string(), 2),
+            ("Export history".to_string(), 3),
+        ];
+
+        let ranked: Vec<i32> = rank("hex", commands.clone()).into_iter().map(|(_, id)| id).collect();
+        assert_eq!(ranked, vec![2]);
+        let ranked: Vec<i32> = rank("hs", commands.clone()).into_iter().map(|(_, id)| id).collect();
+        assert_eq!(ranked, vec![1, 3, 0]);
+        assert_eq!(rank(" ", commands.clone()), commands);
+    }
+}
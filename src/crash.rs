@@ -0,0 +1,125 @@
+use crate::{SerializableColor, Settings, history_store, write_json};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// How often the latest state is copied to the autosave file.
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Everything the app would write on its next save.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub settings: Settings,
+    /// The active workspace's history, which lives outside the settings file.
+    pub history: Vec<SerializableColor>,
+}
+
+/// The most recent snapshot, written by the panic hook if the app crashes
+/// before its next regular save.
+static STAGED: Mutex<Option<Snapshot>> = Mutex::new(None);
+
+/// Chains a panic hook that saves the staged snapshot before the default hook
+/// prints the panic.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let staged = STAGED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(snapshot) = staged {
+            save(&snapshot);
+        }
+        default_hook(info);
+    }));
+}
+
+pub fn stage(snapshot: Snapshot) {
+    *STAGED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(snapshot);
+}
+
+/// Copies the staged snapshot to the autosave file, which [`recover`] reads
+/// back after a crash the panic hook could not catch.
+pub fn autosave() {
+    if let Some(path) = autosave_path() {
+        autosave_to(&path);
+    }
+}
+
+fn autosave_to(path: &Path) {
+    let snapshot = STAGED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    if let Some(snapshot) = snapshot
+        && let Err(e) = write_json(path, &snapshot)
+    {
+        eprintln!("Autosave failed: {}", e);
+    }
+}
+
+/// Called on a clean exit once everything is saved: removes the autosave
+/// file, which would otherwise offer already saved changes at the next
+/// launch, and drops the staged snapshot so nothing writes it again.
+pub fn clean_exit() {
+    if let Some(path) = autosave_path() {
+        clean_exit_at(&path);
+    }
+}
+
+fn clean_exit_at(path: &Path) {
+    STAGED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+    if path.exists()
+        && let Err(e) = std::fs::remove_file(path)
+    {
+        eprintln!("Failed to remove autosave {}: {}", path.display(), e);
+    }
+}
+
+/// The autosave, if it was written after `file` last changed or `file` is
+/// missing; a clean exit removes it, so one that is left over holds changes
+/// that never reached the regular files.
+pub fn recover(file: Option<&Path>) -> Option<Snapshot> {
+    recover_from(&autosave_path()?, file)
+}
+
+fn recover_from(path: &Path, file: Option<&Path>) -> Option<Snapshot> {
+    let autosaved = modified(path)?;
+    if file.and_then(modified).is_some_and(|saved| saved >= autosaved) {
+        return None;
+    }
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).inspect_err(|e| eprintln!("Failed to read the autosave: {}", e)).ok()
+}
+
+fn autosave_path() -> Option<std::path::PathBuf> {
+    Settings::get_settings_path().map(|path| path.with_file_name("autosave.json"))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn save(snapshot: &Snapshot) {
+    if let Err(e) = snapshot.settings.save() {
+        eprintln!("Failed to save settings after a crash: {}", e);
+    }
+    if let Err(e) = history_store::save(snapshot.settings.workspace.as_deref(), &snapshot.history) {
+        eprintln!("Failed to save the history after a crash: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_clean_exit_leaves_no_autosave_behind() {
+        let path = std::env::temp_dir().join(format!("pixel-peeker-autosave-{}.json", std::process::id()));
+        stage(Snapshot { settings: Settings::default(), history: Vec::new() });
+        autosave_to(&path);
+        assert!(recover_from(&path, None).is_some());
+
+        clean_exit_at(&path);
+        assert!(!path.exists());
+        // A tick that autosaves after the final save has nothing left to write.
+        autosave_to(&path);
+        assert!(!path.exists());
+        assert!(recover_from(&path, None).is_none());
+    }
+}
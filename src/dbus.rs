@@ -0,0 +1,83 @@
+use crate::api::{ColorJson, PICK_TIMEOUT, PickRequest};
+use std::sync::{Arc, Mutex, mpsc};
+
+const BUS_NAME: &str = "com.kdheepak.PixelPeeker";
+const OBJECT_PATH: &str = "/com/kdheepak/PixelPeeker";
+
+struct PeekerInterface {
+    current: Arc<Mutex<Option<ColorJson>>>,
+    picks: mpsc::Sender<PickRequest>,
+}
+
+#[zbus::interface(name = "com.kdheepak.PixelPeeker")]
+impl PeekerInterface {
+    /// Freezes the pixel under the cursor and returns its hex value. The
+    /// wait happens off the bus executor, so other calls are answered meanwhile.
+    async fn pick_color(&self) -> zbus::fdo::Result<String> {
+        let (request, response) = PickRequest::new(None);
+        self.picks.send(request).map_err(|_| zbus::fdo::Error::Failed("picker is not running".into()))?;
+        let (reply, picked) = iced::futures::channel::oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = reply.send(response.recv_timeout(PICK_TIMEOUT));
+        });
+        match picked.await {
+            Ok(Ok(Some(color))) => Ok(color.hex().to_string()),
+            Ok(Ok(None)) => Err(zbus::fdo::Error::Failed("capture failed".into())),
+            _ => Err(zbus::fdo::Error::Failed("picker did not respond".into())),
+        }
+    }
+
+    /// Hex value of the frozen color, or of the live color when nothing is frozen.
+    fn get_current_color(&self) -> zbus::fdo::Result<String> {
+        self.current
+            .lock()
+            .ok()
+            .and_then(|current| current.as_ref().map(|color| color.hex().to_string()))
+            .ok_or_else(|| zbus::fdo::Error::Failed("no color captured yet".into()))
+    }
+
+    #[zbus(signal)]
+    async fn color_picked(emitter: &zbus::object_server::SignalEmitter<'_>, hex: &str) -> zbus::Result<()>;
+}
+
+/// The `com.kdheepak.PixelPeeker` session bus service. Like the HTTP API,
+/// reads come from a published snapshot and picks are drained by the app on
+/// its next tick.
+pub struct DbusService {
+    connection: zbus::blocking::Connection,
+    current: Arc<Mutex<Option<ColorJson>>>,
+    picks: mpsc::Receiver<PickRequest>,
+}
+
+impl DbusService {
+    pub fn start() -> zbus::Result<Self> {
+        let current = Arc::new(Mutex::new(None));
+        let (pick_sender, picks) = mpsc::channel();
+        let interface = PeekerInterface { current: current.clone(), picks: pick_sender };
+
+        let connection = zbus::blocking::connection::Builder::session()?
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, interface)?
+            .build()?;
+
+        Ok(Self { connection, current, picks })
+    }
+
+    pub fn publish_current(&self, color: Option<ColorJson>) {
+        if let Ok(mut current) = self.current.lock() {
+            *current = color;
+        }
+    }
+
+    pub fn emit_color_picked(&self, color: &ColorJson) {
+        let result = zbus::object_server::SignalEmitter::new(self.connection.inner(), OBJECT_PATH)
+            .and_then(|emitter| zbus::block_on(PeekerInterface::color_picked(&emitter, color.hex())));
+        if let Err(e) = result {
+            eprintln!("Failed to emit ColorPicked signal: {}", e);
+        }
+    }
+
+    pub fn pending_picks(&self) -> impl Iterator<Item = PickRequest> + '_ {
+        self.picks.try_iter()
+    }
+}
@@ -0,0 +1,68 @@
+use crate::metrics;
+use iced::Color;
+
+/// Pixels whose ΔE is at or below this count as unchanged.
+pub const CHANGE_THRESHOLD: f32 = 1.0;
+
+/// Per-pixel ΔE between two same-sized RGB previews.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewDiff {
+    pub width: u32,
+    pub height: u32,
+    pub delta_e: Vec<f32>,
+}
+
+impl PreviewDiff {
+    /// `None` when the previews differ in size or are not packed RGB.
+    pub fn between(a: &[u8], b: &[u8], width: u32, height: u32) -> Option<Self> {
+        let pixels = (width * height) as usize;
+        if a.len() != pixels * 3 || b.len() != pixels * 3 {
+            return None;
+        }
+        let delta_e = a
+            .chunks_exact(3)
+            .zip(b.chunks_exact(3))
+            .map(|(a, b)| {
+                if a == b {
+                    0.0
+                } else {
+                    metrics::delta_e(Color::from_rgb8(a[0], a[1], a[2]), Color::from_rgb8(b[0], b[1], b[2]))
+                }
+            })
+            .collect();
+        Some(Self { width, height, delta_e })
+    }
+
+    pub fn at(&self, x: u32, y: u32) -> Option<f32> {
+        (x < self.width).then(|| self.delta_e.get((y * self.width + x) as usize).copied()).flatten()
+    }
+
+    pub fn changed(&self) -> usize {
+        self.delta_e.iter().filter(|delta| **delta > CHANGE_THRESHOLD).count()
+    }
+
+    pub fn max(&self) -> f32 {
+        self.delta_e.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn only_changed_pixels_count() {
+        let a = [0, 0, 0, 255, 255, 255, 10, 10, 10, 50, 50, 50];
+        let b = [0, 0, 0, 255, 0, 0, 10, 10, 11, 50, 50, 50];
+        let diff = PreviewDiff::between(&a, &b, 2, 2).unwrap();
+
+        assert_eq!(diff.changed(), 1);
+        assert_eq!(diff.at(0, 0), Some(0.0));
+        assert!(diff.at(1, 0).unwrap() > 10.0);
+        assert!(diff.at(0, 1).unwrap() < CHANGE_THRESHOLD);
+        assert_eq!(diff.at(2, 0), None);
+        assert_eq!(diff.max(), diff.at(1, 0).unwrap());
+        assert_eq!(PreviewDiff::between(&a, &b[..9], 2, 2), None);
+    }
+}
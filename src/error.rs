@@ -0,0 +1,127 @@
+use crate::i18n;
+use std::path::PathBuf;
+
+/// Failures worth telling the user about: screen capture, reading and
+/// writing files, the clipboard and the command-line modes. Shown in the
+/// error banner.
+#[derive(Debug, thiserror::Error)]
+pub enum PeekerError {
+    #[error("Screen capture failed: {0}")]
+    Capture(String),
+    #[error("({x}, {y}) is outside every monitor")]
+    OffScreen { x: i32, y: i32 },
+    #[error("Screen capture is not permitted ({0}); allow screen recording for Pixel Peeker and restart it")]
+    Permission(String),
+    #[error("Could not read {}: {source}", .path.display())]
+    ReadFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Could not write {}: {source}", .path.display())]
+    WriteFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A file that was read but holds nothing usable.
+    #[error("{}: {reason}", .path.display())]
+    InvalidFile { path: PathBuf, reason: String },
+    #[error("Could not encode the export: {0}")]
+    Export(String),
+    /// Standard input and output of the command-line modes.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Malformed arguments to a command-line mode or MCP tool.
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("Could not start the ruler overlay: {0}")]
+    Ruler(#[source] std::io::Error),
+    #[error("{failed} of {total} points could not be sampled")]
+    BatchIncomplete { failed: usize, total: usize },
+    #[error("Could not encode settings: {0}")]
+    SettingsFormat(#[from] serde_json::Error),
+    #[error("Could not determine where to store {0}")]
+    NoConfigDir(&'static str),
+    #[error("Clipboard: {0}")]
+    Clipboard(String),
+    #[error("Could not check for updates: {0}")]
+    UpdateCheck(String),
+    #[error("Could not use the color profile: {0}")]
+    ColorProfile(String),
+    /// A plugin that failed or ran too long, and is turned off until restart.
+    #[error("Plugin {name} failed and was turned off: {reason}")]
+    Plugin { name: String, reason: String },
+}
+
+impl PeekerError {
+    /// The message in the user's language, for the error banner. `Display`
+    /// stays English for logs and the command-line modes.
+    pub fn localized(&self, i18n: &i18n::Translator) -> String {
+        let path = |path: &PathBuf| path.display().to_string();
+        let (id, args): (&str, Vec<(&str, fluent_bundle::FluentValue<'_>)>) = match self {
+            Self::Capture(reason) => ("error-capture", vec![("reason", reason.as_str().into())]),
+            Self::OffScreen { x, y } => ("error-off-screen", vec![("x", (*x).into()), ("y", (*y).into())]),
+            Self::Permission(reason) => ("error-permission", vec![("reason", reason.as_str().into())]),
+            Self::ReadFile { path: file, source } => {
+                ("error-read-file", vec![("path", path(file).into()), ("reason", source.to_string().into())])
+            },
+            Self::WriteFile { path: file, source } => {
+                ("error-write-file", vec![("path", path(file).into()), ("reason", source.to_string().into())])
+            },
+            Self::InvalidFile { path: file, reason } => {
+                ("error-invalid-file", vec![("path", path(file).into()), ("reason", reason.as_str().into())])
+            },
+            Self::Export(reason) => ("error-export", vec![("reason", reason.as_str().into())]),
+            Self::Io(source) => ("error-io", vec![("reason", source.to_string().into())]),
+            Self::InvalidInput(reason) => ("error-invalid-input", vec![("reason", reason.as_str().into())]),
+            Self::Ruler(source) => ("error-ruler", vec![("reason", source.to_string().into())]),
+            Self::BatchIncomplete { failed, total } => {
+                ("error-batch-incomplete", vec![("failed", (*failed).into()), ("total", (*total).into())])
+            },
+            Self::SettingsFormat(source) => ("error-settings-format", vec![("reason", source.to_string().into())]),
+            Self::NoConfigDir(what) => ("error-no-config-dir", vec![("what", (*what).into())]),
+            Self::Clipboard(reason) => ("error-clipboard", vec![("reason", reason.as_str().into())]),
+            Self::UpdateCheck(reason) => ("error-update-check", vec![("reason", reason.as_str().into())]),
+            Self::ColorProfile(reason) => ("error-color-profile", vec![("reason", reason.as_str().into())]),
+            Self::Plugin { name, reason } => {
+                ("error-plugin", vec![("name", name.as_str().into()), ("reason", reason.as_str().into())])
+            },
+        };
+        i18n.tr_with(id, &args)
+    }
+}
+
+impl From<xcap::XCapError> for PeekerError {
+    fn from(error: xcap::XCapError) -> Self {
+        // xcap reports missing screen-recording rights (macOS, Wayland portals)
+        // only through the message.
+        let message = error.to_string();
+        if message.to_lowercase().contains("permission") { Self::Permission(message) } else { Self::Capture(message) }
+    }
+}
+
+impl From<arboard::Error> for PeekerError {
+    fn from(error: arboard::Error) -> Self {
+        Self::Clipboard(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn the_banner_speaks_the_chosen_language() {
+        let error = PeekerError::BatchIncomplete { failed: 2, total: 5 };
+        assert_eq!(error.localized(&i18n::Translator::new(Some("en-US"))), error.to_string());
+        assert_eq!(
+            error.localized(&i18n::Translator::new(Some("de"))),
+            "2 von 5 Punkten konnten nicht gemessen werden"
+        );
+
+        let error = PeekerError::InvalidFile { path: PathBuf::from("palette.gpl"), reason: "No colors found".into() };
+        assert_eq!(error.localized(&i18n::Translator::new(Some("en-US"))), error.to_string());
+    }
+}
@@ -0,0 +1,1019 @@
+use crate::HistoryEntry;
+use crate::error::PeekerError;
+use iced::Color;
+use image::{Rgba, RgbaImage};
+use palette::{Hsv, IntoColor, Oklab, Srgb};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PaletteFormat {
+    Aco,
+    Gpl,
+    PlainText,
+    CssVariables,
+    ScssVariables,
+    Tailwind,
+    DesignTokens,
+    TokensStudio,
+    SketchPalette,
+    Procreate,
+    PngSheet,
+    SvgSheet,
+}
+
+impl PaletteFormat {
+    pub const ALL: &[PaletteFormat] = &[
+        PaletteFormat::Aco,
+        PaletteFormat::Gpl,
+        PaletteFormat::PlainText,
+        PaletteFormat::CssVariables,
+        PaletteFormat::ScssVariables,
+        PaletteFormat::Tailwind,
+        PaletteFormat::DesignTokens,
+        PaletteFormat::TokensStudio,
+        PaletteFormat::SketchPalette,
+        PaletteFormat::Procreate,
+        PaletteFormat::PngSheet,
+        PaletteFormat::SvgSheet,
+    ];
+
+    /// The Fluent message naming the format.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            PaletteFormat::Aco => "palette-format-aco",
+            PaletteFormat::Gpl => "palette-format-gpl",
+            PaletteFormat::PlainText => "palette-format-plain-text",
+            PaletteFormat::CssVariables => "palette-format-css",
+            PaletteFormat::ScssVariables => "palette-format-scss",
+            PaletteFormat::Tailwind => "palette-format-tailwind",
+            PaletteFormat::DesignTokens => "palette-format-design-tokens",
+            PaletteFormat::TokensStudio => "palette-format-tokens-studio",
+            PaletteFormat::SketchPalette => "palette-format-sketch",
+            PaletteFormat::Procreate => "palette-format-procreate",
+            PaletteFormat::PngSheet => "palette-format-png-sheet",
+            PaletteFormat::SvgSheet => "palette-format-svg-sheet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PaletteFormat::Aco => "aco",
+            PaletteFormat::Gpl => "gpl",
+            PaletteFormat::PlainText => "txt",
+            PaletteFormat::CssVariables => "css",
+            PaletteFormat::ScssVariables => "scss",
+            PaletteFormat::Tailwind => "js",
+            PaletteFormat::DesignTokens => "json",
+            PaletteFormat::TokensStudio => "json",
+            PaletteFormat::SketchPalette => "sketchpalette",
+            PaletteFormat::Procreate => "swatches",
+            PaletteFormat::PngSheet => "png",
+            PaletteFormat::SvgSheet => "svg",
+        }
+    }
+
+    /// Whether the output refers to colors by [`Swatch::variable`] rather than by name.
+    pub fn uses_variable_names(&self) -> bool {
+        matches!(
+            self,
+            PaletteFormat::CssVariables
+                | PaletteFormat::ScssVariables
+                | PaletteFormat::Tailwind
+                | PaletteFormat::DesignTokens
+                | PaletteFormat::TokensStudio
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Swatch {
+    pub name: String,
+    pub variable: String,
+    pub color: Color,
+    /// The local day the color was picked, if known.
+    pub date: Option<chrono::NaiveDate>,
+}
+
+/// Gives each swatch a variable name no earlier swatch has, suffixing
+/// repeats with `-2`, `-3`, … so no export drops or overwrites a color whose
+/// label happens to match another's.
+pub fn unique_variables(mut swatches: Vec<Swatch>) -> Vec<Swatch> {
+    let mut taken = std::collections::HashSet::new();
+    for swatch in &mut swatches {
+        swatch.variable = std::iter::once(swatch.variable.clone())
+            .chain((2..).map(|suffix| format!("{}-{}", swatch.variable, suffix)))
+            .find(|variable| taken.insert(css_identifier(variable)))
+            .expect("some suffix is free");
+    }
+    swatches
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    /// Expand each Tailwind color into a `50`–`900` scale around the picked shade.
+    pub tailwind_scales: bool,
+}
+
+pub fn export_palette(
+    format: PaletteFormat,
+    swatches: &[Swatch],
+    options: &ExportOptions,
+) -> Result<Vec<u8>, PeekerError> {
+    let failed = |e: &dyn std::fmt::Display| PeekerError::Export(e.to_string());
+    Ok(match format {
+        PaletteFormat::Aco => write_aco(swatches),
+        PaletteFormat::Gpl => write_gpl(swatches).into_bytes(),
+        PaletteFormat::PlainText => write_plain_text(swatches).into_bytes(),
+        PaletteFormat::CssVariables => write_css_variables(swatches).into_bytes(),
+        PaletteFormat::ScssVariables => write_scss_variables(swatches).into_bytes(),
+        PaletteFormat::Tailwind => write_tailwind(swatches, options.tailwind_scales).into_bytes(),
+        PaletteFormat::DesignTokens => write_design_tokens(swatches).map_err(|e| failed(&e))?.into_bytes(),
+        PaletteFormat::TokensStudio => write_tokens_studio(swatches).map_err(|e| failed(&e))?.into_bytes(),
+        PaletteFormat::SketchPalette => write_sketch_palette(swatches).map_err(|e| failed(&e))?.into_bytes(),
+        PaletteFormat::Procreate => write_procreate(swatches).map_err(|e| failed(&e))?,
+        PaletteFormat::PngSheet => encode_png(&render_swatch_sheet(swatches)).map_err(|e| failed(&e))?,
+        PaletteFormat::SvgSheet => write_svg_sheet(swatches).into_bytes(),
+    })
+}
+
+/// Writes an Adobe Color Swatch file.
+///
+/// Photoshop expects a version 1 section (colors only) followed by a version 2
+/// section that repeats the colors with UTF-16 names; older readers stop after
+/// the first section.
+fn write_aco(swatches: &[Swatch]) -> Vec<u8> {
+    const RGB_COLOR_SPACE: u16 = 0;
+
+    let count = swatches.len().min(u16::MAX as usize) as u16;
+    let swatches = &swatches[..count as usize];
+    let mut out = Vec::new();
+
+    for version in [1u16, 2] {
+        out.extend_from_slice(&version.to_be_bytes());
+        out.extend_from_slice(&count.to_be_bytes());
+
+        for swatch in swatches {
+            out.extend_from_slice(&RGB_COLOR_SPACE.to_be_bytes());
+            for channel in [swatch.color.r, swatch.color.g, swatch.color.b] {
+                out.extend_from_slice(&to_u16_channel(channel).to_be_bytes());
+            }
+            out.extend_from_slice(&0u16.to_be_bytes());
+
+            if version == 2 {
+                let name: Vec<u16> = swatch.name.encode_utf16().collect();
+                out.extend_from_slice(&(name.len() as u32 + 1).to_be_bytes());
+                for unit in name {
+                    out.extend_from_slice(&unit.to_be_bytes());
+                }
+                out.extend_from_slice(&0u16.to_be_bytes());
+            }
+        }
+    }
+
+    out
+}
+
+fn to_u16_channel(value: f32) -> u16 {
+    (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+}
+
+/// Writes a GIMP palette, one `R G B<TAB>name` line per swatch.
+fn write_gpl(swatches: &[Swatch]) -> String {
+    let mut out = String::from("GIMP Palette\nName: Pixel Peeker\nColumns: 0\n#\n");
+    for swatch in swatches {
+        let [r, g, b, _] = swatch.color.into_rgba8();
+        out.push_str(&format!("{:3} {:3} {:3}\t{}\n", r, g, b, swatch.name));
+    }
+    out
+}
+
+/// Writes one `#AABBCC  name  YYYY-MM-DD` line per swatch, leaving out a
+/// name that is just the hex value and an unknown date. Columns are not
+/// padded, so changing one color changes one line of a diff.
+fn write_plain_text(swatches: &[Swatch]) -> String {
+    swatches
+        .iter()
+        .map(|swatch| {
+            let hex = to_hex(swatch.color).to_uppercase();
+            let mut line = hex.clone();
+            if !swatch.name.is_empty() && !swatch.name.eq_ignore_ascii_case(&hex) {
+                line.push_str("  ");
+                line.push_str(&swatch.name);
+            }
+            if let Some(date) = swatch.date {
+                line.push_str(&format!("  {}", date.format("%Y-%m-%d")));
+            }
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+fn write_css_variables(swatches: &[Swatch]) -> String {
+    let mut out = String::from(":root {\n");
+    for swatch in swatches {
+        out.push_str(&format!("  --{}: {};\n", css_identifier(&swatch.variable), to_hex(swatch.color)));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn write_scss_variables(swatches: &[Swatch]) -> String {
+    swatches
+        .iter()
+        .map(|swatch| format!("${}: {};\n", css_identifier(&swatch.variable), to_hex(swatch.color)))
+        .collect()
+}
+
+fn write_tailwind(swatches: &[Swatch], scales: bool) -> String {
+    let mut out = String::from("theme: {\n  extend: {\n    colors: {\n");
+    for swatch in swatches {
+        let key = css_identifier(&swatch.variable);
+        if scales {
+            out.push_str(&format!("      '{}': {{\n", key));
+            for (step, color) in tailwind_scale(swatch.color) {
+                out.push_str(&format!("        {}: '{}',\n", step, to_hex(color)));
+            }
+            out.push_str("      },\n");
+        } else {
+            out.push_str(&format!("      '{}': '{}',\n", key, to_hex(swatch.color)));
+        }
+    }
+    out.push_str("    },\n  },\n},\n");
+    out
+}
+
+/// Writes a Design Tokens Community Group file with one `color` token per swatch.
+fn write_design_tokens(swatches: &[Swatch]) -> Result<String, serde_json::Error> {
+    let mut tokens = serde_json::Map::new();
+    for swatch in swatches {
+        tokens.insert(
+            css_identifier(&swatch.variable),
+            serde_json::json!({
+                "$type": "color",
+                "$value": to_hex(swatch.color),
+                "$description": swatch.name,
+            }),
+        );
+    }
+    let mut out = serde_json::to_string_pretty(&serde_json::Value::Object(tokens))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// Writes a Tokens Studio token file: a single `global` set with one `color`
+/// token per swatch. A `/` in the variable name nests the token in groups,
+/// which Figma shows as folders once the tokens are turned into styles.
+/// Names already taken get a numeric suffix rather than replacing the token
+/// or group that has them.
+fn write_tokens_studio(swatches: &[Swatch]) -> Result<String, serde_json::Error> {
+    let mut set = serde_json::Map::new();
+    for swatch in swatches {
+        let path: Vec<String> =
+            swatch.variable.split('/').map(css_identifier).filter(|segment| !segment.is_empty()).collect();
+        let Some((token, groups)) = path.split_last() else {
+            continue;
+        };
+        let mut group = &mut set;
+        for name in groups {
+            let name = free_key(group, name, |existing| existing.get("value").is_none());
+            let entry = group.entry(name).or_insert_with(|| serde_json::json!({}));
+            group = entry.as_object_mut().expect("groups are objects");
+        }
+        group.insert(
+            free_key(group, token, |_| false),
+            serde_json::json!({
+                "value": to_hex(swatch.color),
+                "type": "color",
+                "description": swatch.name,
+            }),
+        );
+    }
+    let mut out = serde_json::to_string_pretty(&serde_json::json!({ "global": set }))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// `name`, or `name-2`, `name-3`, … when `name` is taken by something that
+/// cannot be `reused`, so a token and a group of the same name both survive.
+fn free_key(
+    map: &serde_json::Map<String, serde_json::Value>,
+    name: &str,
+    reused: impl Fn(&serde_json::Value) -> bool,
+) -> String {
+    std::iter::once(name.to_string())
+        .chain((2..).map(|suffix| format!("{}-{}", name, suffix)))
+        .find(|key| map.get(key).is_none_or(&reused))
+        .expect("some suffix is free")
+}
+
+/// Writes a version 2 file for the Sketch Palettes plugin. The format has no
+/// color names, so only the channels are kept, as fractions of 1.
+fn write_sketch_palette(swatches: &[Swatch]) -> Result<String, serde_json::Error> {
+    let colors: Vec<serde_json::Value> = swatches
+        .iter()
+        .map(|swatch| {
+            let [r, g, b, _] = swatch.color.into_rgba8();
+            let channel = |value: u8| f64::from(value) / 255.0;
+            serde_json::json!({
+                "red": channel(r),
+                "green": channel(g),
+                "blue": channel(b),
+                "alpha": 1.0,
+            })
+        })
+        .collect();
+    let mut out = serde_json::to_string_pretty(&serde_json::json!({
+        "compatibleVersion": "2.0",
+        "pluginVersion": "2.22",
+        "colors": colors,
+        "gradients": [],
+        "images": [],
+    }))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// The number of colors a Procreate palette holds.
+const PROCREATE_PALETTE_SIZE: usize = 30;
+
+/// Writes a Procreate swatches file: a zip archive holding `Swatches.json`,
+/// an array holding one palette of colors as hue, saturation and brightness
+/// fractions. Procreate palettes hold 30 colors, so later swatches are left
+/// out.
+fn write_procreate(swatches: &[Swatch]) -> Result<Vec<u8>, serde_json::Error> {
+    let colors: Vec<serde_json::Value> = swatches
+        .iter()
+        .take(PROCREATE_PALETTE_SIZE)
+        .map(|swatch| {
+            let hsv: Hsv = Srgb::new(swatch.color.r, swatch.color.g, swatch.color.b).into_color();
+            serde_json::json!({
+                "hue": hsv.hue.into_positive_degrees() / 360.0,
+                "saturation": hsv.saturation,
+                "brightness": hsv.value,
+                "alpha": 1,
+                "colorSpace": 0,
+            })
+        })
+        .collect();
+    let json = serde_json::to_vec(&serde_json::json!([{ "name": "Pixel Peeker", "swatches": colors }]))?;
+    Ok(write_stored_zip("Swatches.json", &json))
+}
+
+/// A zip archive with a single uncompressed file, which is all Procreate
+/// needs and saves pulling in a zip library.
+fn write_stored_zip(name: &str, data: &[u8]) -> Vec<u8> {
+    // 1980-01-01, the earliest date a zip entry can carry.
+    const DOS_DATE: u16 = 0x21;
+
+    let crc = crc32(data);
+    let size = data.len() as u32;
+    let name_len = name.len() as u16;
+    let mut entry = Vec::new();
+    for value in [20u16, 0, 0, 0, DOS_DATE] {
+        entry.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in [crc, size, size] {
+        entry.extend_from_slice(&value.to_le_bytes());
+    }
+    entry.extend_from_slice(&name_len.to_le_bytes());
+    entry.extend_from_slice(&0u16.to_le_bytes());
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+    out.extend_from_slice(&entry);
+    out.extend_from_slice(name.as_bytes());
+    out.extend_from_slice(data);
+
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+    out.extend_from_slice(&20u16.to_le_bytes());
+    out.extend_from_slice(&entry);
+    // Comment length, disk number and internal attributes, then external
+    // attributes and the local header's offset.
+    out.extend_from_slice(&[0; 6]);
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(name.as_bytes());
+    let directory_size = out.len() as u32 - directory_offset;
+
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    for value in [0u16, 0, 1, 1] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out.extend_from_slice(&directory_size.to_le_bytes());
+    out.extend_from_slice(&directory_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
+
+/// The CRC-32 a zip entry is checked with.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryFormat {
+    Csv,
+    Json,
+}
+
+impl HistoryFormat {
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => HistoryFormat::Json,
+            _ => HistoryFormat::Csv,
+        }
+    }
+}
+
+/// One history entry with its metadata flattened into plain columns, shared
+/// by the CSV and JSON history exports.
+#[derive(Debug, serde::Serialize)]
+struct HistoryRecord {
+    hex: String,
+    r: u8,
+    g: u8,
+    b: u8,
+    label: Option<String>,
+    variable_name: Option<String>,
+    picked_at: Option<String>,
+    x: Option<i32>,
+    y: Option<i32>,
+    monitor: Option<String>,
+    window_title: Option<String>,
+}
+
+impl From<&HistoryEntry> for HistoryRecord {
+    fn from(entry: &HistoryEntry) -> Self {
+        let [r, g, b, _] = entry.color.into_rgba8();
+        Self {
+            hex: to_hex(entry.color),
+            r,
+            g,
+            b,
+            label: entry.label.clone(),
+            variable_name: entry.variable_name.clone(),
+            picked_at: entry.metadata.picked_at.map(|time| time.to_rfc3339()),
+            x: entry.metadata.position.map(|(x, _)| x),
+            y: entry.metadata.position.map(|(_, y)| y),
+            monitor: entry.metadata.monitor.clone(),
+            window_title: entry.metadata.window_title.clone(),
+        }
+    }
+}
+
+pub fn export_history(format: HistoryFormat, entries: &[HistoryEntry]) -> Result<Vec<u8>, PeekerError> {
+    let records: Vec<HistoryRecord> = entries.iter().map(HistoryRecord::from).collect();
+    match format {
+        HistoryFormat::Json => {
+            let mut out = serde_json::to_string_pretty(&records).map_err(|e| PeekerError::Export(e.to_string()))?;
+            out.push('\n');
+            Ok(out.into_bytes())
+        },
+        HistoryFormat::Csv => Ok(write_history_csv(&records).into_bytes()),
+    }
+}
+
+const HISTORY_CSV_HEADER: &str = "hex,r,g,b,label,variable_name,picked_at,x,y,monitor,window_title\n";
+
+fn write_history_csv(records: &[HistoryRecord]) -> String {
+    let mut out = String::from(HISTORY_CSV_HEADER);
+    for record in records {
+        out.push_str(&history_csv_row(record));
+    }
+    out
+}
+
+fn history_csv_row(record: &HistoryRecord) -> String {
+    let optional = |value: &Option<String>| value.as_deref().map(csv_field).unwrap_or_default();
+    let number = |value: Option<i32>| value.map(|v| v.to_string()).unwrap_or_default();
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}\n",
+        record.hex,
+        record.r,
+        record.g,
+        record.b,
+        optional(&record.label),
+        optional(&record.variable_name),
+        optional(&record.picked_at),
+        number(record.x),
+        number(record.y),
+        optional(&record.monitor),
+        optional(&record.window_title),
+    )
+}
+
+/// Appends one pick to a running log: a JSON object per line for `.jsonl`
+/// and `.json` files, otherwise a CSV row with the history export columns.
+/// The CSV header is written when the file is new or empty.
+pub fn append_pick_log(path: &std::path::Path, entry: &HistoryEntry) -> Result<(), PeekerError> {
+    use std::io::Write;
+
+    let record = HistoryRecord::from(entry);
+    let json_lines = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("json"));
+
+    let line = if json_lines {
+        let mut line = serde_json::to_string(&record).map_err(|e| PeekerError::Export(e.to_string()))?;
+        line.push('\n');
+        line
+    } else {
+        history_csv_row(&record)
+    };
+    let append = || -> std::io::Result<()> {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        if !json_lines && file.metadata()?.len() == 0 {
+            file.write_all(HISTORY_CSV_HEADER.as_bytes())?;
+        }
+        file.write_all(line.as_bytes())
+    };
+    append().map_err(|source| PeekerError::WriteFile { path: path.to_path_buf(), source })
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Builds a `https://coolors.co/aabbcc-ddeeff` link for sharing the palette.
+pub fn coolors_url(swatches: &[Swatch]) -> String {
+    let colors: Vec<String> =
+        swatches.iter().map(|swatch| to_hex(swatch.color).trim_start_matches('#').to_string()).collect();
+    format!("https://coolors.co/{}", colors.join("-"))
+}
+
+const SHEET_SCALE: u32 = 3;
+const SHEET_MARGIN: u32 = 8;
+const SHEET_SWATCH_SIZE: u32 = 40;
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+const SOLID_SWATCH_SIZE: u32 = 64;
+
+/// A plain square of `color`, for tools that render a pasted image better
+/// than a hex string.
+pub fn render_solid_swatch(color: Color) -> RgbaImage {
+    RgbaImage::from_pixel(SOLID_SWATCH_SIZE, SOLID_SWATCH_SIZE, Rgba(color.into_rgba8()))
+}
+
+/// Renders one row per swatch: a filled square followed by its hex value,
+/// drawn with a built-in bitmap font so no font files need to be loaded.
+pub fn render_swatch_sheet(swatches: &[Swatch]) -> RgbaImage {
+    let label_width = 7 * (GLYPH_WIDTH + 1) * SHEET_SCALE;
+    let row_height = SHEET_SWATCH_SIZE + SHEET_MARGIN;
+    let width = SHEET_MARGIN * 3 + SHEET_SWATCH_SIZE + label_width;
+    let height = SHEET_MARGIN + row_height * swatches.len().max(1) as u32;
+
+    let mut sheet = RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255]));
+    for (row, swatch) in swatches.iter().enumerate() {
+        let top = SHEET_MARGIN + row as u32 * row_height;
+
+        let fill = Rgba(swatch.color.into_rgba8());
+        let border = Rgba([128, 128, 128, 255]);
+        for y in 0..SHEET_SWATCH_SIZE {
+            for x in 0..SHEET_SWATCH_SIZE {
+                let edge = x == 0 || y == 0 || x == SHEET_SWATCH_SIZE - 1 || y == SHEET_SWATCH_SIZE - 1;
+                sheet.put_pixel(SHEET_MARGIN + x, top + y, if edge { border } else { fill });
+            }
+        }
+
+        let text_top = top + (SHEET_SWATCH_SIZE - GLYPH_HEIGHT * SHEET_SCALE) / 2;
+        let mut left = SHEET_MARGIN * 2 + SHEET_SWATCH_SIZE;
+        for c in to_hex(swatch.color).to_ascii_uppercase().chars() {
+            draw_glyph(&mut sheet, c, left, text_top);
+            left += (GLYPH_WIDTH + 1) * SHEET_SCALE;
+        }
+    }
+    sheet
+}
+
+fn draw_glyph(image: &mut RgbaImage, c: char, left: u32, top: u32) {
+    let Some(rows) = glyph(c) else {
+        return;
+    };
+    for (y, bits) in rows.iter().enumerate() {
+        for x in 0..GLYPH_WIDTH {
+            if bits & (1 << (GLYPH_WIDTH - 1 - x)) == 0 {
+                continue;
+            }
+            for sy in 0..SHEET_SCALE {
+                for sx in 0..SHEET_SCALE {
+                    image.put_pixel(
+                        left + x * SHEET_SCALE + sx,
+                        top + y as u32 * SHEET_SCALE + sy,
+                        Rgba([32, 32, 32, 255]),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn glyph(c: char) -> Option<[u8; 7]> {
+    Some(match c {
+        '#' => [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        _ => return None,
+    })
+}
+
+/// Same layout as [`render_swatch_sheet`], but with real `<text>` labels so
+/// the hex values stay selectable and the sheet scales cleanly in documents.
+fn write_svg_sheet(swatches: &[Swatch]) -> String {
+    let row_height = SHEET_SWATCH_SIZE + SHEET_MARGIN;
+    let label_left = SHEET_MARGIN * 2 + SHEET_SWATCH_SIZE;
+    let width = label_left + 240;
+    let height = SHEET_MARGIN + row_height * swatches.len().max(1) as u32;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+    out.push_str(&format!("  <rect width=\"{width}\" height=\"{height}\" fill=\"#ffffff\"/>\n"));
+    out.push_str("  <g font-family=\"ui-monospace, monospace\" font-size=\"16\" fill=\"#202020\">\n");
+    for (row, swatch) in swatches.iter().enumerate() {
+        let top = SHEET_MARGIN + row as u32 * row_height;
+        let hex = to_hex(swatch.color);
+        out.push_str(&format!(
+            "    <rect x=\"{SHEET_MARGIN}\" y=\"{top}\" width=\"{SHEET_SWATCH_SIZE}\" height=\"{SHEET_SWATCH_SIZE}\" fill=\"{hex}\" stroke=\"#808080\"/>\n"
+        ));
+
+        let label = if swatch.name.eq_ignore_ascii_case(&hex) {
+            hex.to_ascii_uppercase()
+        } else {
+            format!("{} {}", hex.to_ascii_uppercase(), swatch.name)
+        };
+        out.push_str(&format!(
+            "    <text x=\"{label_left}\" y=\"{}\" dominant-baseline=\"middle\">{}</text>\n",
+            top + SHEET_SWATCH_SIZE / 2,
+            escape_xml(&label)
+        ));
+    }
+    out.push_str("  </g>\n</svg>\n");
+    out
+}
+
+fn escape_xml(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut bytes = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+    Ok(bytes)
+}
+
+/// Builds a Tailwind-style `50`–`900` scale with the picked color at `500`,
+/// mixing towards white for lighter steps and black for darker ones in Oklab
+/// so the perceived lightness steps stay even.
+fn tailwind_scale(color: Color) -> [(u16, Color); 10] {
+    const STEPS: [(u16, f32); 10] = [
+        (50, 0.9),
+        (100, 0.8),
+        (200, 0.6),
+        (300, 0.4),
+        (400, 0.2),
+        (500, 0.0),
+        (600, -0.2),
+        (700, -0.4),
+        (800, -0.6),
+        (900, -0.8),
+    ];
+
+    let base: Oklab = Srgb::new(color.r, color.g, color.b).into_color();
+    STEPS.map(|(step, amount)| {
+        let target = if amount >= 0.0 { Oklab::new(1.0, 0.0, 0.0) } else { Oklab::new(0.0, 0.0, 0.0) };
+        let t = amount.abs();
+        let mixed = Oklab::new(
+            base.l + (target.l - base.l) * t,
+            base.a + (target.a - base.a) * t,
+            base.b + (target.b - base.b) * t,
+        );
+        let rgb: Srgb = mixed.into_color();
+        (step, Color::from_rgb(rgb.red.clamp(0.0, 1.0), rgb.green.clamp(0.0, 1.0), rgb.blue.clamp(0.0, 1.0)))
+    })
+}
+
+/// Turns a user-entered variable name into something CSS and SCSS accept,
+/// dropping any sigil the user typed and replacing other punctuation with `-`.
+fn css_identifier(name: &str) -> String {
+    let name = name.trim().trim_start_matches('$').trim_start_matches("--");
+    name.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' }).collect()
+}
+
+pub fn to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// A CSS `linear-gradient` with `colors` as evenly spaced stops. `angle` is
+/// counterclockwise from the positive x axis, as [`crate::measure::Span`]
+/// reports it; CSS measures clockwise from straight up.
+pub fn css_linear_gradient(angle: f32, colors: &[Color]) -> String {
+    let css_angle = (90.0 - angle).rem_euclid(360.0);
+    let last = colors.len().saturating_sub(1).max(1) as f32;
+    let stops: Vec<String> = colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| format!("{} {}%", to_hex(*color), (i as f32 / last * 100.0).round()))
+        .collect();
+    format!("linear-gradient({}deg, {})", css_angle.round(), stops.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn swatch(name: &str, r: u8, g: u8, b: u8) -> Swatch {
+        Swatch { name: name.to_string(), variable: name.to_string(), color: Color::from_rgb8(r, g, b), date: None }
+    }
+
+    #[test]
+    fn aco_matches_the_file_format_spec() {
+        let swatches = [swatch("Red", 0xFF, 0x00, 0x00), swatch("Grün", 0x3A, 0x80, 0xD5)];
+
+        // Assembled by hand from Adobe's Color Swatch file format: big-endian
+        // words, channels scaled to 0-65535, and in version 2 a name of
+        // UTF-16 code units counted with the terminating zero.
+        #[rustfmt::skip]
+        let expected: Vec<u8> = vec![
+            0x00, 0x01, 0x00, 0x02, // version 1, two colors
+            0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // RGB 65535, 0, 0
+            0x00, 0x00, 0x3A, 0x3A, 0x80, 0x80, 0xD5, 0xD5, 0x00, 0x00, // RGB 14906, 32896, 54741
+            0x00, 0x02, 0x00, 0x02, // version 2, two colors
+            0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x04, // 4 code units
+            0x00, b'R', 0x00, b'e', 0x00, b'd', 0x00, 0x00,
+            0x00, 0x00, 0x3A, 0x3A, 0x80, 0x80, 0xD5, 0xD5, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x05, // 5 code units
+            0x00, b'G', 0x00, b'r', 0x00, 0xFC, 0x00, b'n', 0x00, 0x00, // "Grün", ü is U+00FC
+        ];
+        assert_eq!(export_palette(PaletteFormat::Aco, &swatches, &ExportOptions::default()).unwrap(), expected);
+    }
+
+    #[test]
+    fn aco_empty_palette_has_both_headers() {
+        assert_eq!(
+            export_palette(PaletteFormat::Aco, &[], &ExportOptions::default()).unwrap(),
+            vec![0, 1, 0, 0, 0, 2, 0, 0]
+        );
+    }
+
+    #[test]
+    fn gpl_lists_channels_and_names() {
+        let swatches = [swatch("Primary red", 0xFF, 0x00, 0x00), swatch("#3A7BD5", 0x3A, 0x7B, 0xD5)];
+
+        assert_eq!(
+            String::from_utf8(export_palette(PaletteFormat::Gpl, &swatches, &ExportOptions::default()).unwrap())
+                .unwrap(),
+            "GIMP Palette\nName: Pixel Peeker\nColumns: 0\n#\n255   0   0\tPrimary red\n 58 123 213\t#3A7BD5\n"
+        );
+    }
+
+    #[test]
+    fn plain_text_has_one_line_per_swatch() {
+        let dated = Swatch {
+            date: chrono::NaiveDate::from_ymd_opt(2025, 10, 1),
+            ..swatch("primary/button/hover", 0xAA, 0xBB, 0xCC)
+        };
+        let swatches = [dated, swatch("#3A7BD5", 0x3A, 0x7B, 0xD5)];
+
+        assert_eq!(
+            String::from_utf8(export_palette(PaletteFormat::PlainText, &swatches, &ExportOptions::default()).unwrap())
+                .unwrap(),
+            "#AABBCC  primary/button/hover  2025-10-01\n#3A7BD5\n"
+        );
+    }
+
+    #[test]
+    fn css_and_scss_variables() {
+        let swatches = [swatch("primary", 0xAA, 0xBB, 0xCC), swatch("$button hover", 0x3A, 0x7B, 0xD5)];
+
+        assert_eq!(
+            String::from_utf8(
+                export_palette(PaletteFormat::CssVariables, &swatches, &ExportOptions::default()).unwrap()
+            )
+            .unwrap(),
+            ":root {\n  --primary: #aabbcc;\n  --button-hover: #3a7bd5;\n}\n"
+        );
+        assert_eq!(
+            String::from_utf8(
+                export_palette(PaletteFormat::ScssVariables, &swatches, &ExportOptions::default()).unwrap()
+            )
+            .unwrap(),
+            "$primary: #aabbcc;\n$button-hover: #3a7bd5;\n"
+        );
+    }
+
+    #[test]
+    fn tailwind_scale_keeps_picked_color_at_500() {
+        let swatches = [swatch("brand", 0x3A, 0x7B, 0xD5)];
+        let options = ExportOptions { tailwind_scales: true };
+
+        let output = String::from_utf8(export_palette(PaletteFormat::Tailwind, &swatches, &options).unwrap()).unwrap();
+        assert!(output.contains("        500: '#3a7bd5',\n"));
+        assert_eq!(output.matches(": '#").count(), 10);
+    }
+
+    #[test]
+    fn design_tokens_keep_palette_order() {
+        let swatches = [swatch("surface", 0xFF, 0xFF, 0xFF), swatch("accent", 0x3A, 0x7B, 0xD5)];
+
+        let output = export_palette(PaletteFormat::DesignTokens, &swatches, &ExportOptions::default()).unwrap();
+        let tokens: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            tokens,
+            serde_json::json!({
+                "surface": { "$type": "color", "$value": "#ffffff", "$description": "surface" },
+                "accent": { "$type": "color", "$value": "#3a7bd5", "$description": "accent" },
+            })
+        );
+        let keys: Vec<_> = tokens.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, ["surface", "accent"]);
+    }
+
+    #[test]
+    fn swatches_sharing_a_label_keep_their_own_tokens() {
+        let swatches = unique_variables(vec![
+            swatch("accent", 0x3A, 0x7B, 0xD5),
+            swatch("accent", 0xFF, 0x00, 0x00),
+            swatch("accent-2", 0x00, 0xFF, 0x00),
+        ]);
+        let variables: Vec<_> = swatches.iter().map(|swatch| swatch.variable.as_str()).collect();
+        assert_eq!(variables, ["accent", "accent-2", "accent-2-2"]);
+
+        let output = export_palette(PaletteFormat::DesignTokens, &swatches, &ExportOptions::default()).unwrap();
+        let tokens: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(tokens["accent"]["$value"], "#3a7bd5");
+        assert_eq!(tokens["accent-2"]["$value"], "#ff0000");
+        assert_eq!(tokens["accent-2-2"]["$value"], "#00ff00");
+    }
+
+    #[test]
+    fn tokens_studio_nests_slash_separated_names() {
+        let swatches = [swatch("primary/button/hover", 0xAA, 0xBB, 0xCC), swatch("accent", 0x3A, 0x7B, 0xD5)];
+
+        let output = export_palette(PaletteFormat::TokensStudio, &swatches, &ExportOptions::default()).unwrap();
+        let tokens: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            tokens,
+            serde_json::json!({
+                "global": {
+                    "primary": { "button": { "hover": {
+                        "value": "#aabbcc", "type": "color", "description": "primary/button/hover",
+                    } } },
+                    "accent": { "value": "#3a7bd5", "type": "color", "description": "accent" },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn tokens_studio_keeps_tokens_and_groups_of_the_same_name() {
+        let swatches = [
+            swatch("brand", 0x11, 0x11, 0x11),
+            swatch("brand/dark", 0x22, 0x22, 0x22),
+            swatch("brand", 0x33, 0x33, 0x33),
+        ];
+
+        let output = export_palette(PaletteFormat::TokensStudio, &swatches, &ExportOptions::default()).unwrap();
+        let tokens: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(tokens["global"]["brand"]["value"], "#111111");
+        assert_eq!(tokens["global"]["brand-2"]["dark"]["value"], "#222222");
+        assert_eq!(tokens["global"]["brand-3"]["value"], "#333333");
+    }
+
+    #[test]
+    fn sketch_palette_lists_fractional_channels() {
+        let swatches = [swatch("white", 0xFF, 0xFF, 0xFF), swatch("red", 0xFF, 0x00, 0x00)];
+
+        let output = export_palette(PaletteFormat::SketchPalette, &swatches, &ExportOptions::default()).unwrap();
+        let palette: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(palette["compatibleVersion"], "2.0");
+        assert_eq!(
+            palette["colors"],
+            serde_json::json!([
+                { "red": 1.0, "green": 1.0, "blue": 1.0, "alpha": 1.0 },
+                { "red": 1.0, "green": 0.0, "blue": 0.0, "alpha": 1.0 },
+            ])
+        );
+    }
+
+    #[test]
+    fn procreate_swatches_are_a_zipped_json_palette() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let swatches = [swatch("red", 0xFF, 0x00, 0x00), swatch("gray", 0x80, 0x80, 0x80)];
+        let output = export_palette(PaletteFormat::Procreate, &swatches, &ExportOptions::default()).unwrap();
+        assert_eq!(&output[..4], b"PK\x03\x04");
+        assert_eq!(&output[output.len() - 22..output.len() - 18], b"PK\x05\x06");
+
+        let name = b"Swatches.json";
+        let start = 30 + name.len();
+        assert_eq!(&output[30..start], name);
+        let size = u32::from_le_bytes(output[22..26].try_into().unwrap()) as usize;
+        let palettes: serde_json::Value = serde_json::from_slice(&output[start..start + size]).unwrap();
+        assert_eq!(palettes.as_array().unwrap().len(), 1);
+        let palette = &palettes[0];
+        assert_eq!(palette["name"], "Pixel Peeker");
+        assert_eq!(palette["swatches"][0]["hue"], 0.0);
+        assert_eq!(palette["swatches"][0]["saturation"], 1.0);
+        assert_eq!(palette["swatches"][1]["saturation"], 0.0);
+        assert_eq!(palette["swatches"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn png_sheet_has_one_row_per_swatch() {
+        let swatches = [swatch("#FF0000", 0xFF, 0x00, 0x00), swatch("#3A7BD5", 0x3A, 0x7B, 0xD5)];
+
+        let sheet = render_swatch_sheet(&swatches);
+        assert_eq!(sheet.height(), SHEET_MARGIN + 2 * (SHEET_SWATCH_SIZE + SHEET_MARGIN));
+        assert_eq!(sheet.get_pixel(SHEET_MARGIN + 10, SHEET_MARGIN + 10), &Rgba([0xFF, 0x00, 0x00, 0xFF]));
+        let second_row = SHEET_MARGIN * 2 + SHEET_SWATCH_SIZE + 10;
+        assert_eq!(sheet.get_pixel(SHEET_MARGIN + 10, second_row), &Rgba([0x3A, 0x7B, 0xD5, 0xFF]));
+    }
+
+    #[test]
+    fn svg_sheet_labels_are_text() {
+        let swatches = [swatch("#3A7BD5", 0x3A, 0x7B, 0xD5), swatch("Salt & pepper", 0x80, 0x80, 0x80)];
+
+        let output =
+            String::from_utf8(export_palette(PaletteFormat::SvgSheet, &swatches, &ExportOptions::default()).unwrap())
+                .unwrap();
+        assert!(output.contains("fill=\"#3a7bd5\""));
+        assert!(output.contains(">#3A7BD5</text>"));
+        assert!(output.contains(">#808080 Salt &amp; pepper</text>"));
+    }
+
+    #[test]
+    fn css_gradient_stops() {
+        let colors = [Color::from_rgb8(0xFF, 0, 0), Color::from_rgb8(0, 0xFF, 0), Color::from_rgb8(0, 0, 0xFF)];
+        assert_eq!(css_linear_gradient(0.0, &colors), "linear-gradient(90deg, #ff0000 0%, #00ff00 50%, #0000ff 100%)");
+        assert_eq!(css_linear_gradient(-90.0, &colors[..1]), "linear-gradient(180deg, #ff0000 0%)");
+    }
+
+    #[test]
+    fn coolors_link() {
+        let swatches = [swatch("a", 0xAA, 0xBB, 0xCC), swatch("b", 0x3A, 0x7B, 0xD5)];
+
+        assert_eq!(coolors_url(&swatches), "https://coolors.co/aabbcc-3a7bd5");
+    }
+
+    #[test]
+    fn history_csv_quotes_metadata() {
+        let mut entry = HistoryEntry::new(Color::from_rgb8(0x3A, 0x7B, 0xD5));
+        entry.label = Some("Link".to_string());
+        entry.metadata.position = Some((10, 20));
+        entry.metadata.window_title = Some("Editor, \"main.rs\"".to_string());
+
+        let output = String::from_utf8(export_history(HistoryFormat::Csv, &[entry]).unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "hex,r,g,b,label,variable_name,picked_at,x,y,monitor,window_title\n#3a7bd5,58,123,213,Link,,,10,20,,\"Editor, \"\"main.rs\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn pick_log_writes_csv_header_once() {
+        let path = std::env::temp_dir().join(format!("pixel-peeker-pick-log-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let entry = HistoryEntry::new(Color::from_rgb8(0x3A, 0x7B, 0xD5));
+
+        append_pick_log(&path, &entry).unwrap();
+        append_pick_log(&path, &entry).unwrap();
+        let output = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            output,
+            "hex,r,g,b,label,variable_name,picked_at,x,y,monitor,window_title\n#3a7bd5,58,123,213,,,,,,,\n#3a7bd5,58,123,213,,,,,,,\n"
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use iced::Color;
+use palette::{Hsl, Hsv, IntoColor, LinSrgb, Oklch, Srgb};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ColorFormat {
+    Rgb,
+    Hex,
+    Hsv,
+    Hsl,
+    Oklch,
+    /// Linear-light (not gamma-encoded) RGB in 0–1, as shaders and renderers take it.
+    Linear,
+    /// `iced::Color::from_rgb8(…)`
+    Iced,
+    /// `egui::Color32::from_rgb(…)`
+    Egui,
+    /// `ratatui::style::Color::Rgb(…)`
+    Ratatui,
+    /// SwiftUI `Color(red:green:blue:)`
+    SwiftUi,
+    /// `UIColor(red:green:blue:alpha:)`
+    UiColor,
+    /// An Android resource, `<color name="…">#AARRGGBB</color>`.
+    AndroidXml,
+    /// Jetpack Compose `Color(0xAARRGGBB)`
+    Compose,
+    /// Flutter `Color(0xAARRGGBB)`
+    Flutter,
+    /// Unity `new Color(r, g, b)`
+    Unity,
+    /// Unreal `FLinearColor(r, g, b)`
+    Unreal,
+    /// GLSL and WGSL `vec3(r, g, b)`
+    Vec3,
+    /// GLSL and WGSL `vec4(r, g, b, 1.0)`
+    Vec4,
+    /// `{"hex":"#AABBCC","rgb":[170,187,204],"hsl":[210,25,73]}`, for
+    /// scripts and config files that take structured data.
+    Json,
+}
+
+impl ColorFormat {
+    pub const ALL: [ColorFormat; 19] = [
+        ColorFormat::Rgb,
+        ColorFormat::Hex,
+        ColorFormat::Hsv,
+        ColorFormat::Hsl,
+        ColorFormat::Oklch,
+        ColorFormat::Linear,
+        ColorFormat::Iced,
+        ColorFormat::Egui,
+        ColorFormat::Ratatui,
+        ColorFormat::SwiftUi,
+        ColorFormat::UiColor,
+        ColorFormat::AndroidXml,
+        ColorFormat::Compose,
+        ColorFormat::Flutter,
+        ColorFormat::Unity,
+        ColorFormat::Unreal,
+        ColorFormat::Vec3,
+        ColorFormat::Vec4,
+        ColorFormat::Json,
+    ];
+
+    /// The formats shown until the user picks others: the color-model ones.
+    /// The code snippets are one toggle away in the format options.
+    pub const DEFAULT_VISIBLE: [ColorFormat; 6] = [
+        ColorFormat::Rgb,
+        ColorFormat::Hex,
+        ColorFormat::Hsv,
+        ColorFormat::Hsl,
+        ColorFormat::Oklch,
+        ColorFormat::Linear,
+    ];
+
+    /// The Fluent message naming the format.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ColorFormat::Rgb => "color-format-rgb",
+            ColorFormat::Hex => "color-format-hex",
+            ColorFormat::Hsv => "color-format-hsv",
+            ColorFormat::Hsl => "color-format-hsl",
+            ColorFormat::Oklch => "color-format-oklch",
+            ColorFormat::Linear => "color-format-linear",
+            ColorFormat::Iced => "color-format-iced",
+            ColorFormat::Egui => "color-format-egui",
+            ColorFormat::Ratatui => "color-format-ratatui",
+            ColorFormat::SwiftUi => "color-format-swiftui",
+            ColorFormat::UiColor => "color-format-uicolor",
+            ColorFormat::AndroidXml => "color-format-android-xml",
+            ColorFormat::Compose => "color-format-compose",
+            ColorFormat::Flutter => "color-format-flutter",
+            ColorFormat::Unity => "color-format-unity",
+            ColorFormat::Unreal => "color-format-unreal",
+            ColorFormat::Vec3 => "color-format-vec3",
+            ColorFormat::Vec4 => "color-format-vec4",
+            ColorFormat::Json => "color-format-json",
+        }
+    }
+
+    /// How the format writes its channels unless chosen otherwise; `None`
+    /// for formats without a choice.
+    pub fn default_channels(&self) -> Option<ChannelStyle> {
+        match self {
+            ColorFormat::Rgb => Some(ChannelStyle::Integer),
+            ColorFormat::Linear => Some(ChannelStyle::Float),
+            _ => None,
+        }
+    }
+
+    /// Whether the format converts to linear light unless chosen otherwise;
+    /// `None` for formats without a choice. `FLinearColor` is linear by
+    /// definition and shaders mostly work in linear light, Unity's `Color`
+    /// usually holds sRGB values.
+    pub fn default_linearized(&self) -> Option<bool> {
+        match self {
+            ColorFormat::Unity => Some(false),
+            ColorFormat::Unreal | ColorFormat::Vec3 | ColorFormat::Vec4 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// How a channel is written: `128`, `0.5020` or `50.2%`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelStyle {
+    Integer,
+    Float,
+    Percent,
+}
+
+impl ChannelStyle {
+    pub const ALL: [ChannelStyle; 3] = [ChannelStyle::Integer, ChannelStyle::Float, ChannelStyle::Percent];
+
+    /// `channel` as written for a source of `bit_depth` bits: integers span
+    /// the source's range, and deep sources get two more decimal places.
+    fn write(&self, channel: f32, bit_depth: u8) -> String {
+        let extra = if bit_depth > 8 { 2 } else { 0 };
+        match self {
+            ChannelStyle::Integer => {
+                let max = ((1u32 << bit_depth) - 1) as f32;
+                format!("{}", (channel.clamp(0.0, 1.0) * max).round() as u32)
+            },
+            ChannelStyle::Float => format!("{:.*}", 4 + extra, channel),
+            ChannelStyle::Percent => format!("{:.*}%", 1 + extra, channel * 100.0),
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ChannelStyle::Integer => "0–255",
+            ChannelStyle::Float => "0–1",
+            ChannelStyle::Percent => "%",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatOptions {
+    /// Write hex as `#ABC` when each channel's two digits are the same.
+    pub hex_shorthand: bool,
+    /// Leave the leading `#` off hex colors.
+    pub hex_without_hash: bool,
+    /// Channel representation chosen per format, for formats that offer one.
+    pub channels: BTreeMap<ColorFormat, ChannelStyle>,
+    /// sRGB→linear conversion chosen per format, for formats that offer one.
+    pub linearize: BTreeMap<ColorFormat, bool>,
+    /// Decimal places in `vec3` and `vec4` components.
+    pub shader_decimals: u8,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            hex_shorthand: false,
+            hex_without_hash: false,
+            channels: BTreeMap::new(),
+            linearize: BTreeMap::new(),
+            shader_decimals: 3,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn channels(&self, format: ColorFormat) -> Option<ChannelStyle> {
+        format.default_channels().map(|default| self.channels.get(&format).copied().unwrap_or(default))
+    }
+
+    pub fn linearized(&self, format: ColorFormat) -> Option<bool> {
+        format.default_linearized().map(|default| self.linearize.get(&format).copied().unwrap_or(default))
+    }
+}
+
+/// Each channel of `color` as an integer of `bit_depth` bits, 0–1023 for 10-bit.
+pub fn channel_values(color: &Color, bit_depth: u8) -> [u32; 3] {
+    let max = ((1u32 << bit_depth) - 1) as f32;
+    [color.r, color.g, color.b].map(|channel| (channel.clamp(0.0, 1.0) * max).round() as u32)
+}
+
+fn linear(color: &Color) -> [f32; 3] {
+    let linear: LinSrgb = Srgb::new(color.r, color.g, color.b).into_linear();
+    [linear.red, linear.green, linear.blue]
+}
+
+pub fn format_color(color: &Color, format: &ColorFormat) -> String {
+    format_color_with(color, format, &FormatOptions::default(), 8)
+}
+
+/// `color` in `format`. Formats with a channel style write their channels at
+/// the source's `bit_depth`; the others are always 8-bit.
+pub fn format_color_with(color: &Color, format: &ColorFormat, options: &FormatOptions, bit_depth: u8) -> String {
+    let channels = |values: [f32; 3]| {
+        let style = options.channels(*format).unwrap_or(ChannelStyle::Integer);
+        values.map(|value| style.write(value, bit_depth)).join(", ")
+    };
+
+    let [r, g, b] = channel_values(color, 8);
+    let linearized =
+        || if options.linearized(*format) == Some(true) { linear(color) } else { [color.r, color.g, color.b] };
+    let engine = || linearized().map(|channel| format!("{:.3}f", channel)).join(", ");
+    let shader = || {
+        let decimals = options.shader_decimals as usize;
+        linearized().map(|channel| format!("{:.*}", decimals, channel)).join(", ")
+    };
+    match format {
+        ColorFormat::Rgb => format!("rgb({})", channels([color.r, color.g, color.b])),
+        ColorFormat::Hex => {
+            let hash = if options.hex_without_hash { "" } else { "#" };
+            if options.hex_shorthand && [r, g, b].iter().all(|channel| channel >> 4 == channel & 0xF) {
+                format!("{}{:X}{:X}{:X}", hash, r & 0xF, g & 0xF, b & 0xF)
+            } else {
+                format!("{}{:02X}{:02X}{:02X}", hash, r, g, b)
+            }
+        },
+        ColorFormat::Hsv => {
+            let hsv: Hsv = Srgb::new(color.r, color.g, color.b).into_color();
+            format!(
+                "hsv({:.0}deg, {:.0}%, {:.0}%)",
+                hsv.hue.into_positive_degrees(),
+                hsv.saturation * 100.0,
+                hsv.value * 100.0
+            )
+        },
+        ColorFormat::Hsl => {
+            let hsl: Hsl = Srgb::new(color.r, color.g, color.b).into_color();
+            format!(
+                "hsl({:.0}deg, {:.0}%, {:.0}%)",
+                hsl.hue.into_positive_degrees(),
+                hsl.saturation * 100.0,
+                hsl.lightness * 100.0
+            )
+        },
+        ColorFormat::Oklch => {
+            let oklch: Oklch = Srgb::new(color.r, color.g, color.b).into_color();
+            format!("oklch({:.2} {:.2} {:.1}deg)", oklch.l, oklch.chroma, oklch.hue.into_positive_degrees())
+        },
+        ColorFormat::Linear => channels(linear(color)),
+        ColorFormat::Iced => format!("iced::Color::from_rgb8({}, {}, {})", r, g, b),
+        ColorFormat::Egui => format!("egui::Color32::from_rgb({}, {}, {})", r, g, b),
+        ColorFormat::Ratatui => format!("ratatui::style::Color::Rgb({}, {}, {})", r, g, b),
+        ColorFormat::SwiftUi => format!("Color(red: {:.3}, green: {:.3}, blue: {:.3})", color.r, color.g, color.b),
+        ColorFormat::UiColor => {
+            format!("UIColor(red: {:.3}, green: {:.3}, blue: {:.3}, alpha: 1.0)", color.r, color.g, color.b)
+        },
+        // Android resource names are identifiers, so the name is made from the color.
+        ColorFormat::AndroidXml => {
+            format!("<color name=\"color_{:02x}{:02x}{:02x}\">#FF{:02X}{:02X}{:02X}</color>", r, g, b, r, g, b)
+        },
+        ColorFormat::Compose | ColorFormat::Flutter => format!("Color(0xFF{:02X}{:02X}{:02X})", r, g, b),
+        ColorFormat::Unity => format!("new Color({})", engine()),
+        ColorFormat::Unreal => format!("FLinearColor({})", engine()),
+        ColorFormat::Vec3 => format!("vec3({})", shader()),
+        ColorFormat::Vec4 => format!("vec4({}, 1.0)", shader()),
+        ColorFormat::Json => {
+            let hsl: Hsl = Srgb::new(color.r, color.g, color.b).into_color();
+            let hsl = [hsl.hue.into_positive_degrees(), hsl.saturation * 100.0, hsl.lightness * 100.0];
+            let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
+            serde_json::json!({ "hex": hex, "rgb": [r, g, b], "hsl": hsl.map(|value| value.round() as u32) })
+                .to_string()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hex_can_be_shortened_and_left_bare() {
+        let mut options = FormatOptions::default();
+        let color = Color::from_rgb8(0xAA, 0xBB, 0xCC);
+        let format = |options: &FormatOptions, color| format_color_with(&color, &ColorFormat::Hex, options, 8);
+        assert_eq!(format(&options, color), "#AABBCC");
+
+        options.hex_shorthand = true;
+        assert_eq!(format(&options, color), "#ABC");
+        assert_eq!(format(&options, Color::from_rgb8(0xAA, 0xBB, 0xCD)), "#AABBCD");
+
+        options.hex_without_hash = true;
+        assert_eq!(format(&options, color), "ABC");
+    }
+
+    #[test]
+    fn channels_follow_the_chosen_style() {
+        let color = Color::from_rgb8(255, 128, 0);
+        let mut options = FormatOptions::default();
+        assert_eq!(format_color_with(&color, &ColorFormat::Rgb, &options, 8), "rgb(255, 128, 0)");
+        assert_eq!(format_color_with(&color, &ColorFormat::Linear, &options, 8), "1.0000, 0.2159, 0.0000");
+
+        options.channels.insert(ColorFormat::Rgb, ChannelStyle::Percent);
+        options.channels.insert(ColorFormat::Linear, ChannelStyle::Integer);
+        assert_eq!(format_color_with(&color, &ColorFormat::Rgb, &options, 8), "rgb(100.0%, 50.2%, 0.0%)");
+        assert_eq!(format_color_with(&color, &ColorFormat::Linear, &options, 8), "255, 55, 0");
+
+        options.channels.insert(ColorFormat::Rgb, ChannelStyle::Float);
+        assert_eq!(format_color_with(&color, &ColorFormat::Rgb, &options, 8), "rgb(1.0000, 0.5020, 0.0000)");
+        assert_eq!(format_color_with(&color, &ColorFormat::Rgb, &options, 16), "rgb(1.000000, 0.501961, 0.000000)");
+
+        options.channels.insert(ColorFormat::Rgb, ChannelStyle::Integer);
+        assert_eq!(format_color_with(&color, &ColorFormat::Rgb, &options, 10), "rgb(1023, 514, 0)");
+        assert_eq!(options.channels(ColorFormat::Hex), None);
+    }
+
+    #[test]
+    fn rust_snippets_use_eight_bit_channels() {
+        let color = Color::from_rgb8(18, 52, 86);
+        let snippets =
+            [ColorFormat::Iced, ColorFormat::Egui, ColorFormat::Ratatui].map(|format| format_color(&color, &format));
+        assert_eq!(
+            snippets,
+            [
+                "iced::Color::from_rgb8(18, 52, 86)",
+                "egui::Color32::from_rgb(18, 52, 86)",
+                "ratatui::style::Color::Rgb(18, 52, 86)",
+            ]
+        );
+    }
+
+    #[test]
+    fn swift_snippets_use_fractions() {
+        let color = Color::from_rgb8(255, 128, 0);
+        assert_eq!(format_color(&color, &ColorFormat::SwiftUi), "Color(red: 1.000, green: 0.502, blue: 0.000)");
+        assert_eq!(
+            format_color(&color, &ColorFormat::UiColor),
+            "UIColor(red: 1.000, green: 0.502, blue: 0.000, alpha: 1.0)"
+        );
+    }
+
+    #[test]
+    fn android_snippets_are_opaque_argb() {
+        let color = Color::from_rgb8(0xAA, 0xBB, 0xCC);
+        assert_eq!(format_color(&color, &ColorFormat::AndroidXml), r#"<color name="color_aabbcc">#FFAABBCC</color>"#);
+        assert_eq!(format_color(&color, &ColorFormat::Compose), "Color(0xFFAABBCC)");
+        assert_eq!(format_color(&color, &ColorFormat::Flutter), "Color(0xFFAABBCC)");
+    }
+
+    #[test]
+    fn engine_snippets_can_be_linearized() {
+        let color = Color::from_rgb8(255, 128, 0);
+        let mut options = FormatOptions::default();
+        assert_eq!(format_color_with(&color, &ColorFormat::Unity, &options, 8), "new Color(1.000f, 0.502f, 0.000f)");
+        assert_eq!(
+            format_color_with(&color, &ColorFormat::Unreal, &options, 8),
+            "FLinearColor(1.000f, 0.216f, 0.000f)"
+        );
+
+        options.linearize.insert(ColorFormat::Unity, true);
+        options.linearize.insert(ColorFormat::Unreal, false);
+        assert_eq!(format_color_with(&color, &ColorFormat::Unity, &options, 8), "new Color(1.000f, 0.216f, 0.000f)");
+        assert_eq!(
+            format_color_with(&color, &ColorFormat::Unreal, &options, 8),
+            "FLinearColor(1.000f, 0.502f, 0.000f)"
+        );
+        assert_eq!(options.linearized(ColorFormat::Rgb), None);
+    }
+
+    #[test]
+    fn json_objects_hold_every_representation() {
+        let json = format_color(&Color::from_rgb8(0xAA, 0xBB, 0xCC), &ColorFormat::Json);
+        assert_eq!(json, r##"{"hex":"#AABBCC","rgb":[170,187,204],"hsl":[210,25,73]}"##);
+    }
+
+    #[test]
+    fn shader_vectors_follow_precision_and_linearization() {
+        let color = Color::from_rgb8(255, 128, 0);
+        let mut options = FormatOptions::default();
+        assert_eq!(format_color_with(&color, &ColorFormat::Vec3, &options, 8), "vec3(1.000, 0.216, 0.000)");
+
+        options.shader_decimals = 5;
+        options.linearize.insert(ColorFormat::Vec4, false);
+        assert_eq!(format_color_with(&color, &ColorFormat::Vec4, &options, 8), "vec4(1.00000, 0.50196, 0.00000, 1.0)");
+    }
+}
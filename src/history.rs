@@ -0,0 +1,145 @@
+//! The rules for adding picks to the color history and searching it, kept
+//! apart from the application state so they can be tested.
+
+use crate::formats::ColorFormat;
+use crate::{HistoryEntry, format_color, import, metrics};
+
+/// Adds `entry` as the newest pick and returns whether the history changed.
+///
+/// A repeat of the newest color is dropped. With `dedupe`, a color already
+/// in the history moves to the end instead, keeping its label, variable name
+/// and pin but taking the new pick's metadata. Past `max_unpinned` unpinned
+/// entries the oldest unpinned one is evicted; pinned entries never are.
+pub fn add(history: &mut Vec<HistoryEntry>, entry: HistoryEntry, dedupe: bool, max_unpinned: usize) -> bool {
+    if dedupe && let Some(existing) = history.iter().position(|e| e.color == entry.color) {
+        let previous = history.remove(existing);
+        history.push(HistoryEntry { metadata: entry.metadata, ..previous });
+        return true;
+    }
+
+    if history.last().map(|last| last.color) == Some(entry.color) {
+        return false;
+    }
+    history.push(entry);
+    if history.iter().filter(|entry| !entry.pinned).count() > max_unpinned
+        && let Some(oldest) = history.iter().position(|entry| !entry.pinned)
+    {
+        history.remove(oldest);
+    }
+    true
+}
+
+/// Indices of the entries matching the filter `query`, pinned or not. A full
+/// hex color sorts the whole history by ΔE from it; anything else keeps the
+/// entries whose hex starts with it or whose label or variable name
+/// contains it, ignoring case.
+pub fn filter(history: &[HistoryEntry], query: &str) -> Vec<usize> {
+    let query = query.trim();
+    let mut indices: Vec<usize> = (0..history.len()).collect();
+    if query.is_empty() {
+        return indices;
+    }
+
+    if query.trim_start_matches('#').len() == 6
+        && let Some(target) = import::parse_hex(query)
+    {
+        let distance = |index: &usize| metrics::delta_e(history[*index].color, target);
+        indices.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+        return indices;
+    }
+
+    let hex_query = query.trim_start_matches('#').to_ascii_uppercase();
+    let text_query = query.to_lowercase();
+    let contains = |text: &Option<String>| text.as_ref().is_some_and(|text| text.to_lowercase().contains(&text_query));
+    indices.retain(|&index| {
+        let entry = &history[index];
+        let hex = format_color(&entry.color, &ColorFormat::Hex);
+        (!hex_query.is_empty() && hex[1..].starts_with(&hex_query))
+            || contains(&entry.label)
+            || contains(&entry.variable_name)
+    });
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::Color;
+    use pretty_assertions::assert_eq;
+
+    fn gray(level: u8) -> HistoryEntry {
+        HistoryEntry::new(Color::from_rgb8(level, level, level))
+    }
+
+    fn levels(history: &[HistoryEntry]) -> Vec<u8> {
+        history.iter().map(|entry| entry.color.into_rgba8()[0]).collect()
+    }
+
+    #[test]
+    fn only_consecutive_repeats_are_dropped_without_dedupe() {
+        let mut history = Vec::new();
+        assert!(add(&mut history, gray(1), false, 10));
+        assert!(!add(&mut history, gray(1), false, 10));
+        assert!(add(&mut history, gray(2), false, 10));
+        assert!(add(&mut history, gray(1), false, 10));
+        assert_eq!(levels(&history), [1, 2, 1]);
+    }
+
+    #[test]
+    fn dedupe_moves_the_existing_entry_to_the_end() {
+        let mut history = vec![HistoryEntry { label: Some("Ink".into()), pinned: true, ..gray(1) }, gray(2)];
+        let mut repeat = gray(1);
+        repeat.metadata.position = Some((3, 4));
+
+        assert!(add(&mut history, repeat, true, 10));
+        assert_eq!(levels(&history), [2, 1]);
+        assert_eq!(history[1].label.as_deref(), Some("Ink"));
+        assert!(history[1].pinned);
+        assert_eq!(history[1].metadata.position, Some((3, 4)));
+    }
+
+    #[test]
+    fn filter_matches_labels_hex_prefixes_and_variable_names() {
+        let history = vec![
+            HistoryEntry { label: Some("Brand Blue".into()), ..HistoryEntry::new(Color::from_rgb8(0x3A, 0x7B, 0xD5)) },
+            HistoryEntry { variable_name: Some("surface".into()), ..HistoryEntry::new(Color::WHITE) },
+            HistoryEntry { pinned: true, ..HistoryEntry::new(Color::from_rgb8(0x3A, 0x00, 0x00)) },
+        ];
+        assert_eq!(filter(&history, "  "), [0, 1, 2]);
+        assert_eq!(filter(&history, "blue"), [0]);
+        assert_eq!(filter(&history, "#3a"), [0, 2]);
+        assert_eq!(filter(&history, "SURF"), [1]);
+        assert_eq!(filter(&history, "nothing"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn a_full_hex_sorts_by_distance_including_pinned_entries() {
+        let history = vec![
+            HistoryEntry::new(Color::BLACK),
+            HistoryEntry::new(Color::from_rgb8(0x80, 0x00, 0x00)),
+            HistoryEntry { pinned: true, ..HistoryEntry::new(Color::from_rgb8(0xF0, 0x10, 0x10)) },
+        ];
+        assert_eq!(filter(&history, "#ff0000"), [2, 1, 0]);
+    }
+
+    #[test]
+    fn eviction_skips_pinned_entries() {
+        let mut history = vec![HistoryEntry { pinned: true, ..gray(1) }, gray(2), gray(3)];
+        assert!(add(&mut history, gray(4), false, 2));
+        assert_eq!(levels(&history), [1, 3, 4]);
+    }
+
+    #[test]
+    fn the_history_keeps_the_newest_max_color_history_unpinned_picks() {
+        let pick = |index: usize| HistoryEntry::new(Color::from_rgb8((index % 256) as u8, (index / 256) as u8, 0));
+        let mut history = vec![HistoryEntry { pinned: true, ..HistoryEntry::new(Color::from_rgb8(0, 0, 0xFF)) }];
+        for index in 0..crate::MAX_COLOR_HISTORY + 50 {
+            add(&mut history, pick(index), false, crate::MAX_COLOR_HISTORY);
+        }
+
+        assert_eq!(history.len(), crate::MAX_COLOR_HISTORY + 1);
+        assert!(history[0].pinned);
+        assert_eq!(history[1].color, pick(50).color);
+        assert_eq!(history.last().unwrap().color, pick(crate::MAX_COLOR_HISTORY + 49).color);
+    }
+}
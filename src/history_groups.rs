@@ -0,0 +1,81 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How the history is split under headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryGrouping {
+    None,
+    /// By the local day the color was picked.
+    Day,
+    /// By the run of the app the color was added in.
+    Session,
+}
+
+impl HistoryGrouping {
+    pub const ALL: [HistoryGrouping; 3] = [HistoryGrouping::None, HistoryGrouping::Day, HistoryGrouping::Session];
+
+    /// The Fluent message naming the grouping in the UI.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            HistoryGrouping::None => "grouping-none",
+            HistoryGrouping::Day => "grouping-day",
+            HistoryGrouping::Session => "grouping-session",
+        }
+    }
+}
+
+/// The header an entry goes under, in `zone`; empty for entries without the
+/// time the grouping needs, which were added before it was recorded.
+pub fn group_key<Tz: TimeZone>(
+    grouping: HistoryGrouping,
+    picked_at: Option<DateTime<Utc>>,
+    session: Option<DateTime<Utc>>,
+    zone: &Tz,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let time = match grouping {
+        HistoryGrouping::None => return String::new(),
+        HistoryGrouping::Day => picked_at,
+        HistoryGrouping::Session => session,
+    };
+    let pattern = if grouping == HistoryGrouping::Day { "%Y-%m-%d" } else { "%Y-%m-%d %H:%M" };
+    time.map(|time| time.with_timezone(zone).format(pattern).to_string()).unwrap_or_default()
+}
+
+/// `indices` split by `key`, groups in the order they first appear.
+pub fn group(indices: &[usize], key: impl Fn(usize) -> String) -> Vec<(String, Vec<usize>)> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for &index in indices {
+        let key = key(index);
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, members)) => members.push(index),
+            None => groups.push((key, vec![index])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn entries_group_by_day_and_session() {
+        let at = |hour| Utc.with_ymd_and_hms(2025, 10, 1, hour, 30, 0).single();
+        let late = Utc.with_ymd_and_hms(2025, 10, 2, 1, 0, 0).single();
+        assert_eq!(group_key(HistoryGrouping::Day, at(9), None, &Utc), "2025-10-01");
+        assert_eq!(group_key(HistoryGrouping::Session, at(9), at(8), &Utc), "2025-10-01 08:30");
+        assert_eq!(group_key(HistoryGrouping::Session, at(9), None, &Utc), "");
+        assert_eq!(group_key(HistoryGrouping::None, at(9), at(8), &Utc), "");
+
+        let times = [at(9), late, at(23), None];
+        let groups = group(&[0, 1, 2, 3], |index| group_key(HistoryGrouping::Day, times[index], None, &Utc));
+        assert_eq!(
+            groups,
+            vec![("2025-10-01".into(), vec![0, 2]), ("2025-10-02".into(), vec![1]), (String::new(), vec![3])]
+        );
+    }
+}
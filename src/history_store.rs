@@ -0,0 +1,81 @@
+use crate::error::PeekerError;
+use crate::{SerializableColor, Settings, workspaces, write_json};
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever the history file changes incompatibly.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The default workspace's history, kept in the data directory apart from
+/// the settings so that saving one never rewrites the other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryFile {
+    version: u32,
+    color_history: Vec<SerializableColor>,
+}
+
+/// The history of `workspace`, or of the default workspace when `None`.
+/// `legacy` is the history older versions kept in the settings file, used
+/// until the history file has been written once.
+pub fn load(workspace: Option<&str>, legacy: &[SerializableColor]) -> Vec<SerializableColor> {
+    if let Some(name) = workspace {
+        return workspaces::load(name);
+    }
+    let Some(contents) = get_path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+        return legacy.to_vec();
+    };
+    match parse(&contents) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read the history file: {}", e);
+            Vec::new()
+        },
+    }
+}
+
+pub fn save(workspace: Option<&str>, history: &[SerializableColor]) -> Result<(), PeekerError> {
+    if let Some(name) = workspace {
+        return workspaces::save(name, history);
+    }
+    let path = get_path().ok_or(PeekerError::NoConfigDir("the color history"))?;
+    write_json(&path, &HistoryFile { version: SCHEMA_VERSION, color_history: history.to_vec() })
+}
+
+fn parse(contents: &str) -> Result<Vec<SerializableColor>, String> {
+    let file: HistoryFile = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    if file.version > SCHEMA_VERSION {
+        eprintln!("The history file is from a newer version (schema {}); reading what is understood", file.version);
+    }
+    Ok(file.color_history)
+}
+
+/// Where the history of `workspace` is stored.
+pub fn path(workspace: Option<&str>) -> Option<std::path::PathBuf> {
+    match workspace {
+        Some(name) => workspaces::get_path(name),
+        None => get_path(),
+    }
+}
+
+fn get_path() -> Option<std::path::PathBuf> {
+    directories::ProjectDirs::from("com", "kdheepak", "pixel-peeker")
+        .map(|dirs| dirs.data_dir().join("history.json"))
+        .or_else(|| Settings::get_settings_path().map(|path| path.with_file_name("history.json")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn history_files_carry_a_version() {
+        let history =
+            parse(r#"{ "version": 1, "color_history": [{ "r": 1.0, "g": 0.5, "b": 0.0, "label": "Accent" }] }"#)
+                .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].label.as_deref(), Some("Accent"));
+
+        assert!(parse(r#"{ "version": 2, "color_history": [], "groups": [] }"#).unwrap().is_empty());
+        assert!(parse(r#"{ "color_history": [] }"#).is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+/// Messages for a locale missing from the chosen translation come from here.
+const FALLBACK_LOCALE: &str = "en-US";
+
+/// Bundled translations as `(locale, display name, Fluent source)`.
+pub const LOCALES: &[(&str, &str, &str)] = &[
+    ("en-US", "English", include_str!("../locales/en-US/main.ftl")),
+    ("de", "Deutsch", include_str!("../locales/de/main.ftl")),
+];
+
+pub struct Translator {
+    pub locale: &'static str,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Translator {
+    /// Uses `language` if it names a bundled locale, otherwise the OS
+    /// locale, otherwise English.
+    pub fn new(language: Option<&str>) -> Self {
+        let locale = language
+            .and_then(negotiate)
+            .or_else(|| sys_locale::get_locale().as_deref().and_then(negotiate))
+            .unwrap_or(FALLBACK_LOCALE);
+        Self { locale, bundle: bundle(locale), fallback: bundle(FALLBACK_LOCALE) }
+    }
+
+    pub fn tr(&self, id: &str) -> String {
+        self.tr_with(id, &[])
+    }
+
+    /// Formats message `id` with named arguments. Unknown ids come back as
+    /// the id itself, so a missing string is visible rather than blank.
+    pub fn tr_with(&self, id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+
+        for bundle in [&self.bundle, &self.fallback] {
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+                let mut errors = Vec::new();
+                let formatted = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+                for error in errors {
+                    eprintln!("Failed to format '{}': {}", id, error);
+                }
+                return formatted.into_owned();
+            }
+        }
+        id.to_string()
+    }
+}
+
+/// Picks the bundled locale for a requested one such as `de-AT` or `en_GB`:
+/// an exact match first, then the same language.
+fn negotiate(requested: &str) -> Option<&'static str> {
+    let requested: LanguageIdentifier = requested.replace('_', "-").parse().ok()?;
+    let locales = || LOCALES.iter().map(|(locale, _, _)| *locale);
+    locales().find(|locale| locale.parse::<LanguageIdentifier>().is_ok_and(|locale| locale == requested)).or_else(
+        || {
+            locales().find(|locale| {
+                locale.parse::<LanguageIdentifier>().is_ok_and(|locale| locale.language == requested.language)
+            })
+        },
+    )
+}
+
+fn bundle(locale: &'static str) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = locale.parse().expect("bundled locales are valid");
+    let mut bundle = FluentBundle::new(vec![langid]);
+    // Bidi isolation marks show up as boxes in iced's text rendering.
+    bundle.set_use_isolating(false);
+
+    let source = LOCALES.iter().find(|(name, _, _)| *name == locale).map_or("", |(_, _, source)| source);
+    let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+        eprintln!("Errors in the {} translation: {:?}", locale, errors);
+        resource
+    });
+    if let Err(errors) = bundle.add_resource(resource) {
+        eprintln!("Duplicate messages in the {} translation: {:?}", locale, errors);
+    }
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_translation_has_every_message() {
+        let english: Vec<&str> = LOCALES[0]
+            .2
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect();
+        for (locale, _, _) in &LOCALES[1..] {
+            let translation = bundle(locale);
+            let missing: Vec<&str> = english.iter().copied().filter(|id| !translation.has_message(id)).collect();
+            assert_eq!(missing, Vec::<&str>::new(), "missing from {}", locale);
+        }
+    }
+
+    #[test]
+    fn locales_fall_back_by_language() {
+        assert_eq!(negotiate("de_AT"), Some("de"));
+        assert_eq!(negotiate("en-GB"), Some("en-US"));
+        assert_eq!(negotiate("fr-FR"), None);
+
+        let german = Translator::new(Some("de"));
+        assert_eq!(german.tr("color-history"), "Verlauf:");
+        assert_eq!(german.tr_with("mouse-position", &[("x", 3_i32.into()), ("y", (-4_i32).into())]), "Maus: (3, -4)");
+        assert_eq!(german.tr("no-such-message"), "no-such-message");
+    }
+}
@@ -0,0 +1,441 @@
+use crate::error::PeekerError;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+type Matrix = [[f32; 3]; 3];
+
+/// Chromatic adaptation from the ICC connection space's D50 white to sRGB's D65.
+const BRADFORD_D50_TO_D65: Matrix = [
+    [0.955_576_6, -0.023_039_3, 0.063_163_6],
+    [-0.028_289_5, 1.009_941_6, 0.021_007_7],
+    [0.012_298_2, -0.020_483, 1.329_909_8],
+];
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [3.240_454_2, -1.537_138_5, -0.498_531_4],
+    [-0.969_266, 1.876_010_8, 0.041_556],
+    [0.055_643_4, -0.204_025_9, 1.057_225_2],
+];
+
+/// A tone curve from an ICC `curv` or `para` tag, mapping encoded device
+/// values to linear light.
+#[derive(Debug, Clone, PartialEq)]
+enum Curve {
+    Gamma(f32),
+    Table(Vec<f32>),
+    /// ICC parametric function type 4 (`g, a, b, c, d, e, f`); the simpler
+    /// types are expressed with neutral parameters.
+    Parametric([f32; 7]),
+}
+
+impl Curve {
+    fn apply(&self, value: f32) -> f32 {
+        let value = value.clamp(0.0, 1.0);
+        match self {
+            Self::Gamma(gamma) => value.powf(*gamma),
+            Self::Table(table) => {
+                let position = value * (table.len() - 1) as f32;
+                let (index, fraction) = (position.floor() as usize, position.fract());
+                let next = table.get(index + 1).copied().unwrap_or(table[index]);
+                table[index] + (next - table[index]) * fraction
+            },
+            Self::Parametric([g, a, b, c, d, e, f]) => {
+                if value >= *d {
+                    (a * value + b).max(0.0).powf(*g) + e
+                } else {
+                    c * value + f
+                }
+            },
+        }
+    }
+}
+
+/// A matrix/TRC display profile: the shape nearly every monitor profile has.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    curves: [Curve; 3],
+    /// Linear device RGB to D65 linear sRGB.
+    to_linear_srgb: Matrix,
+}
+
+impl Profile {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&data)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.get(36..40) != Some(b"acsp") {
+            return Err("not an ICC profile".into());
+        }
+        if data.get(16..20) != Some(b"RGB ") || data.get(20..24) != Some(b"XYZ ") {
+            return Err("only RGB display profiles with an XYZ connection space are supported".into());
+        }
+
+        let tags = tag_table(data)?;
+        let tag = |signature: &[u8; 4]| {
+            tags.get(signature).ok_or_else(|| format!("missing {} tag", String::from_utf8_lossy(signature)))
+        };
+        let mut to_xyz = [[0.0; 3]; 3];
+        for (column, signature) in [b"rXYZ", b"gXYZ", b"bXYZ"].into_iter().enumerate() {
+            let xyz = parse_xyz(tag(signature)?)?;
+            for (row, value) in xyz.into_iter().enumerate() {
+                to_xyz[row][column] = value;
+            }
+        }
+        let curves = [parse_curve(tag(b"rTRC")?)?, parse_curve(tag(b"gTRC")?)?, parse_curve(tag(b"bTRC")?)?];
+
+        Ok(Self { curves, to_linear_srgb: multiply(&XYZ_D65_TO_LINEAR_SRGB, &multiply(&BRADFORD_D50_TO_D65, &to_xyz)) })
+    }
+
+    /// Converts an encoded device color to encoded sRGB. Colors outside the
+    /// sRGB gamut are clipped.
+    pub fn to_srgb(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let linear = [self.curves[0].apply(rgb[0]), self.curves[1].apply(rgb[1]), self.curves[2].apply(rgb[2])];
+        let srgb = transform(&self.to_linear_srgb, linear);
+        srgb.map(|channel| encode_srgb(channel.clamp(0.0, 1.0)))
+    }
+
+    /// Converts an 8-bit capture in place.
+    pub fn convert_image(&self, image: &mut xcap::image::RgbaImage) {
+        for pixel in image.pixels_mut() {
+            let rgb = [pixel[0], pixel[1], pixel[2]].map(|channel| channel as f32 / 255.0);
+            let [r, g, b] = self.to_srgb(rgb).map(|channel| (channel * 255.0).round() as u8);
+            (pixel[0], pixel[1], pixel[2]) = (r, g, b);
+        }
+    }
+}
+
+fn encode_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 { linear * 12.92 } else { 1.055 * linear.powf(1.0 / 2.4) - 0.055 }
+}
+
+fn tag_table(data: &[u8]) -> Result<HashMap<[u8; 4], &[u8]>, String> {
+    let count = read_u32(data, 128)? as usize;
+    let mut tags = HashMap::new();
+    for index in 0..count {
+        let entry = 132 + index * 12;
+        let signature: [u8; 4] =
+            data.get(entry..entry + 4).and_then(|bytes| bytes.try_into().ok()).ok_or("truncated tag table")?;
+        let offset = read_u32(data, entry + 4)? as usize;
+        let size = read_u32(data, entry + 8)? as usize;
+        let contents =
+            offset.checked_add(size).and_then(|end| data.get(offset..end)).ok_or("tag outside the profile")?;
+        tags.insert(signature, contents);
+    }
+    Ok(tags)
+}
+
+fn parse_xyz(tag: &[u8]) -> Result<[f32; 3], String> {
+    if tag.get(0..4) != Some(b"XYZ ") {
+        return Err("colorant tag is not XYZ".into());
+    }
+    Ok([read_s15f16(tag, 8)?, read_s15f16(tag, 12)?, read_s15f16(tag, 16)?])
+}
+
+fn parse_curve(tag: &[u8]) -> Result<Curve, String> {
+    match tag.get(0..4) {
+        Some(b"curv") => {
+            let count = read_u32(tag, 8)? as usize;
+            match count {
+                0 => Ok(Curve::Gamma(1.0)),
+                1 => Ok(Curve::Gamma(read_u16(tag, 12)? as f32 / 256.0)),
+                _ => {
+                    let table = (0..count)
+                        .map(|index| read_u16(tag, 12 + index * 2).map(|value| value as f32 / 65535.0))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Curve::Table(table))
+                },
+            }
+        },
+        Some(b"para") => {
+            let function = read_u16(tag, 8)?;
+            let count = match function {
+                0 => 1,
+                1 => 3,
+                2 => 4,
+                3 => 5,
+                4 => 7,
+                _ => return Err(format!("unknown parametric curve type {}", function)),
+            };
+            let mut p = [0.0; 7];
+            for (index, slot) in p.iter_mut().enumerate().take(count) {
+                *slot = read_s15f16(tag, 12 + index * 4)?;
+            }
+            let [g, a, b, c, d, ..] = p;
+            Ok(Curve::Parametric(match function {
+                0 => [g, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                // Below -b/a the curve is 0 (type 1) or c (type 2).
+                1 => [g, a, b, 0.0, -b / a, 0.0, 0.0],
+                2 => [g, a, b, 0.0, -b / a, c, c],
+                3 => [g, a, b, c, d, 0.0, 0.0],
+                _ => p,
+            }))
+        },
+        _ => Err("unsupported tone curve".into()),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    let bytes = data.get(offset..offset + 2).ok_or("truncated profile")?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    let bytes = data.get(offset..offset + 4).ok_or("truncated profile")?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_s15f16(data: &[u8], offset: usize) -> Result<f32, String> {
+    Ok(read_u32(data, offset)? as i32 as f32 / 65536.0)
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut product = [[0.0; 3]; 3];
+    for (row, product_row) in product.iter_mut().enumerate() {
+        for (column, value) in product_row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[row][k] * b[k][column]).sum();
+        }
+    }
+    product
+}
+
+fn transform(matrix: &Matrix, vector: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+/// Monitor profiles by monitor name: the user's choice from the settings if
+/// there is one, otherwise the system's, looked up once per monitor.
+#[derive(Default)]
+pub struct Profiles {
+    loaded: HashMap<String, Option<Profile>>,
+    /// The last profile that could not be used, until [`Self::take_error`].
+    error: Option<PeekerError>,
+}
+
+impl Profiles {
+    pub fn get(&mut self, monitor: &str, overrides: &BTreeMap<String, PathBuf>) -> Option<&Profile> {
+        let error = &mut self.error;
+        self.loaded
+            .entry(monitor.to_string())
+            .or_insert_with(|| {
+                let profile = match overrides.get(monitor) {
+                    Some(path) => Profile::load(path),
+                    None => system_profile(monitor)?,
+                };
+                profile.map_err(|e| *error = Some(PeekerError::ColorProfile(format!("{} ({})", e, monitor)))).ok()
+            })
+            .as_ref()
+    }
+
+    /// Why a monitor's profile was ignored, once; the monitor's captures are
+    /// treated as sRGB meanwhile.
+    pub fn take_error(&mut self) -> Option<PeekerError> {
+        self.error.take()
+    }
+
+    /// Whether a profile was found for `monitor` the last time it was captured.
+    pub fn has_profile(&self, monitor: &str) -> bool {
+        matches!(self.loaded.get(monitor), Some(Some(_)))
+    }
+
+    /// Forgets loaded profiles, after monitors or the chosen profiles change.
+    pub fn clear(&mut self) {
+        self.loaded.clear();
+    }
+}
+
+/// The profile the system assigns to `monitor`, if it has one.
+#[cfg(target_os = "linux")]
+fn system_profile(monitor: &str) -> Option<Result<Profile, String>> {
+    system_profile_path(monitor).map(|path| Profile::load(&path))
+}
+
+/// The profile colord assigns to the output `monitor`.
+#[cfg(target_os = "linux")]
+fn system_profile_path(monitor: &str) -> Option<PathBuf> {
+    use zbus::zvariant::OwnedObjectPath;
+
+    const COLORD: &str = "org.freedesktop.ColorManager";
+    let connection = zbus::blocking::Connection::system().ok()?;
+    let manager = zbus::blocking::Proxy::new(&connection, COLORD, "/org/freedesktop/ColorManager", COLORD).ok()?;
+    let device: OwnedObjectPath = manager.call("FindDeviceByProperty", &("XRANDR_name", monitor)).ok()?;
+    let device = zbus::blocking::Proxy::new(&connection, COLORD, device, "org.freedesktop.ColorManager.Device").ok()?;
+    let profiles: Vec<OwnedObjectPath> = device.get_property("Profiles").ok()?;
+    let profile = zbus::blocking::Proxy::new(
+        &connection,
+        COLORD,
+        profiles.into_iter().next()?,
+        "org.freedesktop.ColorManager.Profile",
+    )
+    .ok()?;
+    profile.get_property::<String>("Filename").ok().filter(|path| !path.is_empty()).map(PathBuf::from)
+}
+
+/// The ICC data of the color space macOS renders `monitor` in.
+#[cfg(target_os = "macos")]
+fn system_profile(monitor: &str) -> Option<Result<Profile, String>> {
+    use std::ffi::c_void;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGDisplayCopyColorSpace(display: u32) -> *const c_void;
+        fn CGColorSpaceCopyICCData(space: *const c_void) -> *const c_void;
+    }
+    #[link(name = "CoreFoundation", kind = "framework")]
+    unsafe extern "C" {
+        fn CFDataGetLength(data: *const c_void) -> isize;
+        fn CFDataGetBytePtr(data: *const c_void) -> *const u8;
+        fn CFRelease(object: *const c_void);
+    }
+
+    let display = monitor_id(monitor)?;
+    // SAFETY: both copies return an owned reference or null; each is released
+    // once, after the bytes have been copied out of the data.
+    let data = unsafe {
+        let space = CGDisplayCopyColorSpace(display);
+        if space.is_null() {
+            return None;
+        }
+        let icc = CGColorSpaceCopyICCData(space);
+        CFRelease(space);
+        if icc.is_null() {
+            return None;
+        }
+        let bytes = std::slice::from_raw_parts(CFDataGetBytePtr(icc), CFDataGetLength(icc).max(0) as usize).to_vec();
+        CFRelease(icc);
+        bytes
+    };
+    Some(Profile::parse(&data))
+}
+
+/// The profile Windows color management associates with `monitor`.
+#[cfg(target_os = "windows")]
+fn system_profile(monitor: &str) -> Option<Result<Profile, String>> {
+    use windows_sys::Win32::Graphics::Gdi::{CreateDCW, DeleteDC, GetMonitorInfoW, MONITORINFOEXW};
+    use windows_sys::Win32::UI::ColorSystem::GetICMProfileW;
+
+    // xcap identifies Windows monitors by their `HMONITOR`.
+    let handle = monitor_id(monitor)? as usize as *mut std::ffi::c_void;
+    let mut path = [0u16; 260];
+    // SAFETY: `info` and `path` outlive the calls that fill them, with their
+    // sizes passed alongside, and the device context is deleted before returning.
+    let found = unsafe {
+        let mut info: MONITORINFOEXW = std::mem::zeroed();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(handle, &mut info.monitorInfo) == 0 {
+            return None;
+        }
+        let context = CreateDCW(info.szDevice.as_ptr(), info.szDevice.as_ptr(), std::ptr::null(), std::ptr::null());
+        if context.is_null() {
+            return None;
+        }
+        let mut len = path.len() as u32;
+        let found = GetICMProfileW(context, &mut len, path.as_mut_ptr());
+        DeleteDC(context);
+        found != 0
+    };
+    let end = path.iter().position(|&unit| unit == 0).unwrap_or(path.len());
+    found.then(|| Profile::load(Path::new(&String::from_utf16_lossy(&path[..end]))))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn monitor_id(monitor: &str) -> Option<u32> {
+    let monitors = xcap::Monitor::all().ok()?;
+    monitors.into_iter().find(|candidate| candidate.name().is_ok_and(|name| name == monitor))?.id().ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn system_profile(_monitor: &str) -> Option<Result<Profile, String>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// The sRGB curve as a type 3 parametric tag.
+    fn srgb_curve() -> Vec<u8> {
+        let mut tag = b"para\0\0\0\0\0\x03\0\0".to_vec();
+        for value in [2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.04045] {
+            tag.extend(((value * 65536.0_f64).round() as i32).to_be_bytes());
+        }
+        tag
+    }
+
+    fn xyz(value: [f64; 3]) -> Vec<u8> {
+        let mut tag = b"XYZ \0\0\0\0".to_vec();
+        for component in value {
+            tag.extend(((component * 65536.0).round() as i32).to_be_bytes());
+        }
+        tag
+    }
+
+    /// A matrix/TRC profile with the given D50-adapted primaries.
+    fn profile(primaries: [[f64; 3]; 3], curve: Vec<u8>) -> Vec<u8> {
+        let tags: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"rXYZ", xyz(primaries[0])),
+            (b"gXYZ", xyz(primaries[1])),
+            (b"bXYZ", xyz(primaries[2])),
+            (b"rTRC", curve.clone()),
+            (b"gTRC", curve.clone()),
+            (b"bTRC", curve),
+        ];
+        let mut header = vec![0; 128];
+        header[16..20].copy_from_slice(b"RGB ");
+        header[20..24].copy_from_slice(b"XYZ ");
+        header[36..40].copy_from_slice(b"acsp");
+
+        let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+        let mut contents = Vec::new();
+        let start = 128 + 4 + tags.len() * 12;
+        for (signature, tag) in &tags {
+            table.extend(*signature);
+            table.extend(((start + contents.len()) as u32).to_be_bytes());
+            table.extend((tag.len() as u32).to_be_bytes());
+            contents.extend(tag);
+        }
+        [header, table, contents].concat()
+    }
+
+    const SRGB_PRIMARIES: [[f64; 3]; 3] =
+        [[0.4361, 0.2225, 0.0139], [0.3851, 0.7169, 0.0971], [0.1431, 0.0606, 0.7141]];
+    const DISPLAY_P3_PRIMARIES: [[f64; 3]; 3] =
+        [[0.5151, 0.2412, -0.0011], [0.2919, 0.6922, 0.0419], [0.1572, 0.0666, 0.7843]];
+
+    fn rounded(rgb: [f32; 3]) -> [u8; 3] {
+        rgb.map(|channel| (channel * 255.0).round() as u8)
+    }
+
+    #[test]
+    fn srgb_profiles_leave_colors_alone() {
+        let srgb = Profile::parse(&profile(SRGB_PRIMARIES, srgb_curve())).unwrap();
+        assert_eq!(rounded(srgb.to_srgb([0.5, 0.2, 0.8])), [128, 51, 204]);
+        assert_eq!(rounded(srgb.to_srgb([1.0, 1.0, 1.0])), [255, 255, 255]);
+    }
+
+    #[test]
+    fn wide_gamut_colors_are_converted_and_clipped() {
+        let p3 = Profile::parse(&profile(DISPLAY_P3_PRIMARIES, srgb_curve())).unwrap();
+        let [r, g, b] = rounded(p3.to_srgb([0.0, 1.0, 0.0]));
+        assert_eq!((r, b), (0, 0));
+        assert!(g == 255);
+        // A mid P3 red is more saturated than the same sRGB numbers.
+        let [r, g, b] = rounded(p3.to_srgb([0.8, 0.3, 0.3]));
+        assert!(r > 204 && g < 77 && b < 77, "{:?}", (r, g, b));
+        assert_eq!(rounded(p3.to_srgb([1.0, 1.0, 1.0])), [255, 255, 255]);
+    }
+
+    #[test]
+    fn curves_and_malformed_profiles() {
+        assert_eq!(parse_curve(b"curv\0\0\0\0\0\0\0\x01\x02\x33").unwrap(), Curve::Gamma(2.0 + 51.0 / 256.0));
+        let table = parse_curve(b"curv\0\0\0\0\0\0\0\x03\0\0\x40\0\xff\xff").unwrap();
+        assert!((table.apply(0.25) - 0.125).abs() < 0.001);
+        assert_eq!(Curve::Gamma(1.0).apply(1.5), 1.0);
+
+        assert!(Profile::parse(b"not a profile").is_err());
+        let mut truncated = profile(SRGB_PRIMARIES, srgb_curve());
+        truncated.truncate(200);
+        assert!(Profile::parse(&truncated).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::PeekerError;
+use iced::widget::image::Handle;
+use iced::{Color, Size};
+use image::{DynamicImage, ImageBuffer, Rgba};
+use xcap::image::RgbaImage;
+
+/// Largest on-screen size of the displayed image, per side.
+const MAX_DISPLAY_SIZE: f32 = 360.0;
+
+/// An image file (or pasted screenshot) that picks are taken from instead of
+/// the live screen. Positions are in image pixels.
+pub struct ImageSource {
+    pub name: String,
+    pub image: RgbaImage,
+    /// The image at 16 bits per channel, kept when the file has more than
+    /// eight so picks are not quantized.
+    deep: Option<ImageBuffer<Rgba<u16>, Vec<u16>>>,
+    /// Bits per channel of the file, at most 16.
+    pub bit_depth: u8,
+    pub handle: Handle,
+    /// The pixel under the cursor, or the last one it was over.
+    pub hover: (i32, i32),
+}
+
+impl ImageSource {
+    pub fn open(path: &std::path::Path) -> Result<Self, PeekerError> {
+        let invalid = |reason: String| PeekerError::InvalidFile { path: path.to_path_buf(), reason };
+        let image = image::open(path).map_err(|e| invalid(e.to_string()))?;
+        if image.width() == 0 || image.height() == 0 {
+            return Err(invalid("The image is empty".to_string()));
+        }
+        let name = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into());
+        Ok(Self::from_dynamic(name, image))
+    }
+
+    pub fn new(name: String, image: RgbaImage) -> Self {
+        let handle = Handle::from_rgba(image.width(), image.height(), image.as_raw().clone());
+        let hover = ((image.width() / 2) as i32, (image.height() / 2) as i32);
+        Self { name, image, deep: None, bit_depth: 8, handle, hover }
+    }
+
+    fn from_dynamic(name: String, image: DynamicImage) -> Self {
+        let color_type = image.color();
+        let bits = (color_type.bits_per_pixel() / color_type.channel_count() as u16).min(16) as u8;
+        let mut source = Self::new(name, image.to_rgba8());
+        if bits > 8 {
+            source.deep = Some(image.to_rgba16());
+            source.bit_depth = bits;
+        }
+        source
+    }
+
+    /// The color of pixel `(x, y)` at the full precision of the file.
+    pub fn color_at(&self, x: u32, y: u32) -> Option<Color> {
+        let Some(deep) = &self.deep else {
+            return crate::extract_color_at(&self.image, x, y);
+        };
+        let pixel = deep.get_pixel_checked(x, y)?;
+        let channel = |value: u16| value as f32 / u16::MAX as f32;
+        Some(Color::from_rgb(channel(pixel[0]), channel(pixel[1]), channel(pixel[2])))
+    }
+
+    /// Up to `radius` pixels on each side of `(x, y)`, clamped to the image,
+    /// and where `(x, y)` lies in that crop.
+    pub fn around(&self, (x, y): (i32, i32), radius: i32) -> Option<(RgbaImage, (u32, u32))> {
+        let (width, height) = (self.image.width() as i32, self.image.height() as i32);
+        if x < 0 || y < 0 || x >= width || y >= height {
+            return None;
+        }
+        let (left, top) = ((x - radius).max(0), (y - radius).max(0));
+        let (right, bottom) = ((x + radius + 1).min(width), (y + radius + 1).min(height));
+        let crop = image::imageops::crop_imm(
+            &self.image,
+            left as u32,
+            top as u32,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        );
+        Some((crop.to_image(), ((x - left) as u32, (y - top) as u32)))
+    }
+
+    /// The image scaled down to fit [`MAX_DISPLAY_SIZE`], keeping its aspect
+    /// ratio; small images are shown at 1:1.
+    pub fn display_size(&self) -> Size {
+        let (width, height) = (self.image.width() as f32, self.image.height() as f32);
+        let scale = (MAX_DISPLAY_SIZE / width.max(height)).min(1.0);
+        Size::new(width * scale, height * scale)
+    }
+
+    /// Maps a point on the displayed image to the image pixel under it.
+    pub fn pixel_at(&self, point: iced::Point) -> (i32, i32) {
+        let display = self.display_size();
+        let x = (point.x / display.width * self.image.width() as f32) as i32;
+        let y = (point.y / display.height * self.image.height() as f32) as i32;
+        (x.clamp(0, self.image.width() as i32 - 1), y.clamp(0, self.image.height() as i32 - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn large_images_are_scaled_to_fit() {
+        let source = ImageSource::new("wide.png".into(), RgbaImage::new(720, 180));
+
+        assert_eq!(source.display_size(), Size::new(360.0, 90.0));
+        assert_eq!(source.pixel_at(iced::Point::new(180.0, 45.0)), (360, 90));
+        assert_eq!(source.pixel_at(iced::Point::new(400.0, -3.0)), (719, 0));
+    }
+
+    #[test]
+    fn crops_around_a_pixel_stop_at_the_edges() {
+        let source = ImageSource::new("small.png".into(), RgbaImage::new(10, 8));
+
+        let (crop, start) = source.around((1, 6), 3).unwrap();
+        assert_eq!((crop.dimensions(), start), ((5, 5), (1, 3)));
+        let (crop, start) = source.around((5, 4), 1).unwrap();
+        assert_eq!((crop.dimensions(), start), ((3, 3), (1, 1)));
+        assert!(source.around((10, 0), 3).is_none());
+        assert!(source.around((-1, 0), 3).is_none());
+    }
+
+    #[test]
+    fn sixteen_bit_images_keep_their_precision() {
+        let deep = ImageBuffer::from_pixel(2, 2, Rgba([257 * 128 + 1, 0, u16::MAX, u16::MAX]));
+        let source = ImageSource::from_dynamic("deep.png".into(), DynamicImage::ImageRgba16(deep));
+
+        assert_eq!(source.bit_depth, 16);
+        let color = source.color_at(1, 1).unwrap();
+        assert_eq!(crate::formats::channel_values(&color, source.bit_depth), [32897, 0, 65535]);
+        assert_eq!(crate::formats::channel_values(&color, 8), [128, 0, 255]);
+        assert!(source.color_at(2, 0).is_none());
+        assert_eq!(ImageSource::new("flat.png".into(), RgbaImage::new(1, 1)).bit_depth, 8);
+    }
+}
@@ -0,0 +1,348 @@
+use crate::error::PeekerError;
+use chrono::NaiveDate;
+use iced::Color;
+use palette::{Hsl, IntoColor, Lab, Oklch, Srgb};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedColor {
+    pub name: Option<String>,
+    pub color: Color,
+    /// The day the color was picked, in formats that record it.
+    pub date: Option<NaiveDate>,
+}
+
+pub fn import_palette(path: &Path) -> Result<Vec<ImportedColor>, PeekerError> {
+    match read_palette(path)? {
+        colors if colors.is_empty() => {
+            Err(PeekerError::InvalidFile { path: path.to_path_buf(), reason: "No colors found".to_string() })
+        },
+        colors => Ok(colors),
+    }
+}
+
+/// [`import_palette`], but a file without colors is an empty palette rather
+/// than an error, as for a shared palette whose colors were all removed.
+pub fn read_palette(path: &Path) -> Result<Vec<ImportedColor>, PeekerError> {
+    let bytes = std::fs::read(path).map_err(|source| PeekerError::ReadFile { path: path.to_path_buf(), source })?;
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+
+    let colors = match extension.as_deref() {
+        Some("ase") => parse_ase(&bytes),
+        Some("gpl") => parse_gpl(&String::from_utf8_lossy(&bytes)),
+        _ => Ok(parse_hex_list(&String::from_utf8_lossy(&bytes))),
+    };
+    colors.map_err(|reason| PeekerError::InvalidFile { path: path.to_path_buf(), reason: reason.to_string() })
+}
+
+/// Parses a GIMP palette: a `GIMP Palette` header, optional `Name:`/`Columns:`
+/// lines and `#` comments, then one `R G B [name]` entry per line.
+fn parse_gpl(contents: &str) -> Result<Vec<ImportedColor>, &'static str> {
+    let mut lines = contents.lines();
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        return Err("Missing 'GIMP Palette' header");
+    }
+
+    let mut colors = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let mut channel = || fields.next().and_then(|value| value.parse::<u8>().ok());
+        let (Some(r), Some(g), Some(b)) = (channel(), channel(), channel()) else {
+            continue;
+        };
+        let name = fields.collect::<Vec<_>>().join(" ");
+
+        colors.push(ImportedColor {
+            name: if name.is_empty() || name == "Untitled" { None } else { Some(name) },
+            color: Color::from_rgb8(r, g, b),
+            date: None,
+        });
+    }
+    Ok(colors)
+}
+
+/// Parses an Adobe Swatch Exchange file. Group blocks are flattened and only
+/// the color entries are kept.
+fn parse_ase(bytes: &[u8]) -> Result<Vec<ImportedColor>, &'static str> {
+    const COLOR_ENTRY: u16 = 0x0001;
+
+    let mut reader = ByteReader { bytes, offset: 0 };
+    if reader.take(4)? != b"ASEF" {
+        return Err("Missing 'ASEF' signature");
+    }
+    let _version = (reader.u16()?, reader.u16()?);
+    let block_count = reader.u32()?;
+
+    let mut colors = Vec::new();
+    for _ in 0..block_count {
+        let block_type = reader.u16()?;
+        let length = reader.u32()? as usize;
+        let block = reader.take(length)?;
+        if block_type != COLOR_ENTRY {
+            continue;
+        }
+
+        let mut block = ByteReader { bytes: block, offset: 0 };
+        let name_len = block.u16()? as usize;
+        let name: Vec<u16> = (0..name_len).map(|_| block.u16()).collect::<Result<_, _>>()?;
+        let name = String::from_utf16_lossy(&name).trim_end_matches('\0').to_string();
+
+        let model = block.take(4)?;
+        let color = match model {
+            b"RGB " => Color::from_rgb(block.f32()?, block.f32()?, block.f32()?),
+            b"Gray" => {
+                let gray = block.f32()?;
+                Color::from_rgb(gray, gray, gray)
+            },
+            b"CMYK" => {
+                let (c, m, y, k) = (block.f32()?, block.f32()?, block.f32()?, block.f32()?);
+                Color::from_rgb((1.0 - c) * (1.0 - k), (1.0 - m) * (1.0 - k), (1.0 - y) * (1.0 - k))
+            },
+            b"LAB " => {
+                let lab = Lab::new(block.f32()? * 100.0, block.f32()?, block.f32()?);
+                let rgb: Srgb = lab.into_color();
+                Color::from_rgb(rgb.red.clamp(0.0, 1.0), rgb.green.clamp(0.0, 1.0), rgb.blue.clamp(0.0, 1.0))
+            },
+            _ => continue,
+        };
+
+        colors.push(ImportedColor { name: if name.is_empty() { None } else { Some(name) }, color, date: None });
+    }
+    Ok(colors)
+}
+
+/// Parses a plain list of hex colors, one per line, with or without the
+/// leading `#`. Anything after the color on a line is treated as its name,
+/// except a trailing `YYYY-MM-DD` date, and lines that don't start with a
+/// color are skipped.
+fn parse_hex_list(contents: &str) -> Vec<ImportedColor> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (token, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let color = parse_hex(token)?;
+            let rest = rest.trim();
+            let (name, last) = rest.rsplit_once(char::is_whitespace).unwrap_or(("", rest));
+            let (name, date) = match NaiveDate::parse_from_str(last, "%Y-%m-%d") {
+                Ok(date) => (name.trim(), Some(date)),
+                Err(_) => (rest, None),
+            };
+            Some(ImportedColor { name: if name.is_empty() { None } else { Some(name.to_string()) }, color, date })
+        })
+        .collect()
+}
+
+/// Parses a shared coolors.co link such as `https://coolors.co/aabbcc-ddeeff`
+/// or `https://coolors.co/palette/aabbcc-ddeeff`.
+pub fn parse_coolors_url(url: &str) -> Option<Vec<ImportedColor>> {
+    let (_, path) = url.trim().split_once("coolors.co/")?;
+    let path = path.split(['?', '#']).next()?;
+    let segment = path.trim_end_matches('/').rsplit('/').next()?;
+
+    let colors: Option<Vec<ImportedColor>> = segment
+        .split('-')
+        .map(|hex| Some(hex).filter(|hex| hex.len() == 6).and_then(parse_hex))
+        .map(|color| color.map(|color| ImportedColor { name: None, color, date: None }))
+        .collect();
+    colors.filter(|colors| !colors.is_empty())
+}
+
+pub fn parse_hex(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, len: usize| u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok();
+    match hex.len() {
+        3 => {
+            let (r, g, b) = (channel(0, 1)?, channel(1, 1)?, channel(2, 1)?);
+            Some(Color::from_rgb8(r * 17, g * 17, b * 17))
+        },
+        6 => Some(Color::from_rgb8(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+        _ => None,
+    }
+}
+
+/// Parses a color as written in CSS or code: `#rgb`, `#rrggbb`, `rgb()`,
+/// `rgba()`, `hsl()` or `oklch()`, in either comma or space syntax. Alpha is
+/// ignored.
+pub fn parse_css_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if value.starts_with('#') {
+        return parse_hex(value);
+    }
+
+    let (function, arguments) = value.strip_suffix(')')?.split_once('(')?;
+    let arguments: Vec<&str> = arguments.split([',', ' ', '/']).filter(|argument| !argument.is_empty()).collect();
+    let (first, second, third) = match arguments[..] {
+        [first, second, third] | [first, second, third, _] => (first, second, third),
+        _ => return None,
+    };
+    let number = |argument: &str| argument.trim_end_matches("deg").parse::<f32>().ok();
+    let percentage = |argument: &str| argument.strip_suffix('%').and_then(|value| value.parse::<f32>().ok());
+    let fraction = |argument: &str| percentage(argument).map(|value| value / 100.0).or_else(|| number(argument));
+
+    let rgb: Srgb = match function.trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => {
+            let channel = |argument: &str| {
+                percentage(argument).map(|value| value / 100.0).or_else(|| number(argument).map(|value| value / 255.0))
+            };
+            Srgb::new(channel(first)?, channel(second)?, channel(third)?)
+        },
+        "hsl" | "hsla" => {
+            let hsl: Hsl = Hsl::new(number(first)?, fraction(second)?, fraction(third)?);
+            hsl.into_color()
+        },
+        "oklch" => {
+            let oklch: Oklch = Oklch::new(fraction(first)?, number(second)?, number(third)?);
+            oklch.into_color()
+        },
+        _ => return None,
+    };
+    Some(Color::from_rgb(rgb.red.clamp(0.0, 1.0), rgb.green.clamp(0.0, 1.0), rgb.blue.clamp(0.0, 1.0)))
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], &'static str> {
+        let end =
+            self.offset.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or("Unexpected end of file")?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], &'static str> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u16(&mut self) -> Result<u16, &'static str> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, &'static str> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, &'static str> {
+        self.array().map(f32::from_be_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn imported(name: Option<&str>, r: u8, g: u8, b: u8) -> ImportedColor {
+        ImportedColor { name: name.map(str::to_string), color: Color::from_rgb8(r, g, b), date: None }
+    }
+
+    #[test]
+    fn gpl_entries_with_and_without_names() {
+        let contents =
+            "GIMP Palette\nName: Brand\nColumns: 4\n# comment\n255   0   0\tPrimary red\n 58 123 213\tUntitled\n";
+
+        assert_eq!(
+            parse_gpl(contents).unwrap(),
+            vec![imported(Some("Primary red"), 255, 0, 0), imported(None, 58, 123, 213)]
+        );
+    }
+
+    #[test]
+    fn css_color_syntaxes() {
+        let accent = Some(Color::from_rgb8(0x3A, 0x7B, 0xD5));
+        assert_eq!(parse_css_color(" #3a7bd5 "), accent);
+        assert_eq!(parse_css_color("rgb(58, 123, 213)"), accent);
+        assert_eq!(parse_css_color("rgba(58 123 213 / 50%)"), accent);
+        assert_eq!(parse_css_color("rgb(100%, 0%, 0%)"), Some(Color::from_rgb(1.0, 0.0, 0.0)));
+        assert_eq!(parse_css_color("hsl(0deg 100% 50%)").map(Color::into_rgba8), Some([255, 0, 0, 255]));
+        assert_eq!(parse_css_color("oklch(1 0 0)").map(Color::into_rgba8), Some([255, 255, 255, 255]));
+        assert_eq!(parse_css_color("rgb(1, 2)"), None);
+        assert_eq!(parse_css_color("3a7bd5"), None);
+        assert_eq!(parse_css_color("translate(1px, 2px, 3px)"), None);
+    }
+
+    #[test]
+    fn gpl_requires_header() {
+        assert!(parse_gpl("255 0 0 Red\n").is_err());
+    }
+
+    #[test]
+    fn hex_list_accepts_short_and_unprefixed_values() {
+        let contents = "#3A7BD5 accent\nabc\n\nnot a color\n#12345\n";
+
+        assert_eq!(
+            parse_hex_list(contents),
+            vec![imported(Some("accent"), 0x3A, 0x7B, 0xD5), imported(None, 0xAA, 0xBB, 0xCC)]
+        );
+    }
+
+    #[test]
+    fn plain_text_lines_keep_names_and_dates() {
+        let contents = "#AABBCC  primary/button/hover  2025-10-01\n#3A7BD5  2025-10-02\n#FF0000  Brand red\n";
+        let dated = |name: Option<&str>, r, g, b, day| ImportedColor {
+            date: NaiveDate::from_ymd_opt(2025, 10, day),
+            ..imported(name, r, g, b)
+        };
+
+        assert_eq!(
+            parse_hex_list(contents),
+            vec![
+                dated(Some("primary/button/hover"), 0xAA, 0xBB, 0xCC, 1),
+                dated(None, 0x3A, 0x7B, 0xD5, 2),
+                imported(Some("Brand red"), 0xFF, 0x00, 0x00),
+            ]
+        );
+    }
+
+    #[test]
+    fn ase_rgb_entry_inside_group() {
+        let mut color_block = Vec::new();
+        color_block.extend_from_slice(&4u16.to_be_bytes());
+        for unit in "Red\0".encode_utf16() {
+            color_block.extend_from_slice(&unit.to_be_bytes());
+        }
+        color_block.extend_from_slice(b"RGB ");
+        for channel in [1.0f32, 0.0, 0.0] {
+            color_block.extend_from_slice(&channel.to_be_bytes());
+        }
+        color_block.extend_from_slice(&2u16.to_be_bytes());
+
+        let mut bytes = b"ASEF".to_vec();
+        bytes.extend_from_slice(&[0, 1, 0, 0]);
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&0xC001u16.to_be_bytes());
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+        bytes.extend_from_slice(&0x0001u16.to_be_bytes());
+        bytes.extend_from_slice(&(color_block.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&color_block);
+        bytes.extend_from_slice(&0xC002u16.to_be_bytes());
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+
+        assert_eq!(parse_ase(&bytes).unwrap(), vec![imported(Some("Red"), 255, 0, 0)]);
+    }
+
+    #[test]
+    fn coolors_links() {
+        let expected = vec![imported(None, 0xAA, 0xBB, 0xCC), imported(None, 0x3A, 0x7B, 0xD5)];
+
+        assert_eq!(parse_coolors_url("https://coolors.co/aabbcc-3a7bd5").unwrap(), expected);
+        assert_eq!(parse_coolors_url("https://coolors.co/palette/aabbcc-3a7bd5?ref=share").unwrap(), expected);
+        assert_eq!(parse_coolors_url("https://example.com/aabbcc"), None);
+        assert_eq!(parse_coolors_url("https://coolors.co/generate"), None);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::import;
+use iced::Color;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+
+pub const URL_SCHEME: &str = "pixel-peeker";
+
+/// Local port the first instance listens on so later launches can forward
+/// their `pixel-peeker://` URL to it instead of opening a second window.
+const INSTANCE_ADDR: (&str, u16) = ("127.0.0.1", 7330);
+
+/// How long a forwarding launch may take to send its URL, so a client that
+/// connects and stays silent cannot block the ones after it.
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The longest URL line that is read from a forwarding launch.
+const MAX_URL_LEN: u64 = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UrlCommand {
+    /// `pixel-peeker://pick`: freeze the pixel under the cursor.
+    Pick,
+    /// `pixel-peeker://show?color=aabbcc`: freeze the given color.
+    Show(Color),
+}
+
+pub fn parse_url(url: &str) -> Option<UrlCommand> {
+    let rest = url.trim().strip_prefix(URL_SCHEME)?.strip_prefix("://")?;
+    let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+    match action.trim_end_matches('/') {
+        "pick" => Some(UrlCommand::Pick),
+        "show" => query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("color="))
+            .and_then(|hex| import::parse_hex(&hex.replace("%23", "#")))
+            .map(UrlCommand::Show),
+        _ => None,
+    }
+}
+
+/// URL commands for the running instance, from its own command line and from
+/// later launches.
+#[derive(Clone)]
+pub struct Instance {
+    commands: Arc<Mutex<mpsc::Receiver<UrlCommand>>>,
+}
+
+impl Instance {
+    pub fn pending_commands(&self) -> Vec<UrlCommand> {
+        self.commands.lock().map(|commands| commands.try_iter().collect()).unwrap_or_default()
+    }
+}
+
+pub enum Claim {
+    Primary(Instance),
+    /// The URL was handed to an instance that is already running.
+    Forwarded,
+}
+
+/// Becomes the primary instance, or forwards `url` to the existing one. A
+/// launch without a URL always starts normally.
+pub fn claim(url: Option<&str>) -> Claim {
+    let (sender, receiver) = mpsc::channel();
+    let instance = Instance { commands: Arc::new(Mutex::new(receiver)) };
+
+    match TcpListener::bind(INSTANCE_ADDR) {
+        Ok(listener) => {
+            let forwarded = sender.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    if stream.set_read_timeout(Some(FORWARD_TIMEOUT)).is_err() {
+                        continue;
+                    }
+                    let mut line = String::new();
+                    if BufReader::new(stream.take(MAX_URL_LEN)).read_line(&mut line).is_ok()
+                        && let Some(command) = parse_url(&line)
+                    {
+                        let _ = forwarded.send(command);
+                    }
+                }
+            });
+        },
+        Err(_) => {
+            if let Some(url) = url
+                && TcpStream::connect(INSTANCE_ADDR).and_then(|mut stream| writeln!(stream, "{}", url)).is_ok()
+            {
+                return Claim::Forwarded;
+            }
+        },
+    }
+
+    if let Some(command) = url.and_then(parse_url) {
+        let _ = sender.send(command);
+    }
+    Claim::Primary(instance)
+}
+
+/// Registers this executable as the handler for `pixel-peeker://` URLs for
+/// the current user.
+pub fn register_url_scheme() -> Result<(), Box<dyn std::error::Error>> {
+    let exe = std::env::current_exe()?;
+
+    if cfg!(target_os = "windows") {
+        let key = format!(r"HKCU\Software\Classes\{}", URL_SCHEME);
+        let command_key = format!(r"{}\shell\open\command", key);
+        let command = format!("\"{}\" \"%1\"", exe.display());
+        for args in [
+            vec!["add", key.as_str(), "/ve", "/d", "URL:Pixel Peeker", "/f"],
+            vec!["add", key.as_str(), "/v", "URL Protocol", "/d", "", "/f"],
+            vec!["add", command_key.as_str(), "/ve", "/d", command.as_str(), "/f"],
+        ] {
+            let status = std::process::Command::new("reg").args(&args).status()?;
+            if !status.success() {
+                return Err(format!("reg {} failed", args.join(" ")).into());
+            }
+        }
+        Ok(())
+    } else if cfg!(target_os = "macos") {
+        Err("On macOS the URL scheme is declared by the app bundle's Info.plist (CFBundleURLTypes)".into())
+    } else {
+        let applications = directories::BaseDirs::new()
+            .map(|dirs| dirs.data_dir().join("applications"))
+            .ok_or("Could not determine applications directory")?;
+        std::fs::create_dir_all(&applications)?;
+
+        let desktop_file = "pixel-peeker-url-handler.desktop";
+        let entry = format!(
+            "[Desktop Entry]\nType=Application\nName=Pixel Peeker\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+            exe.display(),
+            URL_SCHEME
+        );
+        std::fs::write(applications.join(desktop_file), entry)?;
+
+        let status = std::process::Command::new("xdg-mime")
+            .args(["default", desktop_file, &format!("x-scheme-handler/{}", URL_SCHEME)])
+            .status()?;
+        if !status.success() {
+            return Err("xdg-mime failed to register the handler".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn url_commands() {
+        assert_eq!(parse_url("pixel-peeker://pick"), Some(UrlCommand::Pick));
+        assert_eq!(parse_url("pixel-peeker://pick/\n"), Some(UrlCommand::Pick));
+        assert_eq!(
+            parse_url("pixel-peeker://show?color=3a7bd5"),
+            Some(UrlCommand::Show(Color::from_rgb8(0x3A, 0x7B, 0xD5)))
+        );
+        assert_eq!(
+            parse_url("pixel-peeker://show?source=web&color=%233A7BD5"),
+            Some(UrlCommand::Show(Color::from_rgb8(0x3A, 0x7B, 0xD5)))
+        );
+        assert_eq!(parse_url("pixel-peeker://show?color=nope"), None);
+        assert_eq!(parse_url("https://pick"), None);
+    }
+}
@@ -1,39 +1,251 @@
+mod api;
+mod batch;
+mod capture;
+mod commands;
+mod crash;
+#[cfg(target_os = "linux")]
+mod dbus;
+mod diff;
+mod error;
+mod export;
+mod formats;
+mod history;
+mod history_groups;
+mod history_store;
+mod i18n;
+mod icc;
+mod image_source;
+mod import;
+mod instance;
+mod mcp;
+mod measure;
+mod metrics;
+mod named_colors;
+mod palettes;
+mod picker;
+mod pipe;
+mod plugins;
+mod recorder;
+mod ruler;
+mod shared_palette;
+mod shortcuts;
+mod tokens;
+mod update;
+mod watch;
+mod workspaces;
+
 use device_query::{DeviceQuery, DeviceState, Keycode};
-use iced::widget::{Canvas, Column, Container, Row, button, canvas, container, text};
+use error::PeekerError;
+use export::{ExportOptions, HistoryFormat, PaletteFormat, Swatch};
+use formats::{ChannelStyle, ColorFormat, FormatOptions, channel_values, format_color, format_color_with};
+use history_groups::HistoryGrouping;
+use iced::widget::{
+    Canvas, Column, Container, MouseArea, Row, button, canvas, container, mouse_area, pick_list, scrollable, text,
+    text_input, toggler, tooltip,
+};
 use iced::{
     Background, Border, Color, Element, Length, Point, Rectangle, Renderer, Size, Subscription, Task, Theme, mouse,
     window,
 };
-use palette::{Hsl, Hsv, IntoColor, Oklch, Srgb};
+use palettes::PaletteLibrary;
+use picker::{PickerState, Transition};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use xcap::Monitor;
 
 const PREVIEW_SIZE: u32 = 21;
-const MAX_COLOR_HISTORY: usize = 10;
+/// Unpinned history entries kept; enough for days of picks, which the
+/// history groups keep browsable.
+const MAX_COLOR_HISTORY: usize = 200;
 const PREVIEW_CANVAS_SIZE: f32 = 168.0;
+const MAX_UNDO_STEPS: usize = 20;
+const TOAST_DURATION_SECS: u64 = 8;
+/// How far from the frozen pixel the region measurement looks, per side.
+const REGION_SEARCH_RADIUS: i32 = 512;
+/// Window size in widget mode.
+const WIDGET_SIZE: Size = Size::new(220.0, 64.0);
+const MIN_WINDOW_SIZE: Size = Size::new(400.0, 300.0);
+const COMMAND_PALETTE_INPUT: &str = "command-palette";
+/// How many matches the command palette lists; Enter runs the first.
+const COMMAND_PALETTE_RESULTS: usize = 8;
+/// Tick interval while capturing, in milliseconds, normally and in
+/// battery-saver mode. Recordings cannot sample faster than this.
+const CAPTURE_TICK_MS: u16 = 33;
+const LOW_POWER_TICK_MS: u16 = 100;
+/// Bounds of the UI scale and font size settings; loaded values are clamped
+/// into them so a hand-edited file cannot shrink the UI to nothing.
+const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=3.0;
+const FONT_SIZE_RANGE: std::ops::RangeInclusive<f32> = 10.0..=24.0;
+const DEFAULT_FONT_SIZE: f32 = 16.0;
 
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "ruler") {
+        return ruler::run(&args[1..]);
+    }
+    if args.first().is_some_and(|arg| arg == "batch") {
+        if let Err(e) = batch::run(&args[1..]) {
+            eprintln!("Batch pick failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if args.first().is_some_and(|arg| arg == "mcp") {
+        if let Err(e) = mcp::run() {
+            eprintln!("MCP server stopped: {}", e);
+        }
+        return Ok(());
+    }
+    if args.iter().any(|arg| arg == "--register-url-scheme") {
+        match instance::register_url_scheme() {
+            Ok(()) => println!("Registered {}:// URLs", instance::URL_SCHEME),
+            Err(e) => eprintln!("Failed to register URL scheme: {}", e),
+        }
+        return Ok(());
+    }
+
+    let url = args.iter().find(|arg| arg.starts_with(&format!("{}://", instance::URL_SCHEME)));
+    let instance = match instance::claim(url.map(String::as_str)) {
+        instance::Claim::Primary(instance) => instance,
+        instance::Claim::Forwarded => return Ok(()),
+    };
+
+    crash::install_panic_hook();
     let settings = Settings::load();
+    let palette_library = PaletteLibrary::load();
     let window_settings = create_window_settings(&settings);
+    let stdin_mode = args.iter().any(|arg| arg == "--stdin");
+    let font_size = settings.font_size;
 
-    iced::application(move || App::new(settings.clone()), App::update, App::view)
-        .title("Pixel Peeker")
-        .subscription(App::subscription)
-        .theme(Theme::Dark)
-        .window(window_settings)
-        .run()
+    iced::daemon(
+        move || {
+            let (main_window, open) = window::open(window_settings.clone());
+            let mut app = App::new(settings.clone(), palette_library.clone(), instance.clone(), main_window);
+            if stdin_mode {
+                app.stdin = Some(pipe::StdinCommands::start());
+            }
+            (app, open.discard())
+        },
+        App::update,
+        App::view,
+    )
+    .title(App::title)
+    .subscription(App::subscription)
+    .theme(App::theme)
+    .style(App::style)
+    .scale_factor(App::scale_factor)
+    .settings(iced::Settings { default_text_size: font_size.into(), ..iced::Settings::default() })
+    .run()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct Settings {
     window_width: f32,
     window_height: f32,
     window_x: Option<i32>,
     window_y: Option<i32>,
+    /// History saved by versions before it moved to its own file; only read,
+    /// until the history file exists.
+    #[serde(skip_serializing)]
     color_history: Vec<SerializableColor>,
     zoom_factor: f32,
+    /// The point of the preview that stays put while zooming in.
+    zoom_anchor: ZoomAnchor,
     always_on_top: bool,
+    swatch_size: f32,
+    /// Active history workspace; `None` is the default workspace, whose
+    /// history is in the history file.
+    workspace: Option<String>,
+    frozen: Option<FrozenState>,
+    /// Colors kept in slots A, B and C, restored at startup.
+    slots: [Option<SlotState>; 3],
+    /// Move a re-picked color to the end of the history instead of adding it again.
+    dedupe_history: bool,
+    history_grouping: HistoryGrouping,
+    /// Show a desktop notification whenever a color is frozen.
+    notify_on_pick: bool,
+    /// Play a short click whenever a color is frozen or copied.
+    feedback_sound: bool,
+    /// File that every frozen color is appended to, as CSV or JSON lines.
+    pick_log: Option<std::path::PathBuf>,
+    /// Shell command run on every freeze, with the color in `PP_*` variables.
+    pick_command: Option<String>,
+    /// Serve the localhost automation API on `api_port`.
+    api_enabled: bool,
+    api_port: u16,
+    /// Register the `com.kdheepak.PixelPeeker` session bus service (Linux).
+    dbus_enabled: bool,
+    /// Design-tokens file that picks are matched against.
+    design_tokens: Option<std::path::PathBuf>,
+    /// File the history is written to whenever it changes and on exit.
+    auto_export: Option<AutoExport>,
+    /// Palette file shared with others, kept in sync with the named palette
+    /// of the same name.
+    shared_palette: Option<std::path::PathBuf>,
+    /// Named palette that picks are checked against for brand compliance.
+    compliance_palette: Option<String>,
+    /// Largest ΔE still counted as on-brand.
+    compliance_tolerance: f32,
+    /// Shell command run when the watched pixel fires, with its color in `PP_*` variables.
+    watch_command: Option<String>,
+    /// Milliseconds between samples while recording.
+    record_interval_ms: u16,
+    /// Offer to load colors that appear on the clipboard.
+    watch_clipboard: bool,
+    theme: ThemePreference,
+    /// Hex overrides for the theme's colors; unset or invalid entries keep the theme default.
+    custom_colors: CustomColors,
+    /// Multiplies the size of the whole UI, on top of the OS scale factor.
+    ui_scale: f32,
+    /// Default text size in points. Labels and headings scale with it right
+    /// away; unsized text picks it up on the next launch.
+    font_size: f32,
+    /// Shape of the region captured around the cursor.
+    preview_aspect: PreviewAspect,
+    preview_style: PreviewStyle,
+    /// Magnify the grid-style preview with linear filtering instead of
+    /// nearest-neighbor blocks.
+    smooth_preview: bool,
+    /// Show a small frameless, semi-transparent widget that floats above other
+    /// windows instead of the full window.
+    widget_mode: bool,
+    /// Battery saver: poll less often and only capture when the cursor moves.
+    low_power: bool,
+    /// The format copied by the `C` shortcut.
+    preferred_format: ColorFormat,
+    /// Formats listed under the color, in the compare panel and in the
+    /// history menu. Every format stays available in the command palette.
+    visible_formats: Vec<ColorFormat>,
+    /// UI language as a locale such as `de`; `None` follows the OS.
+    language: Option<String>,
+    /// Ask GitHub for a newer release at startup.
+    check_for_updates: bool,
+    /// Monitor to capture from, by name, for remote and virtual sessions
+    /// whose monitor bounds do not match the cursor; `None` uses the
+    /// monitor under the cursor.
+    capture_monitor: Option<String>,
+    /// Convert captures from each monitor's ICC profile to sRGB; off shows
+    /// the monitor's native values.
+    color_managed: bool,
+    /// ICC profiles chosen per monitor name, used instead of the system's.
+    monitor_profiles: std::collections::BTreeMap<String, std::path::PathBuf>,
+    /// Show the raw framebuffer value next to the profile-corrected one.
+    compare_raw_values: bool,
+    /// How colors are written when shown and copied.
+    format_options: FormatOptions,
+    /// Show the nearest Flutter `Colors` constant.
+    show_flutter: bool,
+    /// Show the nearest X11 color name.
+    show_x11: bool,
+    /// Show the nearest of the 16 terminal colors.
+    show_ansi: bool,
+    /// Also show the nearest of the 216 web-safe colors.
+    show_web_safe: bool,
+    /// Also show the nearest RAL Classic code, for matching paint.
+    show_ral: bool,
+    /// Label colors added to the history with their description, e.g. "dusty teal".
+    auto_label_history: bool,
 
     #[serde(skip)]
     path: Option<std::path::PathBuf>,
@@ -44,11 +256,50 @@ struct SerializableColor {
     r: f32,
     g: f32,
     b: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variable_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pinned: bool,
+    #[serde(flatten)]
+    metadata: PickMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AutoExport {
+    path: std::path::PathBuf,
+    format: PaletteFormat,
+}
+
+/// Where and when a history color was picked, for answering "where did this
+/// color come from" long after the fact.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PickMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    picked_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<(i32, i32)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    monitor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    window_title: Option<String>,
+    /// When the run of the app the entry was added in started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    session: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl From<Color> for SerializableColor {
     fn from(color: Color) -> Self {
-        Self { r: color.r, g: color.g, b: color.b }
+        Self {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            variable_name: None,
+            label: None,
+            pinned: false,
+            metadata: PickMetadata::default(),
+        }
     }
 }
 
@@ -58,6 +309,30 @@ impl From<SerializableColor> for Color {
     }
 }
 
+impl From<&HistoryEntry> for SerializableColor {
+    fn from(entry: &HistoryEntry) -> Self {
+        Self {
+            variable_name: entry.variable_name.clone(),
+            label: entry.label.clone(),
+            pinned: entry.pinned,
+            metadata: entry.metadata.clone(),
+            ..SerializableColor::from(entry.color)
+        }
+    }
+}
+
+impl From<SerializableColor> for HistoryEntry {
+    fn from(color: SerializableColor) -> Self {
+        Self {
+            variable_name: color.variable_name.clone(),
+            label: color.label.clone(),
+            pinned: color.pinned,
+            metadata: color.metadata.clone(),
+            color: Color::from(color),
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -67,36 +342,86 @@ impl Default for Settings {
             window_y: None,
             color_history: Vec::new(),
             zoom_factor: 1.0,
+            zoom_anchor: ZoomAnchor::Center,
             always_on_top: true,
+            swatch_size: 24.0,
+            workspace: None,
+            frozen: None,
+            slots: [None, None, None],
+            dedupe_history: false,
+            history_grouping: HistoryGrouping::None,
+            notify_on_pick: false,
+            feedback_sound: false,
+            pick_log: None,
+            pick_command: None,
+            api_enabled: false,
+            api_port: api::DEFAULT_PORT,
+            dbus_enabled: false,
+            design_tokens: None,
+            auto_export: None,
+            shared_palette: None,
+            compliance_palette: None,
+            compliance_tolerance: 2.0,
+            watch_command: None,
+            record_interval_ms: 100,
+            watch_clipboard: false,
+            theme: ThemePreference::Dark,
+            custom_colors: CustomColors::default(),
+            ui_scale: 1.0,
+            font_size: DEFAULT_FONT_SIZE,
+            preview_aspect: PreviewAspect::Square,
+            preview_style: PreviewStyle::Grid,
+            smooth_preview: false,
+            widget_mode: false,
+            low_power: false,
+            preferred_format: ColorFormat::Hex,
+            visible_formats: ColorFormat::DEFAULT_VISIBLE.to_vec(),
+            language: None,
+            check_for_updates: false,
+            capture_monitor: None,
+            color_managed: true,
+            monitor_profiles: std::collections::BTreeMap::new(),
+            compare_raw_values: false,
+            format_options: FormatOptions::default(),
+            show_flutter: false,
+            show_x11: false,
+            show_ansi: false,
+            show_web_safe: false,
+            show_ral: false,
+            auto_label_history: false,
             path: None,
         }
     }
 }
 
 impl Settings {
+    /// Prefers the autosave when it is newer than the settings file, as after
+    /// a crash, and falls back to it when the settings file is unreadable.
     fn load() -> Self {
-        if let Some(settings_path) = Self::get_settings_path() {
-            if let Ok(contents) = std::fs::read_to_string(&settings_path) {
-                if let Ok(mut settings) = serde_json::from_str::<Settings>(&contents) {
-                    settings.path = Some(settings_path);
-                    return settings;
-                }
-            }
+        let read = |path: &std::path::Path| {
+            std::fs::read_to_string(path).ok().and_then(|contents| serde_json::from_str::<Settings>(&contents).ok())
+        };
+        let recover = |file: Option<&std::path::Path>| {
+            let snapshot = crash::recover(file)?;
+            eprintln!("Recovered settings from the autosave");
+            Some(snapshot.settings)
+        };
+
+        if let Some(settings_path) = Self::get_settings_path()
+            && let Some(mut settings) =
+                recover(Some(&settings_path)).or_else(|| read(&settings_path)).or_else(|| recover(None))
+        {
+            settings.path = Some(settings_path);
+            settings.ui_scale = clamp_setting(settings.ui_scale, UI_SCALE_RANGE, 1.0);
+            settings.font_size = clamp_setting(settings.font_size, FONT_SIZE_RANGE, DEFAULT_FONT_SIZE);
+            return settings;
         }
         Self::default()
     }
 
-    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let settings_path = Self::get_settings_path().ok_or("Could not determine settings directory")?;
-
-        if let Some(parent) = settings_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create settings directory: {}", e))?;
-        }
-
-        let contents = serde_json::to_string_pretty(self)?;
-        std::fs::write(&settings_path, contents).map_err(|e| format!("Failed to write settings file: {}", e))?;
-
-        Ok(())
+    fn save(&self) -> Result<(), PeekerError> {
+        let settings_path = Self::get_settings_path().ok_or(PeekerError::NoConfigDir("settings"))?;
+        write_json(&settings_path, self)
     }
 
     fn get_settings_path() -> Option<std::path::PathBuf> {
@@ -120,6 +445,30 @@ impl Settings {
     }
 }
 
+/// Clamps a loaded value into `range`, replacing NaN and infinities with `default`.
+fn clamp_setting(value: f32, range: std::ops::RangeInclusive<f32>, default: f32) -> f32 {
+    if value.is_finite() { value.clamp(*range.start(), *range.end()) } else { default }
+}
+
+/// Writes an export or other user-chosen file in place.
+fn write_file(path: &std::path::Path, contents: Vec<u8>) -> Result<(), PeekerError> {
+    std::fs::write(path, contents).map_err(|source| PeekerError::WriteFile { path: path.to_path_buf(), source })
+}
+
+/// Writes `value` as pretty-printed JSON, creating the parent directory. The
+/// file is replaced in one rename, so a crash mid-write leaves the old one.
+pub(crate) fn write_json(path: &std::path::Path, value: &impl Serialize) -> Result<(), PeekerError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|source| PeekerError::WriteFile { path: parent.to_path_buf(), source })?;
+    }
+    let contents = serde_json::to_string_pretty(value)?;
+    let temporary = path.with_extension("json.tmp");
+    std::fs::write(&temporary, contents)
+        .and_then(|()| std::fs::rename(&temporary, path))
+        .map_err(|source| PeekerError::WriteFile { path: path.to_path_buf(), source })
+}
+
 fn create_window_settings(settings: &Settings) -> window::Settings {
     let position = if let (Some(x), Some(y)) = (settings.window_x, settings.window_y) {
         window::Position::Specific(iced::Point::new(x as f32, y as f32))
@@ -127,38 +476,432 @@ fn create_window_settings(settings: &Settings) -> window::Settings {
         window::Position::default()
     };
 
+    let (size, min_size) = if settings.widget_mode {
+        (WIDGET_SIZE, None)
+    } else {
+        (Size::new(settings.window_width, settings.window_height), Some(MIN_WINDOW_SIZE))
+    };
+
     window::Settings {
-        size: Size::new(settings.window_width, settings.window_height),
+        size,
         position,
-        min_size: Some(Size::new(400.0, 300.0)),
+        min_size,
+        decorations: !settings.widget_mode,
+        // A window's transparency is fixed once it is open, so toggling widget
+        // mode reopens the main window with these settings.
+        transparent: settings.widget_mode,
         max_size: None,
-        level: if settings.always_on_top { window::Level::AlwaysOnTop } else { window::Level::Normal },
+        level: window_level(settings),
+        exit_on_close_request: true,
+        ..window::Settings::default()
+    }
+}
+
+/// The preferences and loupe windows, which stay above the main window when it does.
+fn secondary_window_settings(settings: &Settings, size: Size) -> window::Settings {
+    window::Settings { size, level: window_level(settings), exit_on_close_request: true, ..window::Settings::default() }
+}
+
+/// A borderless window that is made fullscreen once open; see [`App::pick_overlay_view`].
+fn pick_overlay_window_settings() -> window::Settings {
+    window::Settings {
+        decorations: false,
+        transparent: true,
+        resizable: false,
+        level: window::Level::AlwaysOnTop,
         exit_on_close_request: true,
         ..window::Settings::default()
     }
 }
 
+/// The widget floats above everything regardless of the always-on-top setting.
+fn window_level(settings: &Settings) -> window::Level {
+    if settings.always_on_top || settings.widget_mode { window::Level::AlwaysOnTop } else { window::Level::Normal }
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     Tick(Instant),
     CopyColor(ColorFormat),
-    HistoryColorClicked(Color),
+    HistoryColorClicked(usize),
+    Shortcut(shortcuts::Shortcut),
+    CommandPaletteToggled(bool),
+    CommandQueryChanged(String),
+    RunFirstCommand,
+    RunCommand(Box<Message>),
+    PreferredFormatSelected(ColorFormat),
+    TogglePinned(usize),
+    HistoryLabelChanged(String),
+    HistoryContextMenu(usize),
+    SwatchPressed(SwatchSlot),
+    SwatchHovered(SwatchSlot),
+    SwatchReleased(SwatchSlot),
+    CloseContextMenu,
+    CopyHistoryColor(usize, ColorFormat),
+    RenameHistoryEntry(usize),
+    DeleteHistoryEntry(usize),
+    SetReferenceColor(usize),
+    ClearReference,
     ZoomFactor(f32),
+    SwatchSize(f32),
+    HistoryFilterChanged(String),
+    DedupeHistoryToggled(bool),
+    HistoryGroupingSelected(HistoryGrouping),
+    /// Collapses or expands the history group with this key.
+    HistoryGroupToggled(String),
+    ExportHistoryGroup(String),
+    HistoryGroupExportPathChosen(String, Option<std::path::PathBuf>),
+    ClearHistoryGroup(String),
+    NotifyOnPickToggled(bool),
+    FeedbackSoundToggled(bool),
+    PickLogToggled(bool),
+    PickLogPathChosen(Option<std::path::PathBuf>),
+    PickCommandChanged(String),
+    ApiToggled(bool),
+    DbusToggled(bool),
+    MeasureToggled(bool),
+    RulerToggled(bool),
+    MeasureRegion,
+    WatchClipboardToggled(bool),
+    CheckClipboard,
+    ClipboardChecked(Option<String>),
+    LoadClipboardColor,
+    ClipboardColorAsReference,
+    DismissClipboardColor,
+    ThemeSelected(ThemePreference),
+    PreviewAspectSelected(PreviewAspect),
+    PreviewStyleSelected(PreviewStyle),
+    SmoothPreviewToggled(bool),
+    ZoomAnchorSelected(ZoomAnchor),
+    LanguageSelected(String),
+    TogglePause,
+    CheckWindowVisibility,
+    CheckDisplays,
+    CaptureMonitorSelected(String),
+    ColorManagedToggled(bool),
+    CompareRawValuesToggled(bool),
+    FormatShownToggled(ColorFormat, bool),
+    HexShorthandToggled(bool),
+    HexWithoutHashToggled(bool),
+    ChannelStyleSelected(ColorFormat, ChannelStyle),
+    LinearizeToggled(ColorFormat, bool),
+    ShaderDecimalsSelected(u8),
+    FlutterNamesToggled(bool),
+    X11NamesToggled(bool),
+    AnsiNamesToggled(bool),
+    WebSafeToggled(bool),
+    RalToggled(bool),
+    AutoLabelHistoryToggled(bool),
+    ChooseMonitorProfile(String),
+    MonitorProfileChosen(String, Option<std::path::PathBuf>),
+    ResetMonitorProfile(String),
+    WindowMinimized(bool),
+    LowPowerToggled(bool),
+    WidgetModeToggled(bool),
+    DragWindow,
+    WidgetMenuToggled,
+    PreferencesToggled(bool),
+    LoupeToggled(bool),
+    PickOverlayToggled(bool),
+    /// The pick overlay was clicked; the pixel under the cursor is picked
+    /// once the overlay has closed.
+    OverlayPicked,
+    UiScale(f32),
+    FontSize(f32),
+    CustomColorChanged(CustomColor, String),
+    SystemThemeChecked(bool),
+    OpenImage,
+    PasteImage,
+    ImagePathChosen(Option<std::path::PathBuf>),
+    ImageHovered(iced::Point),
+    PreviewHovered(iced::Point),
+    /// Re-samples the frozen preview at the hovered cell.
+    PreviewClicked,
+    ImageClicked,
+    CloseImage,
+    StoreSnapshot(usize),
+    ClearSnapshots,
+    /// Keeps the active color in the slot.
+    StoreSlot(usize),
+    ClearSlot(usize),
+    SlotFormatSelected(usize, ColorFormat),
+    CopySlot(usize),
+    RecordToggled(bool),
+    RecordPathChosen(Option<std::path::PathBuf>),
+    RecordInterval(u16),
+    WatchToggled(bool),
+    WatchTargetChanged(String),
+    WatchCommandChanged(String),
+    LineSamplesChanged(u8),
+    SampleLine,
+    CopyGradient,
+    WorkspaceSelected(String),
+    WorkspaceNameDraftChanged(String),
+    CreateWorkspace,
     WindowResized(Size),
     WindowMoved(iced::Point),
     ToggleAlwaysOnTop,
     ClearHistory,
     SaveSettings,
-    WindowEvent(window::Event),
+    Undo,
+    DismissToast,
+    DismissError,
+    UpdateChecksToggled(bool),
+    CheckForUpdates,
+    OpenReleasePage,
+    DismissUpdate,
+    WindowEvent(window::Id, window::Event),
+    ExportFormatSelected(PaletteFormat),
+    ExportPalette(PaletteFormat),
+    CopyPluginFormat(usize, Color),
+    LoadDesignTokens,
+    CompliancePaletteSelected(Option<String>),
+    ComplianceTolerance(f32),
+    DesignTokensPathChosen(Option<std::path::PathBuf>),
+    ClearDesignTokens,
+    ChooseAutoExportPath(PaletteFormat),
+    AutoExportPathChosen(PaletteFormat, Option<std::path::PathBuf>),
+    AutoExportFormatSelected(PaletteFormat),
+    DisableAutoExport,
+    LinkSharedPalette,
+    SharedPalettePathChosen(Option<std::path::PathBuf>),
+    UnlinkSharedPalette,
+    CheckSharedPalette,
+    ExportWithPlugin(usize),
+    PluginExportPathChosen(usize, Option<std::path::PathBuf>),
+    PaletteExportPathChosen(PaletteFormat, Option<std::path::PathBuf>),
+    VariableNameChanged(usize, String),
+    TailwindScalesToggled(bool),
+    ImportPalette,
+    PaletteImportPathChosen(Option<std::path::PathBuf>),
+    PaletteSelected(String),
+    NewPalette,
+    PaletteNameDraftChanged(String),
+    RenamePalette,
+    DeletePalette,
+    AddActiveColorToPalette,
+    PaletteColorClicked(usize),
+    MovePaletteColor(String),
+    RemovePaletteColor,
+    CopyPaletteImage,
+    CopyColorImage(Color),
+    CopyCoolorsLink,
+    PasteCoolorsLink,
+    CoolorsLinkPasted(Option<String>),
+    ExportHistory,
+    HistoryExportPathChosen(Option<std::path::PathBuf>),
 }
 
-#[derive(Debug, Clone)]
-pub enum ColorFormat {
-    Rgb,
-    Hex,
-    Hsv,
-    Hsl,
-    Oklch,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemePreference {
+    Dark,
+    Light,
+    /// Follow the operating system's light/dark setting.
+    System,
+}
+
+impl ThemePreference {
+    const ALL: [ThemePreference; 3] = [ThemePreference::Dark, ThemePreference::Light, ThemePreference::System];
+
+    fn message_id(self) -> &'static str {
+        match self {
+            ThemePreference::Dark => "theme-dark",
+            ThemePreference::Light => "theme-light",
+            ThemePreference::System => "theme-system",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreviewAspect {
+    Square,
+    /// Twice as wide as it is tall, for horizontal gradients and text baselines.
+    Wide,
+}
+
+impl PreviewAspect {
+    const ALL: [PreviewAspect; 2] = [PreviewAspect::Square, PreviewAspect::Wide];
+
+    /// Width and height in pixels, both odd so there is a middle pixel.
+    fn size(self) -> (u32, u32) {
+        match self {
+            PreviewAspect::Square => (PREVIEW_SIZE, PREVIEW_SIZE),
+            PreviewAspect::Wide => (PREVIEW_SIZE * 2 - 1, PREVIEW_SIZE),
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewAspect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (width, height) = self.size();
+        write!(f, "{}×{}", width, height)
+    }
+}
+
+/// How the magnified preview is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreviewStyle {
+    Grid,
+    /// Masked to a circle around the crosshair, like a loupe.
+    Circle,
+}
+
+impl PreviewStyle {
+    const ALL: [PreviewStyle; 2] = [PreviewStyle::Grid, PreviewStyle::Circle];
+
+    fn message_id(self) -> &'static str {
+        match self {
+            PreviewStyle::Grid => "preview-grid",
+            PreviewStyle::Circle => "preview-circle",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ZoomAnchor {
+    Center,
+    Crosshair,
+    /// The point under the mouse, falling back to the crosshair.
+    Cursor,
+}
+
+impl ZoomAnchor {
+    const ALL: [ZoomAnchor; 3] = [ZoomAnchor::Center, ZoomAnchor::Crosshair, ZoomAnchor::Cursor];
+
+    /// Where on a `canvas`-sized preview, as drawn unzoomed, the anchor is.
+    fn point(self, canvas: Size, width: u32, height: u32, crosshair: (u32, u32), cursor: Option<Point>) -> Point {
+        let (cell_size, offset) = preview_layout(canvas, width, height, 1.0, Point::ORIGIN);
+        let at_crosshair = || {
+            Point::new(
+                offset.x + (crosshair.0 as f32 + 0.5) * cell_size,
+                offset.y + (crosshair.1 as f32 + 0.5) * cell_size,
+            )
+        };
+        match self {
+            ZoomAnchor::Center => Point::new(canvas.width / 2.0, canvas.height / 2.0),
+            ZoomAnchor::Crosshair => at_crosshair(),
+            ZoomAnchor::Cursor => cursor.unwrap_or_else(at_crosshair),
+        }
+    }
+
+    fn message_id(self) -> &'static str {
+        match self {
+            ZoomAnchor::Center => "zoom-center",
+            ZoomAnchor::Crosshair => "zoom-crosshair",
+            ZoomAnchor::Cursor => "zoom-cursor",
+        }
+    }
+}
+
+/// A pick list option shown under its translated name; see [`App::choices`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice<T> {
+    value: T,
+    label: String,
+}
+
+impl<T> Choice<T> {
+    fn new(value: T, label: String) -> Self {
+        Self { value, label }
+    }
+}
+
+impl<T> std::fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct CustomColors {
+    accent: Option<String>,
+    background: Option<String>,
+    text: Option<String>,
+    /// Status text while live.
+    live: Option<String>,
+    /// Status text while frozen.
+    frozen: Option<String>,
+    /// Window background while frozen.
+    frozen_background: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomColor {
+    Accent,
+    Background,
+    Text,
+    Live,
+    Frozen,
+    FrozenBackground,
+}
+
+impl CustomColor {
+    const ALL: [CustomColor; 6] = [
+        CustomColor::Accent,
+        CustomColor::Background,
+        CustomColor::Text,
+        CustomColor::Live,
+        CustomColor::Frozen,
+        CustomColor::FrozenBackground,
+    ];
+
+    fn message_id(&self) -> &'static str {
+        match self {
+            CustomColor::Accent => "custom-accent",
+            CustomColor::Background => "custom-background",
+            CustomColor::Text => "custom-text",
+            CustomColor::Live => "custom-live",
+            CustomColor::Frozen => "custom-frozen",
+            CustomColor::FrozenBackground => "custom-frozen-background",
+        }
+    }
+}
+
+impl CustomColors {
+    fn get_mut(&mut self, which: CustomColor) -> &mut Option<String> {
+        match which {
+            CustomColor::Accent => &mut self.accent,
+            CustomColor::Background => &mut self.background,
+            CustomColor::Text => &mut self.text,
+            CustomColor::Live => &mut self.live,
+            CustomColor::Frozen => &mut self.frozen,
+            CustomColor::FrozenBackground => &mut self.frozen_background,
+        }
+    }
+
+    fn raw(&self, which: CustomColor) -> &str {
+        let value = match which {
+            CustomColor::Accent => &self.accent,
+            CustomColor::Background => &self.background,
+            CustomColor::Text => &self.text,
+            CustomColor::Live => &self.live,
+            CustomColor::Frozen => &self.frozen,
+            CustomColor::FrozenBackground => &self.frozen_background,
+        };
+        value.as_deref().unwrap_or("")
+    }
+
+    fn color(&self, which: CustomColor) -> Option<Color> {
+        import::parse_hex(self.raw(which))
+    }
+}
+
+/// The button or swatch that Tab has moved to and Enter presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyboardFocus {
+    /// The preferred-format star of a visible format.
+    PreferredButton(ColorFormat),
+    /// The copy button of a visible format.
+    CopyButton(ColorFormat),
+    HistorySwatch(usize),
+    PaletteSwatch(usize),
+}
+
+/// Outline of the focused control: the theme's text color, which contrasts
+/// with both its background and the primary buttons.
+fn focus_color(theme: &Theme) -> Color {
+    theme.palette().text
 }
 
 #[derive(Debug, Clone)]
@@ -166,427 +909,4681 @@ struct ColorInfo {
     color: Color,
     position: (i32, i32),
     preview: Option<PreviewData>,
+    monitor: Option<String>,
+    /// Both readings of the pixel, when its monitor has a color profile.
+    managed: Option<ManagedColor>,
+    /// Bits per channel of the source. Screen captures are always 8; image
+    /// files can have up to 16.
+    bit_depth: u8,
 }
 
-#[derive(Debug, Clone)]
+impl ColorInfo {
+    /// A color that did not come from a capture: no preview, monitor or
+    /// profile, at 8 bits per channel.
+    fn solid(color: Color, position: (i32, i32)) -> Self {
+        Self { color, position, preview: None, monitor: None, managed: None, bit_depth: 8 }
+    }
+}
+
+/// A color kept for comparison, with the format it is shown in.
+#[derive(Debug, Clone, Copy)]
+struct ColorSlot {
+    color: Color,
+    format: ColorFormat,
+}
+
+const SLOT_NAMES: [&str; 3] = ["A", "B", "C"];
+
+/// Shift with one of these freezes into slot A, B or C.
+const SLOT_KEYS: [Keycode; 3] = [Keycode::Key1, Keycode::Key2, Keycode::Key3];
+
+/// A slot as written to the settings file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SlotState {
+    color: SerializableColor,
+    format: ColorFormat,
+}
+
+impl From<ColorSlot> for SlotState {
+    fn from(slot: ColorSlot) -> Self {
+        Self { color: SerializableColor::from(slot.color), format: slot.format }
+    }
+}
+
+impl From<SlotState> for ColorSlot {
+    fn from(state: SlotState) -> Self {
+        Self { color: Color::from(state.color), format: state.format }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ManagedColor {
+    /// The value in the framebuffer, in the monitor's native gamut.
+    raw: Color,
+    /// `raw` converted to sRGB through the monitor's profile.
+    corrected: Color,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PreviewData {
     rgb_data: Vec<u8>,
     width: u32,
     height: u32,
+    /// The cell the color was sampled from, when it was moved away from the
+    /// middle while frozen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crosshair: Option<(u32, u32)>,
+    /// `rgb_data` as an image for the smoothed preview, built on first use so
+    /// redraws reuse the uploaded texture.
+    #[serde(skip)]
+    image: std::sync::OnceLock<iced::widget::image::Handle>,
+}
+
+impl PreviewData {
+    fn image(&self) -> iced::widget::image::Handle {
+        self.image.get_or_init(|| iced::widget::image::Handle::from_rgba(self.width, self.height, self.rgba())).clone()
+    }
+
+    fn rgba(&self) -> Vec<u8> {
+        self.rgb_data.chunks_exact(3).flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255]).collect()
+    }
+
+    fn crosshair(&self) -> (u32, u32) {
+        self.crosshair.unwrap_or((self.width / 2, self.height / 2))
+    }
+
+    fn to_image(&self) -> Option<xcap::image::RgbaImage> {
+        xcap::image::RgbaImage::from_raw(self.width, self.height, self.rgba())
+    }
+
+    fn color_at(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let index = (y * self.width + x) as usize * 3;
+        let pixel = self.rgb_data.get(index..index + 3)?;
+        Some(Color::from_rgb8(pixel[0], pixel[1], pixel[2]))
+    }
+}
+
+/// A capture around a frozen pixel.
+struct Surroundings {
+    position: (i32, i32),
+    image: xcap::image::RgbaImage,
+    /// Where `position` lies in `image`.
+    start: (u32, u32),
+}
+
+/// A [`Surroundings`] capture running on its own thread, so freezing does
+/// not wait for a thousand-pixel square of the screen.
+struct SurroundingsCapture {
+    /// The capture, not yet color-managed, and the monitor it came from.
+    result: std::sync::mpsc::Receiver<Result<(Surroundings, Option<String>), PeekerError>>,
+}
+
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    color: Color,
+    variable_name: Option<String>,
+    label: Option<String>,
+    /// Pinned entries are exempt from `MAX_COLOR_HISTORY` eviction and "Clear".
+    pinned: bool,
+    metadata: PickMetadata,
+}
+
+impl HistoryEntry {
+    fn new(color: Color) -> Self {
+        Self { color, variable_name: None, label: None, pinned: false, metadata: PickMetadata::default() }
+    }
+
+    /// The explicit variable name, else one derived from the label, else `color-N`.
+    fn variable_name_or_default(&self, index: usize) -> String {
+        self.variable_name
+            .clone()
+            .or_else(|| self.label.as_deref().map(slugify).filter(|slug| !slug.is_empty()))
+            .unwrap_or_else(|| default_variable_name(index))
+    }
+
+    fn display_name(&self) -> String {
+        self.label.clone().unwrap_or_else(|| format_color(&self.color, &ColorFormat::Hex))
+    }
+
+    /// The entry as the `index`th swatch of an exported palette.
+    fn swatch(&self, index: usize) -> Swatch {
+        Swatch {
+            name: self.display_name(),
+            variable: self.variable_name_or_default(index),
+            color: self.color,
+            date: self.metadata.picked_at.map(|picked_at| picked_at.with_timezone(&chrono::Local).date_naive()),
+        }
+    }
+
+    /// An entry for an imported color, dated to the start of the day it
+    /// was picked on when the file records one.
+    fn from_import(imported: import::ImportedColor) -> Self {
+        let picked_at = imported
+            .date
+            .and_then(|date| date.and_time(chrono::NaiveTime::MIN).and_local_timezone(chrono::Local).earliest());
+        Self {
+            label: imported.name,
+            metadata: PickMetadata {
+                picked_at: picked_at.map(|picked_at| picked_at.with_timezone(&chrono::Utc)),
+                ..PickMetadata::default()
+            },
+            ..Self::new(imported.color)
+        }
+    }
+
+    fn from_pick(info: &ColorInfo, window_title: Option<String>) -> Self {
+        Self {
+            metadata: PickMetadata {
+                picked_at: Some(chrono::Utc::now()),
+                position: Some(info.position),
+                monitor: info.monitor.clone(),
+                window_title,
+                session: None,
+            },
+            ..Self::new(info.color)
+        }
+    }
+
+    fn describe(&self, i18n: &i18n::Translator) -> String {
+        let mut lines = vec![format_color(&self.color, &ColorFormat::Hex)];
+        if let Some(label) = &self.label {
+            lines.push(label.clone());
+        }
+        if let Some(picked_at) = self.metadata.picked_at {
+            let time = picked_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string();
+            lines.push(i18n.tr_with("entry-picked-at", &[("time", time.into())]));
+        }
+        if let Some((x, y)) = self.metadata.position {
+            lines.push(i18n.tr_with("entry-position", &[("x", x.into()), ("y", y.into())]));
+        }
+        if let Some(monitor) = &self.metadata.monitor {
+            lines.push(i18n.tr_with("entry-monitor", &[("name", monitor.as_str().into())]));
+        }
+        if let Some(title) = &self.metadata.window_title {
+            lines.push(i18n.tr_with("entry-window", &[("title", title.as_str().into())]));
+        }
+        lines.join("\n")
+    }
+}
+
+/// A swatch position in either the history or the active named palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwatchSlot {
+    History(usize),
+    Palette(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DragState {
+    source: SwatchSlot,
+    hover: Option<SwatchSlot>,
+}
+
+/// The frozen pick as written to the settings file, so quitting while frozen
+/// reopens on the same pixel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FrozenState {
+    color: SerializableColor,
+    position: (i32, i32),
+    preview: Option<PreviewData>,
+    monitor: Option<String>,
+    /// Bits per channel when the pick came from a deeper image than 8 bits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bit_depth: Option<u8>,
+}
+
+impl From<&ColorInfo> for FrozenState {
+    fn from(info: &ColorInfo) -> Self {
+        Self {
+            color: SerializableColor::from(info.color),
+            position: info.position,
+            preview: info.preview.clone(),
+            monitor: info.monitor.clone(),
+            bit_depth: (info.bit_depth > 8).then_some(info.bit_depth),
+        }
+    }
+}
+
+impl From<FrozenState> for ColorInfo {
+    fn from(state: FrozenState) -> Self {
+        Self {
+            preview: state.preview,
+            monitor: state.monitor,
+            bit_depth: state.bit_depth.unwrap_or(8),
+            ..Self::solid(Color::from(state.color), state.position)
+        }
+    }
 }
 
 #[derive(Default)]
 struct InputState {
     space_pressed_last_frame: bool,
+    pause_pressed_last_frame: bool,
+    slot_pressed_last_frame: Option<usize>,
     device_state: DeviceState,
 }
 
 struct App {
     current_color: Option<ColorInfo>,
-    frozen_color: Option<ColorInfo>,
+    picker: PickerState,
     input_state: InputState,
-    color_history: Vec<Color>,
+    color_history: Vec<HistoryEntry>,
     zoom_factor: f32,
+    export_format: PaletteFormat,
+    export_options: ExportOptions,
     settings: Settings,
     settings_dirty: bool,
     last_save_time: Instant,
+    /// What was last written to the settings and history files, to skip
+    /// rewriting a file whose contents did not change.
+    saved_settings: String,
+    saved_history: String,
+    /// State changed since the last autosave.
+    autosave_pending: bool,
+    next_autosave: Instant,
+    palette_library: PaletteLibrary,
+    shared_palette: Option<shared_palette::SharedPalette>,
+    palette_name_draft: String,
+    selected_palette_color: Option<usize>,
+    selected_history: Option<usize>,
+    context_menu: Option<usize>,
+    reference_color: Option<Color>,
+    drag: Option<DragState>,
+    history_filter: String,
+    /// Keys of the history groups folded away.
+    collapsed_groups: std::collections::BTreeSet<String>,
+    /// When this run of the app started, recorded on new history entries.
+    session_started: chrono::DateTime<chrono::Utc>,
+    undo_stack: Vec<Vec<HistoryEntry>>,
+    toast: Option<Toast>,
+    workspaces: Vec<String>,
+    workspace_name_draft: String,
+    api: Option<api::ApiServer>,
+    instance: instance::Instance,
+    stdin: Option<pipe::StdinCommands>,
+    plugins: plugins::Plugins,
+    design_tokens: Vec<tokens::Token>,
+    /// `Some` while measure mode is on.
+    measure: Option<measure::Measurement>,
+    /// The `pixel-peeker ruler` overlay process for the current measurement.
+    ruler: Option<std::process::Child>,
+    /// Contiguous same-color region around the frozen pixel at this position.
+    region: Option<((i32, i32), measure::Region)>,
+    /// What was around the frozen pixel when it was frozen, so measuring its
+    /// region sees the same pixels as the pick.
+    surroundings: Option<Surroundings>,
+    surroundings_capture: Option<SurroundingsCapture>,
+    /// How many colors to sample between the measured points.
+    line_samples: u8,
+    /// Colors sampled along the measured line, from A to B.
+    line_stops: Vec<Color>,
+    main_window: window::Id,
+    preferences_window: Option<window::Id>,
+    loupe_window: Option<window::Id>,
+    pick_overlay: Option<window::Id>,
+    /// Where the pick overlay was clicked, picked when it has closed so the
+    /// overlay itself is not captured.
+    overlay_pick: Option<(i32, i32)>,
+    /// The window is minimized, so nobody can see the live preview.
+    hidden: bool,
+    last_capture_position: Option<(i32, i32)>,
+    capture_backoff: capture::Backoff,
+    displays: Vec<capture::Display>,
+    color_profiles: icc::Profiles,
+    black_frames: capture::BlackFrames,
+    /// Whether the widget-mode context menu is open.
+    widget_menu: bool,
+    keyboard_focus: Option<KeyboardFocus>,
+    /// The command palette's search text, while it is open.
+    command_query: Option<String>,
+    i18n: i18n::Translator,
+    /// The most recent failure, shown until dismissed.
+    error: Option<PeekerError>,
+    update_check: Option<update::UpdateCheck>,
+    /// A newer release than this build, shown until dismissed.
+    available_update: Option<update::Release>,
+    /// Whether the OS is in dark mode, for [`ThemePreference::System`].
+    system_dark: bool,
+    /// Last clipboard text seen while watching, so each copy is offered once;
+    /// `None` until the first read.
+    clipboard_text: Option<Option<String>>,
+    /// A color found on the clipboard, waiting to be loaded or dismissed.
+    clipboard_color: Option<Color>,
+    /// The last text this app copied, which is not offered back as a color.
+    clipboard_written: Option<String>,
+    /// Kept open after copying an image: on X11 and Wayland the owner serves
+    /// pastes, so dropping it would take the image off the clipboard.
+    image_clipboard: Option<arboard::Clipboard>,
+    /// `Some` while picking from an image file instead of the screen.
+    image_source: Option<image_source::ImageSource>,
+    /// Where the cursor last was over the preview canvas.
+    preview_hover: Option<Point>,
+    /// Frozen captures before and after a change, for the preview diff.
+    snapshots: [Option<ColorInfo>; 2],
+    /// Colors kept side by side in slots A, B and C.
+    slots: [Option<ColorSlot>; 3],
+    /// `Some` while a screen pixel is being watched for changes.
+    watch: Option<watch::Watch>,
+    watch_target_draft: String,
+    /// `Some` while the color time series is being recorded.
+    recorder: Option<recorder::Recorder>,
+    #[cfg(target_os = "linux")]
+    dbus: Option<dbus::DbusService>,
+}
+
+struct Toast {
+    message: String,
+    shown_at: Instant,
 }
 
 impl App {
-    fn new(settings: Settings) -> Self {
-        let color_history: Vec<Color> = settings.color_history.iter().map(|c| Color::from(c.clone())).collect();
+    fn new(
+        settings: Settings,
+        palette_library: PaletteLibrary,
+        instance: instance::Instance,
+        main_window: window::Id,
+    ) -> Self {
+        let workspace = settings.workspace.as_deref();
+        let stored_history = crash::recover(history_store::path(workspace).as_deref())
+            .filter(|snapshot| snapshot.settings.workspace == settings.workspace)
+            .map(|snapshot| snapshot.history)
+            .unwrap_or_else(|| history_store::load(workspace, &settings.color_history));
+        let color_history: Vec<HistoryEntry> = stored_history.into_iter().map(HistoryEntry::from).collect();
+        let (design_tokens, error) = match settings.design_tokens.as_deref().map(tokens::load) {
+            Some(Ok(design_tokens)) => (design_tokens, None),
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                (Vec::new(), Some(e))
+            },
+            None => (Vec::new(), None),
+        };
+        let shared_palette = settings.shared_palette.clone().map(shared_palette::SharedPalette::new);
 
-        Self {
+        let slots = settings.slots.clone().map(|slot| slot.map(ColorSlot::from));
+        let mut app = Self {
             current_color: None,
-            frozen_color: None,
+            picker: PickerState::new(settings.frozen.clone().map(ColorInfo::from)),
             input_state: InputState::default(),
             color_history,
             zoom_factor: settings.zoom_factor,
+            export_format: PaletteFormat::Aco,
+            export_options: ExportOptions::default(),
+            i18n: i18n::Translator::new(settings.language.as_deref()),
+            error,
+            update_check: settings.check_for_updates.then(|| update::UpdateCheck::start(false)),
+            available_update: None,
             settings,
             settings_dirty: false,
             last_save_time: Instant::now(),
+            saved_settings: String::new(),
+            saved_history: String::new(),
+            autosave_pending: false,
+            next_autosave: Instant::now() + crash::AUTOSAVE_INTERVAL,
+            palette_name_draft: palette_library.active().map(|palette| palette.name.clone()).unwrap_or_default(),
+            palette_library,
+            shared_palette,
+            selected_palette_color: None,
+            selected_history: None,
+            context_menu: None,
+            reference_color: None,
+            drag: None,
+            history_filter: String::new(),
+            collapsed_groups: Default::default(),
+            session_started: chrono::Utc::now(),
+            undo_stack: Vec::new(),
+            toast: None,
+            workspaces: workspaces::list(),
+            workspace_name_draft: String::new(),
+            api: None,
+            instance,
+            stdin: None,
+            plugins: plugins::Plugins::load(),
+            design_tokens,
+            measure: None,
+            region: None,
+            surroundings: None,
+            surroundings_capture: None,
+            line_samples: 8,
+            line_stops: Vec::new(),
+            main_window,
+            preferences_window: None,
+            loupe_window: None,
+            pick_overlay: None,
+            overlay_pick: None,
+            hidden: false,
+            last_capture_position: None,
+            capture_backoff: capture::Backoff::default(),
+            displays: capture::list_displays(),
+            color_profiles: icc::Profiles::default(),
+            black_frames: capture::BlackFrames::default(),
+            widget_menu: false,
+            keyboard_focus: None,
+            command_query: None,
+            system_dark: system_prefers_dark(),
+            clipboard_text: None,
+            clipboard_color: None,
+            clipboard_written: None,
+            image_clipboard: None,
+            image_source: None,
+            preview_hover: None,
+            snapshots: [None, None],
+            slots,
+            watch: None,
+            watch_target_draft: String::new(),
+            recorder: None,
+            ruler: None,
+            #[cfg(target_os = "linux")]
+            dbus: None,
+        };
+        if app.settings.api_enabled {
+            app.start_api();
         }
+        #[cfg(target_os = "linux")]
+        if app.settings.dbus_enabled {
+            app.start_dbus();
+        }
+        app
     }
 
     fn update_settings(&mut self) {
-        self.settings.color_history = self.color_history.iter().map(|c| SerializableColor::from(*c)).collect();
         self.settings.zoom_factor = self.zoom_factor;
+        self.settings.frozen = self.picker.frozen().map(FrozenState::from);
+        self.settings.slots = self.slots.map(|slot| slot.map(SlotState::from));
         self.settings_dirty = true;
+        if let Some(api) = &self.api {
+            api.publish_history(&self.color_history);
+        }
     }
 
-    fn save_settings_if_dirty(&mut self) {
-        if self.settings_dirty {
-            if let Err(e) = self.settings.save() {
-                eprintln!("Failed to save settings: {}", e);
+    fn start_api(&mut self) {
+        match api::ApiServer::start(self.settings.api_port) {
+            Ok(server) => {
+                server.publish_history(&self.color_history);
+                self.api = Some(server);
+            },
+            Err(e) => eprintln!("Failed to start API server on port {}: {}", self.settings.api_port, e),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn start_dbus(&mut self) {
+        match dbus::DbusService::start() {
+            Ok(service) => self.dbus = Some(service),
+            Err(e) => eprintln!("Failed to register D-Bus service: {}", e),
+        }
+    }
+
+    /// Answers picks queued by the HTTP API and D-Bus service, and publishes
+    /// the color they report as current.
+    fn serve_api(&mut self) {
+        let mut picks: Vec<api::PickRequest> = Vec::new();
+        if let Some(api) = &self.api {
+            picks.extend(api.pending_picks());
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(dbus) = &self.dbus {
+            picks.extend(dbus.pending_picks());
+        }
+
+        for pick in picks {
+            let position = pick.position.unwrap_or_else(|| self.get_mouse_position());
+            let picked = self.pick_at(position);
+            pick.respond(picked);
+        }
+
+        let current = self.get_active_color().map(|info| api::ColorJson::new(info, self.is_frozen()));
+        #[cfg(target_os = "linux")]
+        if let Some(dbus) = &self.dbus {
+            dbus.publish_current(current.clone());
+        }
+        if let Some(api) = &self.api {
+            api.publish_current(current);
+        }
+    }
+
+    /// Freezes the pixel at `position` as if SPACE had been pressed there.
+    fn pick_at(&mut self, position: (i32, i32)) -> Option<api::ColorJson> {
+        if self.capture_at_position(position) {
+            self.freeze_current(None);
+        }
+        let frozen = self.picker.frozen().filter(|info| info.position == position);
+        frozen.map(|info| api::ColorJson::new(info, true))
+    }
+
+    /// Answers `--stdin` commands with one JSON line each on stdout.
+    fn serve_stdin(&mut self) -> Task<Message> {
+        let Some(stdin) = &self.stdin else {
+            return Task::none();
+        };
+
+        for command in stdin.pending_commands() {
+            let response = match command {
+                Ok(pipe::PipeCommand::Pick(position)) => {
+                    let position = position.unwrap_or_else(|| self.get_mouse_position());
+                    match self.pick_at(position) {
+                        Some(color) => serde_json::to_value(color).unwrap_or_default(),
+                        None => serde_json::json!({ "error": "capture failed" }),
+                    }
+                },
+                Ok(pipe::PipeCommand::History) => export::export_history(HistoryFormat::Json, &self.color_history)
+                    .ok()
+                    .and_then(|json| serde_json::from_slice(&json).ok())
+                    .unwrap_or_default(),
+                Ok(pipe::PipeCommand::Quit) => {
+                    println!("{}", serde_json::json!({ "ok": true }));
+                    return self.shut_down();
+                },
+                Err(error) => serde_json::json!({ "error": error }),
+            };
+            println!("{}", response);
+        }
+        Task::none()
+    }
+
+    fn serve_url_commands(&mut self) {
+        for command in self.instance.pending_commands() {
+            let position = self.get_mouse_position();
+            match command {
+                instance::UrlCommand::Pick => {
+                    self.pick_at(position);
+                },
+                instance::UrlCommand::Show(color) => {
+                    self.transition(Transition::Freeze(ColorInfo::solid(color, position)));
+                    self.update_settings();
+                },
             }
-            self.settings_dirty = false;
         }
     }
 
+    /// Snapshots the history before a destructive action and offers to undo it.
+    fn push_undo(&mut self, description: &str) {
+        self.undo_stack.push(self.color_history.clone());
+        if self.undo_stack.len() > MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.toast = Some(Toast { message: description.to_string(), shown_at: Instant::now() });
+    }
+
+    fn switch_workspace(&mut self, name: &str) {
+        self.update_settings();
+        self.save_settings_if_dirty();
+
+        self.settings.workspace = (name != workspaces::DEFAULT_WORKSPACE).then(|| name.to_string());
+        let stored_history = history_store::load(self.settings.workspace.as_deref(), &self.settings.color_history);
+        self.color_history = stored_history.into_iter().map(HistoryEntry::from).collect();
+        self.selected_history = None;
+        self.context_menu = None;
+        self.undo_stack.clear();
+        self.toast = None;
+        self.update_settings();
+        self.save_settings_if_dirty();
+    }
+
+    /// Hands the unsaved state to the panic hook and the next autosave.
+    fn stage_crash_snapshot(&mut self) {
+        crash::stage(crash::Snapshot {
+            settings: self.settings.clone(),
+            history: self.color_history.iter().map(SerializableColor::from).collect(),
+        });
+        self.autosave_pending = true;
+    }
+
+    fn poll_update_check(&mut self) {
+        let Some(result) = self.update_check.as_ref().and_then(update::UpdateCheck::poll) else {
+            return;
+        };
+        let manual = self.update_check.take().is_some_and(|check| check.manual);
+        match result {
+            Ok(release) if update::is_newer(&release.tag_name, env!("CARGO_PKG_VERSION")) => {
+                self.available_update = Some(release);
+            },
+            Ok(_) if manual => {
+                self.toast = Some(Toast { message: self.tr("up-to-date"), shown_at: Instant::now() });
+            },
+            Ok(_) => {},
+            Err(e) if manual => self.report(e),
+            Err(e) => eprintln!("Update check failed: {}", e),
+        }
+    }
+
+    /// Logs `error` and shows it in the error banner.
+    fn report(&mut self, error: PeekerError) {
+        eprintln!("{}", error);
+        self.error = Some(error);
+    }
+
+    fn palettes_changed(&mut self) {
+        self.sync_shared_palette();
+        self.selected_palette_color = None;
+        self.palette_name_draft = self.palette_library.active().map(|palette| palette.name.clone()).unwrap_or_default();
+        if let Err(e) = self.palette_library.save() {
+            self.report(e);
+        }
+        self.write_auto_export();
+    }
+
+    /// Applies what others added, removed and renamed in the shared palette
+    /// file to its named palette, then writes the palette back so our own
+    /// changes reach them. Returns whether the palette changed.
+    fn sync_shared_palette(&mut self) -> bool {
+        let Some(shared) = &mut self.shared_palette else {
+            return false;
+        };
+        let index = self.palette_library.index_or_create(&shared.palette_name());
+        let palette = &mut self.palette_library.palettes[index];
+        let changes = match shared.poll() {
+            Ok(changes) => changes.unwrap_or_default(),
+            // Writing now would overwrite changes we could not read.
+            Err(e) => {
+                self.report(e);
+                return false;
+            },
+        };
+
+        let rgba = |color: &SerializableColor| Color::from_rgb(color.r, color.g, color.b).into_rgba8();
+        let before = palette.colors.len();
+        palette.colors.retain(|color| !changes.removed.contains(&rgba(color)));
+        let mut merged = palette.colors.len() != before;
+        for (renamed, name) in changes.renamed {
+            for color in palette.colors.iter_mut().filter(|color| rgba(color) == renamed) {
+                color.label = name.clone();
+                merged = true;
+            }
+        }
+        let existing: Vec<Color> = palette.colors.iter().cloned().map(Color::from).collect();
+        for imported in shared_palette::new_colors(&existing, changes.added) {
+            let entry = HistoryEntry::from_import(imported);
+            palette.colors.push(SerializableColor::from(&entry));
+            merged = true;
+        }
+
+        let swatches = export::unique_variables(
+            palette
+                .colors
+                .iter()
+                .cloned()
+                .map(HistoryEntry::from)
+                .enumerate()
+                .map(|(index, entry)| entry.swatch(index))
+                .collect(),
+        );
+        if let Err(e) = shared.write(&swatches) {
+            self.report(e);
+        }
+        merged
+    }
+
+    /// Adds a pick to the shared palette, when one is linked, and writes it
+    /// out for the others.
+    fn share_pick(&mut self, color: Color) {
+        let Some(shared) = &self.shared_palette else {
+            return;
+        };
+        let index = self.palette_library.index_or_create(&shared.palette_name());
+        let palette = &mut self.palette_library.palettes[index];
+        if palette
+            .colors
+            .iter()
+            .all(|existing| Color::from_rgb(existing.r, existing.g, existing.b).into_rgba8() != color.into_rgba8())
+        {
+            palette.colors.push(SerializableColor::from(color));
+            self.sync_shared_palette();
+            if let Err(e) = self.palette_library.save() {
+                self.report(e);
+            }
+        }
+    }
+
+    /// Writes the current palette (the active one, or else the history) to
+    /// the auto-export file, if one is set.
+    fn write_auto_export(&mut self) {
+        let Some(AutoExport { path, format }) = &self.settings.auto_export else {
+            return;
+        };
+        let result = export::export_palette(*format, &self.palette_swatches(), &self.export_options)
+            .and_then(|contents| write_file(path, contents));
+        if let Err(e) = result {
+            self.report(e);
+        }
+    }
+
+    /// Writes the settings and history files, each only if its contents
+    /// changed since it was last written. Returns whether both files are up
+    /// to date.
+    fn save_settings_if_dirty(&mut self) -> bool {
+        let mut saved = true;
+        if self.settings_dirty {
+            let settings = serde_json::to_string(&self.settings).unwrap_or_default();
+            if settings != self.saved_settings {
+                match self.settings.save() {
+                    Ok(()) => self.saved_settings = settings,
+                    Err(e) => {
+                        self.report(e);
+                        saved = false;
+                    },
+                }
+            }
+
+            let history: Vec<SerializableColor> = self.color_history.iter().map(SerializableColor::from).collect();
+            let contents = serde_json::to_string(&(&self.settings.workspace, &history)).unwrap_or_default();
+            if contents != self.saved_history {
+                match history_store::save(self.settings.workspace.as_deref(), &history) {
+                    Ok(()) => self.saved_history = contents,
+                    Err(e) => {
+                        self.report(e);
+                        saved = false;
+                    },
+                }
+                self.write_auto_export();
+            }
+            self.settings_dirty = false;
+        }
+        saved
+    }
+
+    /// Ends the app however it is quit: saves the last changes, which the
+    /// save throttle may not have written yet, exports and closes the ruler.
+    /// The autosave is only removed once everything reached its file.
+    fn shut_down(&mut self) -> Task<Message> {
+        self.update_settings();
+        let saved = self.save_settings_if_dirty();
+        self.write_auto_export();
+        self.stop_ruler();
+        if saved {
+            crash::clean_exit();
+        }
+        iced::exit()
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle(message);
+        self.prepare_plugin_formats();
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ZoomFactor(zoom_factor) => {
                 self.zoom_factor = zoom_factor;
                 self.update_settings();
                 Task::none()
             },
-            Message::WindowResized(size) => {
-                self.settings.window_width = size.width;
-                self.settings.window_height = size.height;
+            Message::WorkspaceSelected(name) => {
+                self.switch_workspace(&name);
+                Task::none()
+            },
+            Message::WorkspaceNameDraftChanged(name) => {
+                self.workspace_name_draft = name;
+                Task::none()
+            },
+            Message::CreateWorkspace => {
+                let name = self.workspace_name_draft.trim().to_string();
+                let slug = slugify(&name);
+                if !slug.is_empty() && !self.workspaces.iter().any(|existing| slugify(existing) == slug) {
+                    self.workspaces.push(name.clone());
+                    self.workspace_name_draft.clear();
+                    self.switch_workspace(&name);
+                }
+                Task::none()
+            },
+            Message::DedupeHistoryToggled(enabled) => {
+                self.settings.dedupe_history = enabled;
                 self.settings_dirty = true;
                 Task::none()
             },
-            Message::WindowMoved(position) => {
-                self.settings.window_x = Some(position.x as i32);
-                self.settings.window_y = Some(position.y as i32);
+            Message::HistoryGroupingSelected(grouping) => {
+                self.settings.history_grouping = grouping;
                 self.settings_dirty = true;
+                self.collapsed_groups.clear();
                 Task::none()
             },
-            Message::WindowEvent(event) => {
-                match event {
-                    window::Event::Resized(size) => {
-                        return self.update(Message::WindowResized(size));
-                    },
-                    window::Event::Moved(position) => {
-                        return self.update(Message::WindowMoved(position));
-                    },
-                    window::Event::CloseRequested => {
-                        self.save_settings_if_dirty();
-                        if let Err(e) = self.settings.save() {
-                            eprintln!("Final save failed: {}", e);
-                        }
-                    },
-                    _ => {},
+            Message::HistoryGroupToggled(key) => {
+                if !self.collapsed_groups.remove(&key) {
+                    self.collapsed_groups.insert(key);
                 }
                 Task::none()
             },
-            Message::ToggleAlwaysOnTop => {
-                self.settings.always_on_top = !self.settings.always_on_top;
-                self.settings_dirty = true;
+            Message::ExportHistoryGroup(key) => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("pixel-peeker-history.csv")
+                    .add_filter(self.tr("filter-csv"), &["csv"])
+                    .add_filter(self.tr("filter-json"), &["json"])
+                    .save_file(),
+                move |handle| Message::HistoryGroupExportPathChosen(key, handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::HistoryGroupExportPathChosen(key, path) => {
+                if let Some(path) = path {
+                    let entries: Vec<HistoryEntry> = self
+                        .history_group_members(&key)
+                        .into_iter()
+                        .map(|index| self.color_history[index].clone())
+                        .collect();
+                    let result = export::export_history(HistoryFormat::from_path(&path), &entries)
+                        .and_then(|contents| write_file(&path, contents));
+                    if let Err(e) = result {
+                        self.report(e);
+                    }
+                }
                 Task::none()
             },
-            Message::ClearHistory => {
-                self.color_history.clear();
+            Message::ClearHistoryGroup(key) => {
+                self.push_undo(&self.tr("history-cleared"));
+                for index in self.history_group_members(&key).into_iter().rev() {
+                    self.color_history.remove(index);
+                }
+                self.selected_history = None;
                 self.update_settings();
                 self.save_settings_if_dirty();
                 Task::none()
             },
-            Message::SaveSettings => {
-                self.save_settings_if_dirty();
+            Message::NotifyOnPickToggled(enabled) => {
+                self.settings.notify_on_pick = enabled;
+                self.settings_dirty = true;
                 Task::none()
             },
-            Message::Tick(now) => {
-                self.update_color_picking();
-                if self.settings_dirty && now.duration_since(self.last_save_time).as_secs() >= 5 {
-                    self.save_settings_if_dirty();
+            Message::FeedbackSoundToggled(enabled) => {
+                self.settings.feedback_sound = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::PickLogToggled(true) => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("pixel-peeker-picks.csv")
+                    .add_filter(self.tr("filter-csv"), &["csv"])
+                    .add_filter(self.tr("filter-json-lines"), &["jsonl"])
+                    .save_file(),
+                |handle| Message::PickLogPathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::PickLogToggled(false) => {
+                self.settings.pick_log = None;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::PickLogPathChosen(path) => {
+                if path.is_some() {
+                    self.settings.pick_log = path;
+                    self.settings_dirty = true;
                 }
                 Task::none()
             },
-            Message::CopyColor(format) => {
-                if let Some(color_info) = self.get_active_color() {
-                    let text = format_color(&color_info.color, &format);
-                    iced::clipboard::write(text)
-                } else {
-                    Task::none()
+            Message::PickCommandChanged(command) => {
+                self.settings.pick_command = Some(command).filter(|command| !command.trim().is_empty());
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::MeasureToggled(enabled) => {
+                self.measure = enabled.then(measure::Measurement::default);
+                self.stop_ruler();
+                Task::none()
+            },
+            Message::RulerToggled(enabled) => {
+                self.stop_ruler();
+                if enabled {
+                    self.start_ruler();
+                }
+                Task::none()
+            },
+            Message::MeasureRegion => {
+                // Without a capture from freeze time, e.g. for a color loaded from
+                // history, the frozen preview is all there is to search.
+                self.poll_surroundings_capture();
+                if let Some(frozen) = self.picker.frozen() {
+                    let region = match &self.surroundings {
+                        Some(surroundings) if surroundings.position == frozen.position => {
+                            measure::contiguous_region(&surroundings.image, surroundings.start)
+                        },
+                        _ => frozen
+                            .preview
+                            .as_ref()
+                            .and_then(|preview| measure::contiguous_region(&preview.to_image()?, preview.crosshair())),
+                    };
+                    self.region = region.map(|region| (frozen.position, region));
+                }
+                Task::none()
+            },
+            Message::WatchClipboardToggled(enabled) => {
+                self.settings.watch_clipboard = enabled;
+                self.settings_dirty = true;
+                self.clipboard_color = None;
+                self.clipboard_text = None;
+                Task::none()
+            },
+            Message::CheckClipboard => iced::clipboard::read().map(Message::ClipboardChecked),
+            Message::ClipboardChecked(contents) => {
+                // The first read only records what was already there.
+                if let Some(previous) = &self.clipboard_text
+                    && *previous != contents
+                    && contents != self.clipboard_written
+                    && let Some(color) = contents.as_deref().and_then(import::parse_css_color)
+                {
+                    self.clipboard_color = Some(color);
+                }
+                self.clipboard_text = Some(contents);
+                Task::none()
+            },
+            Message::LoadClipboardColor => {
+                if let Some(color) = self.clipboard_color.take() {
+                    self.transition(Transition::Freeze(ColorInfo::solid(color, (0, 0))));
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::ClipboardColorAsReference => {
+                self.reference_color = self.clipboard_color.take().or(self.reference_color);
+                Task::none()
+            },
+            Message::DismissClipboardColor => {
+                self.clipboard_color = None;
+                Task::none()
+            },
+            Message::CheckDisplays => {
+                let displays = capture::list_displays();
+                if displays != self.displays {
+                    self.color_profiles.clear();
+                    self.displays = displays;
+                }
+                Task::none()
+            },
+            Message::ColorManagedToggled(enabled) => {
+                self.settings.color_managed = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::CompareRawValuesToggled(enabled) => {
+                self.settings.compare_raw_values = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::FormatShownToggled(format, shown) => {
+                self.settings.visible_formats.retain(|&visible| visible != format);
+                if shown {
+                    self.settings.visible_formats.push(format);
+                }
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::HexShorthandToggled(enabled) => {
+                self.settings.format_options.hex_shorthand = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::HexWithoutHashToggled(enabled) => {
+                self.settings.format_options.hex_without_hash = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ChannelStyleSelected(format, style) => {
+                self.settings.format_options.channels.insert(format, style);
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::LinearizeToggled(format, enabled) => {
+                self.settings.format_options.linearize.insert(format, enabled);
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ShaderDecimalsSelected(decimals) => {
+                self.settings.format_options.shader_decimals = decimals;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::FlutterNamesToggled(enabled) => {
+                self.settings.show_flutter = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::X11NamesToggled(enabled) => {
+                self.settings.show_x11 = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::AnsiNamesToggled(enabled) => {
+                self.settings.show_ansi = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::WebSafeToggled(enabled) => {
+                self.settings.show_web_safe = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::RalToggled(enabled) => {
+                self.settings.show_ral = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::AutoLabelHistoryToggled(enabled) => {
+                self.settings.auto_label_history = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ChooseMonitorProfile(monitor) => Task::perform(
+                rfd::AsyncFileDialog::new().add_filter(self.tr("filter-icc-profiles"), &["icc", "icm"]).pick_file(),
+                move |handle| Message::MonitorProfileChosen(monitor, handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::MonitorProfileChosen(monitor, Some(path)) => {
+                match icc::Profile::load(&path) {
+                    Ok(_) => {
+                        self.settings.monitor_profiles.insert(monitor, path);
+                        self.settings_dirty = true;
+                        self.color_profiles.clear();
+                    },
+                    Err(e) => self.report(PeekerError::ColorProfile(e)),
+                }
+                Task::none()
+            },
+            Message::MonitorProfileChosen(_, None) => Task::none(),
+            Message::ResetMonitorProfile(monitor) => {
+                self.settings.monitor_profiles.remove(&monitor);
+                self.settings_dirty = true;
+                self.color_profiles.clear();
+                Task::none()
+            },
+            Message::CaptureMonitorSelected(name) => {
+                self.settings.capture_monitor =
+                    self.displays.iter().any(|display| display.name == name).then_some(name);
+                self.settings_dirty = true;
+                self.black_frames.reset();
+                self.capture_backoff.succeeded();
+                Task::none()
+            },
+            Message::CheckWindowVisibility => window::is_minimized(self.main_window)
+                .map(|minimized| Message::WindowMinimized(minimized.unwrap_or(false))),
+            Message::WindowMinimized(minimized) => {
+                self.hidden = minimized;
+                Task::none()
+            },
+            Message::LowPowerToggled(enabled) => {
+                self.settings.low_power = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::TogglePause => {
+                self.transition(Transition::TogglePause);
+                Task::none()
+            },
+            Message::PreviewAspectSelected(aspect) => {
+                self.settings.preview_aspect = aspect;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::PreviewStyleSelected(style) => {
+                self.settings.preview_style = style;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ZoomAnchorSelected(anchor) => {
+                self.settings.zoom_anchor = anchor;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::SmoothPreviewToggled(enabled) => {
+                self.settings.smooth_preview = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ThemeSelected(theme) => {
+                self.settings.theme = theme;
+                self.settings_dirty = true;
+                self.system_dark = system_prefers_dark();
+                Task::none()
+            },
+            Message::WidgetModeToggled(enabled) => {
+                self.settings.widget_mode = enabled;
+                self.settings_dirty = true;
+                self.widget_menu = false;
+                // Once `main_window` points at the new window, closing the old one
+                // is handled like any secondary window and does not exit.
+                let previous = self.main_window;
+                let (id, open) = window::open(create_window_settings(&self.settings));
+                self.main_window = id;
+                let level = window_level(&self.settings);
+                let secondary = self.preferences_window.into_iter().chain(self.loupe_window);
+                Task::batch(
+                    [open.discard(), window::close(previous)]
+                        .into_iter()
+                        .chain(secondary.map(|id| window::set_level(id, level))),
+                )
+            },
+            Message::DragWindow => {
+                self.widget_menu = false;
+                window::drag(self.main_window)
+            },
+            Message::WidgetMenuToggled => {
+                self.widget_menu = !self.widget_menu;
+                Task::none()
+            },
+            Message::PreferencesToggled(show) => {
+                let size = Size::new(560.0, 420.0);
+                toggle_window(&mut self.preferences_window, show, || secondary_window_settings(&self.settings, size))
+            },
+            Message::LoupeToggled(show) => {
+                let size = Size::new(320.0, 360.0);
+                toggle_window(&mut self.loupe_window, show, || secondary_window_settings(&self.settings, size))
+            },
+            Message::PickOverlayToggled(show) => {
+                self.transition(if show { Transition::StartPicking } else { Transition::CancelPicking });
+                let open = toggle_window(&mut self.pick_overlay, show, pick_overlay_window_settings);
+                match self.pick_overlay {
+                    Some(id) if show => open.chain(window::set_mode(id, window::Mode::Fullscreen)),
+                    _ => open,
+                }
+            },
+            Message::OverlayPicked => {
+                self.overlay_pick = Some(self.get_mouse_position());
+                toggle_window(&mut self.pick_overlay, false, pick_overlay_window_settings)
+            },
+            Message::UiScale(scale) => {
+                self.settings.ui_scale = scale;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::FontSize(size) => {
+                self.settings.font_size = size;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::CustomColorChanged(which, value) => {
+                *self.settings.custom_colors.get_mut(which) = Some(value).filter(|value| !value.trim().is_empty());
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::LanguageSelected(name) => {
+                self.settings.language = i18n::LOCALES
+                    .iter()
+                    .find(|(_, display_name, _)| *display_name == name)
+                    .map(|(locale, _, _)| locale.to_string());
+                self.i18n = i18n::Translator::new(self.settings.language.as_deref());
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::SystemThemeChecked(dark) => {
+                self.system_dark = dark;
+                Task::none()
+            },
+            Message::Shortcut(shortcut) => self.handle_shortcut(shortcut),
+            Message::CommandPaletteToggled(open) => {
+                self.command_query = open.then(String::new);
+                if open { iced::widget::operation::focus(COMMAND_PALETTE_INPUT) } else { Task::none() }
+            },
+            Message::CommandQueryChanged(query) => {
+                self.command_query = Some(query);
+                Task::none()
+            },
+            Message::RunFirstCommand => {
+                let query = self.command_query.clone().unwrap_or_default();
+                match commands::rank(&query, self.palette_commands()).into_iter().next() {
+                    Some((_, message)) => self.update(Message::RunCommand(Box::new(message))),
+                    None => Task::none(),
+                }
+            },
+            Message::RunCommand(message) => {
+                self.command_query = None;
+                self.update(*message)
+            },
+            Message::PreferredFormatSelected(format) => {
+                self.settings.preferred_format = format;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::OpenImage => Task::perform(
+                rfd::AsyncFileDialog::new().add_filter(self.tr("filter-images"), &["png", "jpg", "jpeg"]).pick_file(),
+                |handle| Message::ImagePathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::ImagePathChosen(Some(path)) => {
+                match image_source::ImageSource::open(&path) {
+                    Ok(source) => self.open_image_source(source),
+                    Err(e) => self.report(e),
+                }
+                Task::none()
+            },
+            Message::ImagePathChosen(None) => Task::none(),
+            Message::PasteImage => {
+                match read_clipboard_image() {
+                    Ok(image) => {
+                        self.open_image_source(image_source::ImageSource::new(self.tr("clipboard-image"), image))
+                    },
+                    Err(e) => self.report(e),
+                }
+                Task::none()
+            },
+            Message::ImageHovered(point) => {
+                if let Some(source) = &mut self.image_source {
+                    source.hover = source.pixel_at(point);
                 }
+                Task::none()
+            },
+            Message::PreviewHovered(point) => {
+                self.preview_hover = Some(point);
+                Task::none()
             },
-            Message::HistoryColorClicked(color) => {
-                self.frozen_color = Some(ColorInfo { color, position: (0, 0), preview: None });
+            Message::PreviewClicked => {
+                let cell = self.picker.frozen().and_then(|frozen| {
+                    let preview = frozen.preview.as_ref()?;
+                    preview_cell_at(self.preview_hover?, preview, self.zoom_factor, self.settings.zoom_anchor)
+                });
+                if let Some(cell) = cell {
+                    self.resample_frozen(cell);
+                }
                 Task::none()
             },
+            Message::ImageClicked => {
+                if let Some(position) = self.image_source.as_ref().map(|source| source.hover) {
+                    self.handle_freeze(position, None);
+                }
+                Task::none()
+            },
+            Message::CloseImage => {
+                self.image_source = None;
+                self.transition(Transition::Unfreeze);
+                self.current_color = None;
+                Task::none()
+            },
+            Message::StoreSnapshot(index) => {
+                if let Some(frozen) = self.picker.frozen().cloned().filter(|frozen| frozen.preview.is_some()) {
+                    self.snapshots[index] = Some(frozen);
+                }
+                Task::none()
+            },
+            Message::ClearSnapshots => {
+                self.snapshots = [None, None];
+                Task::none()
+            },
+            Message::StoreSlot(slot) => {
+                if let Some(color) = self.get_active_color().map(|info| info.color) {
+                    self.keep_in_slot(slot, color);
+                }
+                Task::none()
+            },
+            Message::ClearSlot(slot) => {
+                self.slots[slot] = None;
+                self.update_settings();
+                Task::none()
+            },
+            Message::SlotFormatSelected(slot, format) => {
+                if let Some(slot) = &mut self.slots[slot] {
+                    slot.format = format;
+                }
+                self.update_settings();
+                Task::none()
+            },
+            Message::CopySlot(slot) => match self.slots[slot] {
+                Some(slot) => {
+                    self.play_feedback_sound();
+                    self.write_clipboard(self.format(&slot.color, slot.format))
+                },
+                None => Task::none(),
+            },
+            Message::LineSamplesChanged(count) => {
+                self.line_samples = count;
+                Task::none()
+            },
+            Message::SampleLine => {
+                self.line_stops.clear();
+                if let Some((Some(a), Some(b))) = self.measure.as_ref().map(|m| (m.a.as_ref(), m.b.as_ref())) {
+                    match self.sample_line(a.position, b.position, self.line_samples as usize) {
+                        Ok(colors) => self.line_stops = colors,
+                        Err(e) => self.report(e),
+                    }
+                }
+                Task::none()
+            },
+            Message::CopyGradient => match self.measure.as_ref().and_then(measure::Measurement::span) {
+                Some(span) if !self.line_stops.is_empty() => {
+                    self.play_feedback_sound();
+                    self.write_clipboard(export::css_linear_gradient(span.angle, &self.line_stops))
+                },
+                _ => Task::none(),
+            },
+            Message::RecordToggled(true) => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("pixel-peeker-recording.csv")
+                    .add_filter(self.tr("filter-csv"), &["csv"])
+                    .save_file(),
+                |handle| Message::RecordPathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::RecordToggled(false) => {
+                self.stop_recording();
+                Task::none()
+            },
+            Message::RecordPathChosen(Some(path)) => {
+                let interval_ms = self.settings.record_interval_ms.max(self.capture_tick_ms());
+                let interval = std::time::Duration::from_millis(interval_ms.into());
+                match recorder::Recorder::start(path.clone(), interval) {
+                    Ok(recorder) => self.recorder = Some(recorder),
+                    Err(source) => self.report(PeekerError::WriteFile { path, source }),
+                }
+                Task::none()
+            },
+            Message::RecordPathChosen(None) => Task::none(),
+            Message::RecordInterval(interval) => {
+                self.settings.record_interval_ms = interval;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::WatchToggled(enabled) => {
+                self.watch = enabled.then(|| {
+                    let position =
+                        self.get_active_color().map_or_else(|| self.get_mouse_position(), |info| info.position);
+                    watch::Watch::new(position, import::parse_hex(&self.watch_target_draft))
+                });
+                Task::none()
+            },
+            Message::WatchTargetChanged(target) => {
+                if let Some(watch) = &mut self.watch {
+                    watch.target = import::parse_hex(&target);
+                }
+                self.watch_target_draft = target;
+                Task::none()
+            },
+            Message::WatchCommandChanged(command) => {
+                self.settings.watch_command = Some(command).filter(|command| !command.trim().is_empty());
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ApiToggled(enabled) => {
+                self.settings.api_enabled = enabled;
+                self.settings_dirty = true;
+                if enabled {
+                    self.start_api();
+                } else {
+                    self.api = None;
+                }
+                Task::none()
+            },
+            Message::DbusToggled(enabled) => {
+                self.settings.dbus_enabled = enabled;
+                self.settings_dirty = true;
+                #[cfg(target_os = "linux")]
+                if enabled {
+                    self.start_dbus();
+                } else {
+                    self.dbus = None;
+                }
+                Task::none()
+            },
+            Message::HistoryFilterChanged(filter) => {
+                self.history_filter = filter;
+                Task::none()
+            },
+            Message::SwatchSize(size) => {
+                self.settings.swatch_size = size;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            // Some platforms report minimizing as a resize to zero.
+            Message::WindowResized(size) if size.width == 0.0 || size.height == 0.0 => {
+                self.hidden = true;
+                Task::none()
+            },
+            // The widget's size is fixed; keep the full window's size for later.
+            Message::WindowResized(_) if self.settings.widget_mode => {
+                self.hidden = false;
+                Task::none()
+            },
+            Message::WindowResized(size) => {
+                self.hidden = false;
+                self.settings.window_width = size.width;
+                self.settings.window_height = size.height;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::WindowMoved(position) => {
+                self.settings.window_x = Some(position.x as i32);
+                self.settings.window_y = Some(position.y as i32);
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::WindowEvent(id, event) => {
+                let main = id == self.main_window;
+                match event {
+                    window::Event::Resized(size) if main => {
+                        return self.update(Message::WindowResized(size));
+                    },
+                    window::Event::Moved(position) if main => {
+                        return self.update(Message::WindowMoved(position));
+                    },
+                    window::Event::FileDropped(path) => {
+                        return self.update(Message::ImagePathChosen(Some(path)));
+                    },
+                    // The daemon outlives its windows, so closing the main one has to end it.
+                    // Windows exit on close requests, so this is the only event that arrives.
+                    window::Event::Closed if main => return self.shut_down(),
+                    window::Event::Closed => {
+                        for slot in [&mut self.preferences_window, &mut self.loupe_window, &mut self.pick_overlay] {
+                            if *slot == Some(id) {
+                                *slot = None;
+                            }
+                        }
+                        if self.pick_overlay.is_none() && self.picker.is_picking() {
+                            match self.overlay_pick.take() {
+                                Some(position) => {
+                                    self.pick_at(position);
+                                },
+                                None => self.transition(Transition::CancelPicking),
+                            }
+                        }
+                    },
+                    _ => {},
+                }
+                Task::none()
+            },
+            Message::ToggleAlwaysOnTop => {
+                self.settings.always_on_top = !self.settings.always_on_top;
+                self.settings_dirty = true;
+                let level = window_level(&self.settings);
+                let windows = std::iter::once(self.main_window).chain(self.preferences_window).chain(self.loupe_window);
+                Task::batch(windows.map(|id| window::set_level(id, level)))
+            },
+            Message::ClearHistory => {
+                self.push_undo(&self.tr("history-cleared"));
+                self.color_history.retain(|entry| entry.pinned);
+                self.selected_history = None;
+                self.update_settings();
+                self.save_settings_if_dirty();
+                Task::none()
+            },
+            Message::SaveSettings => {
+                self.save_settings_if_dirty();
+                Task::none()
+            },
+            Message::Undo => {
+                if let Some(history) = self.undo_stack.pop() {
+                    self.color_history = history;
+                    self.selected_history = None;
+                    self.context_menu = None;
+                    self.update_settings();
+                }
+                self.toast = None;
+                Task::none()
+            },
+            Message::DismissError => {
+                self.error = None;
+                Task::none()
+            },
+            Message::UpdateChecksToggled(enabled) => {
+                self.settings.check_for_updates = enabled;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::CheckForUpdates => {
+                self.update_check = Some(update::UpdateCheck::start(true));
+                Task::none()
+            },
+            Message::OpenReleasePage => {
+                if let Some(release) = self.available_update.take() {
+                    open_url(&release.html_url);
+                }
+                Task::none()
+            },
+            Message::DismissUpdate => {
+                self.available_update = None;
+                Task::none()
+            },
+            Message::DismissToast => {
+                self.toast = None;
+                Task::none()
+            },
+            Message::Tick(now) => {
+                if self
+                    .toast
+                    .as_ref()
+                    .is_some_and(|toast| now.duration_since(toast.shown_at).as_secs() >= TOAST_DURATION_SECS)
+                {
+                    self.toast = None;
+                }
+                self.update_color_picking();
+                self.check_watch(now);
+                self.record_sample(now);
+                self.serve_api();
+                self.serve_url_commands();
+                self.poll_update_check();
+                self.poll_surroundings_capture();
+                if self.settings_dirty {
+                    self.stage_crash_snapshot();
+                    if now.duration_since(self.last_save_time).as_secs() >= 5 {
+                        self.save_settings_if_dirty();
+                        self.last_save_time = now;
+                    }
+                }
+                if self.autosave_pending && now >= self.next_autosave {
+                    crash::autosave();
+                    self.autosave_pending = false;
+                    self.next_autosave = now + crash::AUTOSAVE_INTERVAL;
+                }
+                // SPACE picks while the overlay is open too, which ends picking.
+                let close_overlay = match self.pick_overlay {
+                    Some(id) if !self.picker.is_picking() => {
+                        self.pick_overlay = None;
+                        window::close(id)
+                    },
+                    _ => Task::none(),
+                };
+                Task::batch([close_overlay, self.serve_stdin()])
+            },
+            Message::CopyColor(format) => {
+                if let Some(color_info) = self.get_active_color() {
+                    let text = self.format_info(color_info, format);
+                    self.play_feedback_sound();
+                    self.write_clipboard(text)
+                } else {
+                    Task::none()
+                }
+            },
+            Message::HistoryColorClicked(index) => {
+                if let Some(entry) = self.color_history.get(index) {
+                    let color = entry.color;
+                    self.transition(Transition::Freeze(ColorInfo::solid(color, (0, 0))));
+                    self.selected_history = Some(index);
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::HistoryLabelChanged(label) => {
+                if let Some(entry) = self.selected_history.and_then(|index| self.color_history.get_mut(index)) {
+                    entry.label = if label.is_empty() { None } else { Some(label) };
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::SwatchPressed(slot) => {
+                self.drag = Some(DragState { source: slot, hover: Some(slot) });
+                Task::none()
+            },
+            Message::SwatchHovered(slot) => {
+                if let Some(drag) = &mut self.drag {
+                    drag.hover = Some(slot);
+                }
+                Task::none()
+            },
+            Message::SwatchReleased(target) => {
+                let Some(drag) = self.drag.take() else {
+                    return Task::none();
+                };
+                match (drag.source, target) {
+                    (SwatchSlot::History(index), _) if drag.source == target => {
+                        self.update(Message::HistoryColorClicked(index))
+                    },
+                    (SwatchSlot::Palette(index), _) if drag.source == target => {
+                        self.update(Message::PaletteColorClicked(index))
+                    },
+                    (SwatchSlot::History(from), SwatchSlot::History(to)) => {
+                        if from < self.color_history.len() && to < self.color_history.len() {
+                            let entry = self.color_history.remove(from);
+                            self.color_history.insert(to, entry);
+                            self.selected_history = None;
+                            self.update_settings();
+                        }
+                        Task::none()
+                    },
+                    (SwatchSlot::History(from), SwatchSlot::Palette(to)) => {
+                        if let Some(entry) = self.color_history.get(from).cloned() {
+                            self.palette_library.insert_into_active(to, &entry);
+                            self.palettes_changed();
+                        }
+                        Task::none()
+                    },
+                    (SwatchSlot::Palette(from), SwatchSlot::Palette(to)) => {
+                        self.palette_library.reorder_active(from, to);
+                        self.palettes_changed();
+                        Task::none()
+                    },
+                    (SwatchSlot::Palette(_), SwatchSlot::History(_)) => Task::none(),
+                }
+            },
+            Message::HistoryContextMenu(index) => {
+                self.context_menu = (index < self.color_history.len()).then_some(index);
+                Task::none()
+            },
+            Message::CloseContextMenu => {
+                self.context_menu = None;
+                Task::none()
+            },
+            Message::CopyHistoryColor(index, format) => {
+                self.context_menu = None;
+                match self.color_history.get(index) {
+                    Some(entry) => {
+                        self.play_feedback_sound();
+                        let text = self.format(&entry.color, format);
+                        self.write_clipboard(text)
+                    },
+                    None => Task::none(),
+                }
+            },
+            Message::RenameHistoryEntry(index) => {
+                self.context_menu = None;
+                self.selected_history = (index < self.color_history.len()).then_some(index);
+                Task::none()
+            },
+            Message::DeleteHistoryEntry(index) => {
+                self.context_menu = None;
+                if index < self.color_history.len() {
+                    self.push_undo(&self.tr("color-deleted"));
+                    self.color_history.remove(index);
+                    self.selected_history = None;
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::SetReferenceColor(index) => {
+                self.context_menu = None;
+                if let Some(entry) = self.color_history.get(index) {
+                    self.reference_color = Some(entry.color);
+                }
+                Task::none()
+            },
+            Message::ClearReference => {
+                self.reference_color = None;
+                Task::none()
+            },
+            Message::TogglePinned(index) => {
+                self.context_menu = None;
+                if let Some(entry) = self.color_history.get_mut(index) {
+                    entry.pinned = !entry.pinned;
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::ImportPalette => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .add_filter(self.tr("filter-palettes"), &["gpl", "ase", "txt", "hex"])
+                    .add_filter(self.tr("filter-all-files"), &["*"])
+                    .pick_file(),
+                |handle| Message::PaletteImportPathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::PaletteImportPathChosen(path) => {
+                if let Some(path) = path {
+                    match import::import_palette(&path) {
+                        Ok(colors) => {
+                            let name = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+                            self.import_colors(&name, colors);
+                        },
+                        Err(e) => self.report(e),
+                    }
+                }
+                Task::none()
+            },
+            Message::PaletteSelected(name) => {
+                self.palette_library.select(&name);
+                self.palettes_changed();
+                Task::none()
+            },
+            Message::NewPalette => {
+                self.palette_library.create("Untitled");
+                self.palettes_changed();
+                Task::none()
+            },
+            Message::PaletteNameDraftChanged(name) => {
+                self.palette_name_draft = name;
+                Task::none()
+            },
+            Message::RenamePalette => {
+                let name = self.palette_name_draft.clone();
+                self.palette_library.rename_active(&name);
+                self.palettes_changed();
+                Task::none()
+            },
+            Message::DeletePalette => {
+                self.palette_library.delete_active();
+                self.palettes_changed();
+                Task::none()
+            },
+            Message::AddActiveColorToPalette => {
+                if let Some(color_info) = self.get_active_color() {
+                    let entry = HistoryEntry::new(color_info.color);
+                    self.palette_library.add_to_active(&entry);
+                    self.palettes_changed();
+                }
+                Task::none()
+            },
+            Message::PaletteColorClicked(index) => {
+                if self.selected_palette_color == Some(index) {
+                    self.selected_palette_color = None;
+                } else if let Some(entry) = self.palette_library.active().and_then(|palette| palette.colors.get(index))
+                {
+                    self.transition(Transition::Freeze(ColorInfo::solid(Color::from(entry.clone()), (0, 0))));
+                    self.selected_palette_color = Some(index);
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::MovePaletteColor(target) => {
+                if let Some(index) = self.selected_palette_color {
+                    self.palette_library.move_from_active(index, &target);
+                    self.palettes_changed();
+                }
+                Task::none()
+            },
+            Message::RemovePaletteColor => {
+                if let Some(index) = self.selected_palette_color {
+                    self.palette_library.remove_from_active(index);
+                    self.palettes_changed();
+                }
+                Task::none()
+            },
+            Message::VariableNameChanged(index, name) => {
+                if let Some(entry) = self.color_history.get_mut(index) {
+                    entry.variable_name = if name.is_empty() { None } else { Some(name) };
+                    self.update_settings();
+                }
+                Task::none()
+            },
+            Message::TailwindScalesToggled(enabled) => {
+                self.export_options.tailwind_scales = enabled;
+                Task::none()
+            },
+            Message::ExportFormatSelected(format) => {
+                self.export_format = format;
+                Task::none()
+            },
+            Message::ExportPalette(format) => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(format!("palette.{}", format.extension()))
+                    .add_filter(self.tr(format.message_id()), &[format.extension()])
+                    .save_file(),
+                move |handle| Message::PaletteExportPathChosen(format, handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::CompliancePaletteSelected(name) => {
+                self.settings.compliance_palette = name;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ComplianceTolerance(tolerance) => {
+                self.settings.compliance_tolerance = tolerance;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::LoadDesignTokens => Task::perform(
+                rfd::AsyncFileDialog::new().add_filter(self.tr("filter-design-tokens"), &["json"]).pick_file(),
+                |handle| Message::DesignTokensPathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::DesignTokensPathChosen(path) => {
+                if let Some(path) = path {
+                    match tokens::load(&path) {
+                        Ok(design_tokens) => {
+                            self.design_tokens = design_tokens;
+                            self.settings.design_tokens = Some(path);
+                            self.settings_dirty = true;
+                        },
+                        Err(e) => self.report(e),
+                    }
+                }
+                Task::none()
+            },
+            Message::ClearDesignTokens => {
+                self.design_tokens.clear();
+                self.settings.design_tokens = None;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::ChooseAutoExportPath(format) => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(format!("palette.{}", format.extension()))
+                    .add_filter(self.tr(format.message_id()), &[format.extension()])
+                    .save_file(),
+                move |handle| Message::AutoExportPathChosen(format, handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::AutoExportPathChosen(format, path) => {
+                if let Some(path) = path {
+                    self.settings.auto_export = Some(AutoExport { path, format });
+                    self.settings_dirty = true;
+                    self.write_auto_export();
+                }
+                Task::none()
+            },
+            Message::AutoExportFormatSelected(format) => {
+                if let Some(auto_export) = &mut self.settings.auto_export {
+                    // The old file stays as it was; the new format gets its own.
+                    auto_export.format = format;
+                    auto_export.path.set_extension(format.extension());
+                    self.settings_dirty = true;
+                    self.write_auto_export();
+                } else {
+                    self.export_format = format;
+                }
+                Task::none()
+            },
+            Message::DisableAutoExport => {
+                self.settings.auto_export = None;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::LinkSharedPalette => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("palette.gpl")
+                    .add_filter(self.tr(PaletteFormat::Gpl.message_id()), &["gpl"])
+                    .add_filter(self.tr(PaletteFormat::PlainText.message_id()), &["txt"])
+                    .save_file(),
+                |handle| Message::SharedPalettePathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::SharedPalettePathChosen(path) => {
+                if let Some(path) = path {
+                    let shared = shared_palette::SharedPalette::new(path.clone());
+                    let name = shared.palette_name();
+                    self.shared_palette = Some(shared);
+                    self.settings.shared_palette = Some(path);
+                    self.settings_dirty = true;
+                    self.sync_shared_palette();
+                    self.palette_library.select(&name);
+                    self.palettes_changed();
+                }
+                Task::none()
+            },
+            Message::UnlinkSharedPalette => {
+                self.shared_palette = None;
+                self.settings.shared_palette = None;
+                self.settings_dirty = true;
+                Task::none()
+            },
+            Message::CheckSharedPalette => {
+                if self.sync_shared_palette()
+                    && let Err(e) = self.palette_library.save()
+                {
+                    self.report(e);
+                }
+                Task::none()
+            },
+            Message::CopyPluginFormat(index, color) => {
+                self.context_menu = None;
+                match self.plugins.format(index, color).map(str::to_string) {
+                    Some(formatted) => {
+                        self.play_feedback_sound();
+                        self.write_clipboard(formatted)
+                    },
+                    None => Task::none(),
+                }
+            },
+            Message::ExportWithPlugin(index) => {
+                let extension = self.plugins.export_extension(index).unwrap_or("txt").to_string();
+                Task::perform(
+                    rfd::AsyncFileDialog::new()
+                        .set_file_name(format!("palette.{}", extension))
+                        .add_filter(extension.clone(), &[extension])
+                        .save_file(),
+                    move |handle| Message::PluginExportPathChosen(index, handle.map(|h| h.path().to_path_buf())),
+                )
+            },
+            Message::PluginExportPathChosen(index, path) => {
+                if let Some(path) = path {
+                    let result = self
+                        .plugins
+                        .export(index, &self.palette_swatches())
+                        .map_err(|e| PeekerError::Export(e.to_string()))
+                        .and_then(|contents| write_file(&path, contents));
+                    if let Err(e) = result {
+                        self.report(e);
+                    }
+                }
+                Task::none()
+            },
+            Message::CopyCoolorsLink => self.write_clipboard(export::coolors_url(&self.palette_swatches())),
+            Message::PasteCoolorsLink => iced::clipboard::read().map(Message::CoolorsLinkPasted),
+            Message::CoolorsLinkPasted(contents) => {
+                match contents.as_deref().and_then(import::parse_coolors_url) {
+                    Some(colors) => self.import_colors("Coolors", colors),
+                    None => self.report(PeekerError::Clipboard(self.tr("no-coolors-link"))),
+                }
+                Task::none()
+            },
+            Message::ExportHistory => Task::perform(
+                rfd::AsyncFileDialog::new()
+                    .set_file_name("pixel-peeker-history.csv")
+                    .add_filter(self.tr("filter-csv"), &["csv"])
+                    .add_filter(self.tr("filter-json"), &["json"])
+                    .save_file(),
+                |handle| Message::HistoryExportPathChosen(handle.map(|h| h.path().to_path_buf())),
+            ),
+            Message::HistoryExportPathChosen(path) => {
+                if let Some(path) = path {
+                    let result = export::export_history(HistoryFormat::from_path(&path), &self.color_history)
+                        .and_then(|contents| write_file(&path, contents));
+                    if let Err(e) = result {
+                        self.report(e);
+                    }
+                }
+                Task::none()
+            },
+            Message::CopyPaletteImage => {
+                let sheet = export::render_swatch_sheet(&self.palette_swatches());
+                if let Err(e) = self.copy_image_to_clipboard(&sheet) {
+                    self.report(e);
+                }
+                Task::none()
+            },
+            Message::CopyColorImage(color) => {
+                self.context_menu = None;
+                if let Err(e) = self.copy_image_to_clipboard(&export::render_solid_swatch(color)) {
+                    self.report(e);
+                }
+                Task::none()
+            },
+            Message::PaletteExportPathChosen(format, path) => {
+                if let Some(path) = path {
+                    let result = export::export_palette(format, &self.palette_swatches(), &self.export_options)
+                        .and_then(|contents| write_file(&path, contents));
+                    if let Err(e) = result {
+                        self.report(e);
+                    }
+                }
+                Task::none()
+            },
+        }
+    }
+
+    fn title(&self, window: window::Id) -> String {
+        if Some(window) == self.preferences_window {
+            self.tr("preferences-title")
+        } else if Some(window) == self.loupe_window {
+            self.tr("loupe-title")
+        } else {
+            "Pixel Peeker".to_string()
+        }
+    }
+
+    fn view(&self, window: window::Id) -> Element<'_, Message> {
+        if Some(window) == self.preferences_window {
+            return self.preferences_view();
+        }
+        if Some(window) == self.loupe_window {
+            return self.loupe_view();
+        }
+        if Some(window) == self.pick_overlay {
+            return self.pick_overlay_view();
+        }
+        if self.settings.widget_mode {
+            return self.widget_view();
+        }
+
+        let mut content = Column::new().spacing(10).push(
+            Row::new()
+                .spacing(20)
+                .push(self.create_title())
+                .push(self.create_workspace_selector())
+                .push(self.create_theme_selector())
+                .push(
+                    button(text(self.tr("widget-mode")).size(self.text_size(12.0)))
+                        .on_press(Message::WidgetModeToggled(true)),
+                )
+                .push(
+                    toggler(self.settings.always_on_top)
+                        .label(self.tr("always-on-top"))
+                        .on_toggle(|_| Message::ToggleAlwaysOnTop)
+                        .text_size(self.text_size(12.0)),
+                ),
+        );
+        if let Some(query) = &self.command_query {
+            content = content.push(self.create_command_palette(query));
+        }
+        if let Some(error) = &self.error {
+            content = content.push(self.create_error_banner(error));
+        }
+        if let Some(release) = &self.available_update {
+            content = content.push(self.create_update_banner(release));
+        }
+
+        let (display_x, display_y) = self.get_display_position();
+        content = content
+            .push(text(self.i18n.tr_with("mouse-position", &[("x", display_x.into()), ("y", display_y.into())])));
+
+        if let Some(color_info) = self.get_active_color() {
+            let preview_row = self.create_preview_row(color_info);
+            content = content.push(preview_row);
+            if let Some(reference) = self.reference_color {
+                content = content.push(self.create_compare_panel(color_info.color, reference));
+            }
+        } else {
+            content = content.push(text(self.tr("no-preview")));
+        }
+
+        content = content.push(self.create_status_text());
+        if let Some(notice) = self.create_display_notice() {
+            content = content.push(notice);
+        }
+        content = content.push(self.create_image_source_row());
+        content = content.push(self.create_pick_options());
+        if let Some(measurement) = &self.measure {
+            content = content.push(self.create_measure_section(measurement));
+        }
+        if self.slots.iter().any(Option::is_some) {
+            content = content.push(self.create_slots_section());
+        }
+        if self.snapshots.iter().any(Option::is_some) {
+            content = content.push(self.create_diff_section());
+        }
+        if let Some(watch) = &self.watch {
+            content = content.push(self.create_watch_section(watch));
+        }
+        if let Some(recorder) = &self.recorder {
+            content = content.push(self.create_record_section(recorder));
+        }
+
+        if !self.color_history.is_empty() {
+            content = content.push(self.create_history_section());
+        } else {
+            content = content.push(self.create_import_row());
+        }
+
+        content = content.push(self.create_palette_section());
+
+        if let Some(color) = self.clipboard_color {
+            content = content.push(self.create_clipboard_offer(color));
+        }
+
+        if let Some(toast) = &self.toast {
+            content = content.push(self.create_toast(toast));
+        }
+
+        let custom = self.settings.custom_colors.clone();
+        let frozen = self.is_frozen();
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(20)
+            .style(move |theme: &Theme| {
+                let palette = theme.extended_palette();
+                let background = if frozen {
+                    custom.color(CustomColor::FrozenBackground).unwrap_or(if palette.is_dark {
+                        Color::from_rgb(0.05, 0.05, 0.05)
+                    } else {
+                        palette.background.weak.color
+                    })
+                } else {
+                    custom.color(CustomColor::Background).unwrap_or(if palette.is_dark {
+                        Color::from_rgb(0.1, 0.1, 0.2)
+                    } else {
+                        palette.background.base.color
+                    })
+                };
+                container::Style { background: Some(Background::Color(background)), ..Default::default() }
+            })
+            .into()
+    }
+
+    /// Widget mode: the live color and its hex value on a translucent card.
+    /// Dragging moves the window; right-click opens the menu.
+    fn widget_view(&self) -> Element<'_, Message> {
+        let mut row = Row::new().spacing(10).align_y(iced::Alignment::Center);
+        if let Some(info) = self.get_active_color() {
+            row = row
+                .push(self.create_color_swatch(info.color))
+                .push(text(self.format_info(info, ColorFormat::Hex)).size(self.text_size(16.0)));
+        }
+        if self.is_frozen() {
+            row = row.push(text("❄").size(self.text_size(14.0)));
+        }
+        if self.widget_menu {
+            row = row
+                .push(
+                    button(text(self.tr("copy")).size(self.text_size(12.0)))
+                        .on_press(Message::CopyColor(ColorFormat::Hex)),
+                )
+                .push(
+                    button(text(self.tr("exit-widget")).size(self.text_size(12.0)))
+                        .on_press(Message::WidgetModeToggled(false)),
+                );
+        }
+
+        let card = container(row).padding(10).width(Length::Fill).height(Length::Fill).style(|theme: &Theme| {
+            let palette = theme.extended_palette();
+            container::Style {
+                background: Some(Background::Color(Color { a: 0.8, ..palette.background.base.color })),
+                border: Border { color: palette.background.strong.color, width: 1.0, radius: 8.0.into() },
+                ..Default::default()
+            }
+        });
+
+        mouse_area(card).on_press(Message::DragWindow).on_right_press(Message::WidgetMenuToggled).into()
+    }
+
+    /// Preferences: appearance, language and update checks.
+    fn preferences_view(&self) -> Element<'_, Message> {
+        let content = Column::new()
+            .spacing(10)
+            .push(Row::new().spacing(20).push(self.create_title()).push(self.create_theme_selector()))
+            .push(self.create_appearance_section())
+            .push(self.create_format_options_section())
+            .push(self.create_named_colors_section())
+            .push(self.create_auto_export_section())
+            .push(self.create_color_management_section());
+        window_background(scrollable(content))
+    }
+
+    /// The zoomed preview on its own, sized with its window.
+    fn loupe_view(&self) -> Element<'_, Message> {
+        let mut content = Column::new().spacing(10);
+        match self.get_active_color() {
+            Some(info) => {
+                if let Some(preview) = &info.preview {
+                    content = content.push(
+                        Canvas::new(PreviewRenderer {
+                            rgb_data: preview.rgb_data.clone(),
+                            width: preview.width,
+                            height: preview.height,
+                            zoom_factor: self.zoom_factor,
+                            anchor: self.settings.zoom_anchor,
+                            crosshair: preview.crosshair(),
+                            circular: self.settings.preview_style == PreviewStyle::Circle,
+                            smooth: self.settings.smooth_preview.then(|| preview.image()),
+                        })
+                        .width(Length::Fill)
+                        .height(Length::Fill),
+                    );
+                }
+                let (x, y) = info.position;
+                content = content.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_y(iced::Alignment::Center)
+                        .push(self.create_color_swatch(info.color))
+                        .push(text(self.format_info(info, self.settings.preferred_format)).size(self.text_size(16.0)))
+                        .push(text(format!("({}, {})", x, y)).size(self.text_size(12.0))),
+                );
+            },
+            None => content = content.push(text(self.tr("no-preview"))),
+        }
+        window_background(content)
+    }
+
+    /// Covers the screen with a faint tint; clicking anywhere picks the pixel
+    /// under the cursor, right-click or ESC cancels.
+    fn pick_overlay_view(&self) -> Element<'_, Message> {
+        let hint = container(text(self.tr("pick-overlay-hint")).size(self.text_size(14.0)))
+            .padding(10)
+            .style(container::rounded_box);
+        let tint = container(hint).width(Length::Fill).height(Length::Fill).center_x(Length::Fill).padding(30).style(
+            |_theme: &Theme| container::Style {
+                background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.05))),
+                ..Default::default()
+            },
+        );
+        mouse_area(tint)
+            .interaction(mouse::Interaction::Crosshair)
+            .on_press(Message::OverlayPicked)
+            .on_right_press(Message::PickOverlayToggled(false))
+            .into()
+    }
+
+    /// Every view paints its own background, so windows are cleared to
+    /// transparent: widget mode and the pick overlay show what is beneath.
+    fn style(&self, theme: &Theme) -> iced::theme::Style {
+        iced::theme::Style { background_color: Color::TRANSPARENT, text_color: theme.palette().text }
+    }
+
+    /// Scales a text size laid out for the default font size to the
+    /// configured one, so smaller labels follow the font size setting.
+    fn text_size(&self, size: f32) -> f32 {
+        size * self.settings.font_size / DEFAULT_FONT_SIZE
+    }
+
+    fn scale_factor(&self, _window: window::Id) -> f32 {
+        self.settings.ui_scale
+    }
+
+    fn theme(&self, _window: window::Id) -> Theme {
+        let dark = match self.settings.theme {
+            ThemePreference::Dark => true,
+            ThemePreference::Light => false,
+            ThemePreference::System => self.system_dark,
+        };
+        let base = if dark { Theme::Dark } else { Theme::Light };
+
+        let custom = &self.settings.custom_colors;
+        let mut palette = base.palette();
+        let overrides = [
+            (CustomColor::Accent, &mut palette.primary),
+            (CustomColor::Background, &mut palette.background),
+            (CustomColor::Text, &mut palette.text),
+        ];
+        let mut customized = false;
+        for (which, slot) in overrides {
+            if let Some(color) = custom.color(which) {
+                *slot = color;
+                customized = true;
+            }
+        }
+        if customized { Theme::custom(format!("{} (custom)", base), palette) } else { base }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let system_theme = if self.settings.theme == ThemePreference::System {
+            iced::time::every(std::time::Duration::from_secs(2))
+                .map(|_| Message::SystemThemeChecked(system_prefers_dark()))
+        } else {
+            Subscription::none()
+        };
+        let shared_palette = if self.shared_palette.is_some() {
+            iced::time::every(std::time::Duration::from_secs(2)).map(|_| Message::CheckSharedPalette)
+        } else {
+            Subscription::none()
+        };
+        let clipboard = if self.settings.watch_clipboard {
+            iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::CheckClipboard)
+        } else {
+            Subscription::none()
+        };
+
+        // While paused or hidden only the resume hotkey (and any watch or
+        // recording) needs polling, so tick slowly unless one of those is running.
+        let tick =
+            if (self.picker.is_paused() || self.nothing_visible()) && self.watch.is_none() && self.recorder.is_none() {
+                std::time::Duration::from_millis(250)
+            } else {
+                std::time::Duration::from_millis(self.capture_tick_ms().into())
+            };
+
+        Subscription::batch([
+            iced::time::every(tick).map(Message::Tick),
+            window::events().map(|(id, event)| Message::WindowEvent(id, event)),
+            iced::time::every(std::time::Duration::from_secs(1)).map(|_| Message::CheckWindowVisibility),
+            iced::time::every(std::time::Duration::from_secs(5)).map(|_| Message::CheckDisplays),
+            iced::event::listen_with(|event, status, _| match (event, status) {
+                (
+                    iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+                        key: iced::keyboard::Key::Named(iced::keyboard::key::Named::Escape),
+                        ..
+                    }),
+                    _,
+                ) => Some(Message::PickOverlayToggled(false)),
+                (
+                    iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }),
+                    iced::event::Status::Ignored,
+                ) => shortcuts::from_key(&key, modifiers).map(Message::Shortcut),
+                _ => None,
+            }),
+            clipboard,
+            system_theme,
+            shared_palette,
+        ])
+    }
+
+    fn handle_shortcut(&mut self, shortcut: shortcuts::Shortcut) -> Task<Message> {
+        use shortcuts::Shortcut;
+
+        match shortcut {
+            Shortcut::FocusNext | Shortcut::FocusPrevious => {
+                let order = self.focus_order();
+                if order.is_empty() {
+                    self.keyboard_focus = None;
+                    return Task::none();
+                }
+                let current = self.keyboard_focus.and_then(|focus| order.iter().position(|&item| item == focus));
+                let next = match (current, shortcut) {
+                    (None, Shortcut::FocusNext) => 0,
+                    (None, _) => order.len() - 1,
+                    (Some(index), Shortcut::FocusNext) => (index + 1) % order.len(),
+                    (Some(index), _) => (index + order.len() - 1) % order.len(),
+                };
+                self.keyboard_focus = Some(order[next]);
+                Task::none()
+            },
+            Shortcut::Activate => match self.keyboard_focus {
+                Some(KeyboardFocus::PreferredButton(format)) => self.update(Message::PreferredFormatSelected(format)),
+                Some(KeyboardFocus::CopyButton(format)) => self.update(Message::CopyColor(format)),
+                Some(KeyboardFocus::HistorySwatch(index)) => self.update(Message::HistoryColorClicked(index)),
+                Some(KeyboardFocus::PaletteSwatch(index)) => self.update(Message::PaletteColorClicked(index)),
+                None => Task::none(),
+            },
+            Shortcut::CopyPreferred => self.update(Message::CopyColor(self.settings.preferred_format)),
+            Shortcut::CopyFormat(index) => match self.visible_formats().get(index) {
+                Some(&format) => self.update(Message::CopyColor(format)),
+                None => Task::none(),
+            },
+            Shortcut::CommandPalette => self.update(Message::CommandPaletteToggled(self.command_query.is_none())),
+            Shortcut::MoveSample(dx, dy) => {
+                let cell = self.picker.frozen().and_then(|frozen| frozen.preview.as_ref()).and_then(|preview| {
+                    let (x, y) = preview.crosshair();
+                    Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+                });
+                if let Some(cell) = cell {
+                    self.resample_frozen(cell);
+                }
+                Task::none()
+            },
+            Shortcut::JumpToHistory => {
+                if let Some(&index) = self.filtered_history_indices().first() {
+                    self.keyboard_focus = Some(KeyboardFocus::HistorySwatch(index));
+                }
+                Task::none()
+            },
+        }
+    }
+
+    /// `color` as shown and copied, following the format options.
+    fn format(&self, color: &Color, format: ColorFormat) -> String {
+        format_color_with(color, &format, &self.settings.format_options, 8)
+    }
+
+    /// [`Self::format`] at the bit depth `info` was sampled at.
+    fn format_info(&self, info: &ColorInfo, format: ColorFormat) -> String {
+        format_color_with(&info.color, &format, &self.settings.format_options, info.bit_depth)
+    }
+
+    fn tr(&self, id: &str) -> String {
+        self.i18n.tr(id)
+    }
+
+    /// A format's name as shown in the UI; only the descriptive ones are
+    /// translated, code-style names like `vec3` stay as they are.
+    fn format_name(&self, format: ColorFormat) -> String {
+        self.tr(format.message_id())
+    }
+
+    /// `all` as pick list options, each shown under `label(value)`.
+    fn choices<T: Copy>(&self, all: &[T], label: impl Fn(T) -> String) -> Vec<Choice<T>> {
+        all.iter().map(|&value| Choice::new(value, label(value))).collect()
+    }
+
+    /// Every action the command palette offers, labelled for fuzzy search.
+    fn palette_commands(&self) -> Vec<(String, Message)> {
+        let toggle = |enabled: bool, name: &str| {
+            self.i18n
+                .tr_with(if enabled { "command-disable" } else { "command-enable" }, &[("name", self.tr(name).into())])
+        };
+
+        let mut commands: Vec<(String, Message)> = ColorFormat::ALL
+            .iter()
+            .map(|&format| {
+                (
+                    self.i18n.tr_with("copy-as", &[("format", self.format_name(format).into())]),
+                    Message::CopyColor(format),
+                )
+            })
+            .collect();
+        commands.extend(PaletteFormat::ALL.iter().map(|&format| {
+            (
+                self.i18n.tr_with("export-palette-as", &[("format", self.tr(format.message_id()).into())]),
+                Message::ExportPalette(format),
+            )
+        }));
+        if let Some(color_info) = self.get_active_color() {
+            commands.push((self.tr("copy-as-image"), Message::CopyColorImage(color_info.color)));
+        }
+        commands.extend([
+            (self.tr("copy-gradient"), Message::CopyGradient),
+            (self.tr(if self.picker.is_paused() { "resume-picking" } else { "pause-picking" }), Message::TogglePause),
+            (toggle(self.settings.always_on_top, "always-on-top"), Message::ToggleAlwaysOnTop),
+            (toggle(self.settings.widget_mode, "widget-mode"), Message::WidgetModeToggled(!self.settings.widget_mode)),
+            (toggle(self.settings.low_power, "battery-saver"), Message::LowPowerToggled(!self.settings.low_power)),
+            (
+                toggle(self.settings.watch_clipboard, "clipboard-colors"),
+                Message::WatchClipboardToggled(!self.settings.watch_clipboard),
+            ),
+            (toggle(self.measure.is_some(), "measure"), Message::MeasureToggled(self.measure.is_none())),
+            (toggle(self.ruler.is_some(), "ruler-overlay"), Message::RulerToggled(self.ruler.is_none())),
+            (toggle(self.watch.is_some(), "watch"), Message::WatchToggled(self.watch.is_none())),
+            (toggle(self.recorder.is_some(), "record"), Message::RecordToggled(self.recorder.is_none())),
+            (
+                toggle(self.settings.compare_raw_values, "compare-raw"),
+                Message::CompareRawValuesToggled(!self.settings.compare_raw_values),
+            ),
+            (
+                toggle(self.settings.show_flutter, "show-flutter"),
+                Message::FlutterNamesToggled(!self.settings.show_flutter),
+            ),
+            (toggle(self.settings.show_x11, "show-x11"), Message::X11NamesToggled(!self.settings.show_x11)),
+            (toggle(self.settings.show_ansi, "show-ansi"), Message::AnsiNamesToggled(!self.settings.show_ansi)),
+            (
+                toggle(self.settings.show_web_safe, "show-web-safe"),
+                Message::WebSafeToggled(!self.settings.show_web_safe),
+            ),
+            (toggle(self.settings.show_ral, "show-ral"), Message::RalToggled(!self.settings.show_ral)),
+            (
+                toggle(self.settings.smooth_preview, "smooth-preview"),
+                Message::SmoothPreviewToggled(!self.settings.smooth_preview),
+            ),
+            (
+                self.tr(if self.preferences_window.is_some() { "hide-preferences" } else { "show-preferences" }),
+                Message::PreferencesToggled(self.preferences_window.is_none()),
+            ),
+            (toggle(self.loupe_window.is_some(), "loupe"), Message::LoupeToggled(self.loupe_window.is_none())),
+            (self.tr("pick-anywhere"), Message::PickOverlayToggled(true)),
+            (self.tr("open-image"), Message::OpenImage),
+            (self.tr("paste-image"), Message::PasteImage),
+            (self.tr("import-palette"), Message::ImportPalette),
+            (self.tr("export-history"), Message::ExportHistory),
+            (self.tr("load-design-tokens"), Message::LoadDesignTokens),
+            (self.tr("new-palette"), Message::NewPalette),
+            (self.tr("add-to-palette"), Message::AddActiveColorToPalette),
+            (self.tr("clear-history"), Message::ClearHistory),
+            (self.tr("save-settings"), Message::SaveSettings),
+            (self.tr("check-for-updates"), Message::CheckForUpdates),
+            (self.tr("undo"), Message::Undo),
+        ]);
+        commands.extend(ThemePreference::ALL.iter().map(|&theme| {
+            (
+                self.i18n.tr_with("theme-command", &[("theme", self.tr(theme.message_id()).into())]),
+                Message::ThemeSelected(theme),
+            )
+        }));
+        commands
+    }
+
+    /// The formats the user chose to show, in [`ColorFormat::ALL`] order.
+    fn visible_formats(&self) -> Vec<ColorFormat> {
+        ColorFormat::ALL.into_iter().filter(|format| self.settings.visible_formats.contains(format)).collect()
+    }
+
+    /// Everything Tab can reach, in on-screen order: each visible format's
+    /// star and copy button (shown only while there is a color), the visible
+    /// history swatches, then the active palette's swatches.
+    fn focus_order(&self) -> Vec<KeyboardFocus> {
+        let formats = if self.get_active_color().is_some() { self.visible_formats() } else { Vec::new() };
+        let palette = self.palette_library.active().map_or(0, |palette| palette.colors.len());
+        formats
+            .into_iter()
+            .flat_map(|format| [KeyboardFocus::PreferredButton(format), KeyboardFocus::CopyButton(format)])
+            .chain(self.filtered_history_indices().into_iter().map(KeyboardFocus::HistorySwatch))
+            .chain((0..palette).map(KeyboardFocus::PaletteSwatch))
+            .collect()
+    }
+
+    /// Whether no window shows the live color: the main window is minimized
+    /// and neither the loupe nor the pick overlay is open.
+    fn nothing_visible(&self) -> bool {
+        self.hidden && self.loupe_window.is_none() && self.pick_overlay.is_none()
+    }
+
+    fn update_color_picking(&mut self) {
+        if self.nothing_visible() {
+            return;
+        }
+
+        // Releasing the mouse outside any swatch never reaches a `mouse_area`,
+        // so drop abandoned drags here.
+        if self.drag.is_some()
+            && !self.input_state.device_state.get_mouse().button_pressed.get(1).copied().unwrap_or(false)
+        {
+            self.drag = None;
+        }
+
+        let input_event = self.process_input();
+        if matches!(input_event, InputEvent::TogglePause) {
+            self.transition(Transition::TogglePause);
+            return;
+        }
+        if self.picker.is_paused() {
+            return;
+        }
+
+        let mouse_pos = match &self.image_source {
+            Some(source) => source.hover,
+            None => self.get_mouse_position(),
+        };
+
+        match input_event {
+            InputEvent::Freeze(_) if self.measure.is_some() => {
+                self.capture_at_position(mouse_pos);
+                if let (Some(measurement), Some(current)) = (&mut self.measure, &self.current_color) {
+                    measurement.mark(current.clone());
+                }
+                self.line_stops.clear();
+                self.stop_ruler();
+                return;
+            },
+            InputEvent::Freeze(slot) => {
+                self.handle_freeze(mouse_pos, slot);
+                return;
+            },
+            InputEvent::Unfreeze => {
+                if let Some(measurement) = &mut self.measure {
+                    *measurement = measure::Measurement::default();
+                    self.line_stops.clear();
+                    self.stop_ruler();
+                }
+                if self.is_frozen() {
+                    self.transition(Transition::Unfreeze);
+                    self.update_settings();
+                }
+                return;
+            },
+            InputEvent::TogglePause | InputEvent::None => {},
+        }
+
+        if !self.picker.is_capturing() {
+            return;
+        }
+
+        // In battery-saver mode a still cursor keeps the last capture.
+        if self.settings.low_power && self.last_capture_position == Some(mouse_pos) {
+            return;
+        }
+        if !self.capture_backoff.ready(Instant::now()) {
+            return;
+        }
+        if self.capture_at_position(mouse_pos) {
+            self.last_capture_position = Some(mouse_pos);
+        }
+    }
+
+    fn get_active_color(&self) -> Option<&ColorInfo> {
+        self.picker.frozen().or(self.current_color.as_ref())
+    }
+
+    fn get_display_position(&self) -> (i32, i32) {
+        self.get_active_color().map(|info| info.position).unwrap_or_else(|| self.get_mouse_position())
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.picker.frozen().is_some()
+    }
+
+    /// Moves the frozen sample to another cell of the frozen preview, reading
+    /// the stored pixels instead of capturing the screen again.
+    fn resample_frozen(&mut self, (x, y): (u32, u32)) {
+        let Some(frozen) = self.picker.frozen() else {
+            return;
+        };
+        let Some(preview) = &frozen.preview else {
+            return;
+        };
+        let Some(color) = preview.color_at(x, y) else {
+            return;
+        };
+        let (from_x, from_y) = preview.crosshair();
+        let position = (frozen.position.0 + x as i32 - from_x as i32, frozen.position.1 + y as i32 - from_y as i32);
+        let info = ColorInfo {
+            preview: Some(PreviewData { crosshair: Some((x, y)), ..preview.clone() }),
+            monitor: frozen.monitor.clone(),
+            bit_depth: frozen.bit_depth,
+            ..ColorInfo::solid(color, position)
+        };
+        self.transition(Transition::Freeze(info));
+    }
+
+    fn transition(&mut self, transition: Transition) {
+        self.picker = std::mem::take(&mut self.picker).apply(transition);
+    }
+
+    fn get_mouse_position(&self) -> (i32, i32) {
+        let mouse = self.input_state.device_state.get_mouse();
+        (mouse.coords.0, mouse.coords.1)
+    }
+
+    fn process_input(&mut self) -> InputEvent {
+        let keys = self.input_state.device_state.get_keys();
+        let space_pressed = keys.contains(&Keycode::Space);
+        let esc_pressed = keys.contains(&Keycode::Escape);
+
+        let just_pressed = space_pressed && !self.input_state.space_pressed_last_frame;
+        self.input_state.space_pressed_last_frame = space_pressed;
+
+        let control = keys.contains(&Keycode::LControl) || keys.contains(&Keycode::RControl);
+        let shift = keys.contains(&Keycode::LShift) || keys.contains(&Keycode::RShift);
+        let pause_pressed = control && shift && keys.contains(&Keycode::P);
+        let pause_just_pressed = pause_pressed && !self.input_state.pause_pressed_last_frame;
+        self.input_state.pause_pressed_last_frame = pause_pressed;
+
+        let slot_pressed = (shift && !control).then(|| SLOT_KEYS.iter().position(|key| keys.contains(key))).flatten();
+        let slot_just_pressed = slot_pressed.filter(|_| self.input_state.slot_pressed_last_frame.is_none());
+        self.input_state.slot_pressed_last_frame = slot_pressed;
+
+        if pause_just_pressed {
+            InputEvent::TogglePause
+        } else if self.picker.is_paused() {
+            InputEvent::None
+        } else if just_pressed {
+            InputEvent::Freeze(None)
+        } else if let Some(slot) = slot_just_pressed {
+            InputEvent::Freeze(Some(slot))
+        } else if esc_pressed {
+            InputEvent::Unfreeze
+        } else {
+            InputEvent::None
+        }
+    }
+
+    /// Picks the pixel at `position`. While capturing, the last tick already
+    /// captured it; otherwise the held preview is stale and it is captured again.
+    fn handle_freeze(&mut self, position: (i32, i32), slot: Option<usize>) {
+        if !self.picker.is_capturing() && !self.capture_at_position(position) {
+            return;
+        }
+        self.freeze_current(slot);
+    }
+
+    /// Records the latest capture as a pick and freezes on it, keeping it in
+    /// `slot` when one was chosen.
+    fn freeze_current(&mut self, slot: Option<usize>) {
+        if let Some(current) = self.current_color.clone() {
+            // Positions in an opened image say nothing about the windows on screen.
+            let window_title = if self.image_source.is_some() {
+                None
+            } else {
+                window_title_at(current.position.0, current.position.1)
+            };
+            let entry = HistoryEntry::from_pick(&current, window_title);
+            if let Some(path) = &self.settings.pick_log
+                && let Err(e) = export::append_pick_log(path, &entry)
+            {
+                self.report(e);
+            }
+            if let Some(command) = &self.settings.pick_command {
+                run_pick_command(command, &current);
+            }
+            self.plugins.on_pick(current.color, current.position);
+            let picked = api::ColorJson::new(&current, true);
+            if let Some(api) = &self.api {
+                api.broadcast_pick(&picked);
+            }
+            #[cfg(target_os = "linux")]
+            if let Some(dbus) = &self.dbus {
+                dbus.emit_color_picked(&picked);
+            }
+            self.add_to_history(entry);
+            self.share_pick(current.color);
+            if self.settings.notify_on_pick {
+                show_notification(
+                    self.i18n.tr_with("picked", &[("color", format_color(&current.color, &ColorFormat::Hex).into())]),
+                );
+            }
+            self.play_feedback_sound();
+            self.capture_surroundings(current.position);
+            if let Some(slot) = slot {
+                self.keep_in_slot(slot, current.color);
+            }
+            self.transition(Transition::Freeze(current));
+            self.update_settings();
+            self.save_settings_if_dirty();
+        }
+    }
+
+    /// Puts `color` in `slot`, in the format the slot was already shown in.
+    fn keep_in_slot(&mut self, slot: usize, color: Color) {
+        let format = self.slots[slot].map_or(self.settings.preferred_format, |slot| slot.format);
+        self.slots[slot] = Some(ColorSlot { color, format });
+        self.update_settings();
+    }
+
+    /// Keeps what lies around the frozen `position` for measuring regions:
+    /// cut from the opened image when there is one, else captured from the
+    /// screen in the background and picked up by
+    /// [`Self::poll_surroundings_capture`].
+    fn capture_surroundings(&mut self, position: (i32, i32)) {
+        self.surroundings = None;
+        self.surroundings_capture = None;
+        if let Some(source) = &self.image_source {
+            self.surroundings = source.around(position, REGION_SEARCH_RADIUS).map(|(image, start)| Surroundings {
+                position,
+                image,
+                start,
+            });
+            return;
+        }
+        let (sender, result) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(capture_around(position, REGION_SEARCH_RADIUS));
+        });
+        self.surroundings_capture = Some(SurroundingsCapture { result });
+    }
+
+    /// Color-manages and keeps the background capture once it has finished.
+    fn poll_surroundings_capture(&mut self) {
+        let Some(capture) = &self.surroundings_capture else {
+            return;
+        };
+        let Ok(result) = capture.result.try_recv() else {
+            return;
+        };
+        self.surroundings_capture = None;
+        match result {
+            Ok((mut surroundings, monitor)) => {
+                if let Some(monitor) = monitor {
+                    color_manage_on(&mut surroundings.image, &monitor, &self.settings, &mut self.color_profiles);
+                    self.report_profile_error();
+                }
+                self.surroundings = Some(surroundings);
+            },
+            Err(e) => eprintln!("Failed to capture around the frozen pixel: {}", e),
+        }
+    }
+
+    /// [`sample_line`] from the opened image when there is one.
+    fn sample_line(&mut self, a: (i32, i32), b: (i32, i32), count: usize) -> Result<Vec<Color>, PeekerError> {
+        let Some(source) = &self.image_source else {
+            let colors = sample_line(a, b, count, &self.settings, &mut self.color_profiles);
+            self.report_profile_error();
+            return colors;
+        };
+        Ok(measure::points_along(a, b, count)
+            .into_iter()
+            .filter_map(|(x, y)| source.color_at(u32::try_from(x).ok()?, u32::try_from(y).ok()?))
+            .collect())
+    }
+
+    /// Runs plugin formats for the colors the view shows them for, the
+    /// active color and the history entry with its menu open, so drawing
+    /// only reads cached results. Shows why a plugin was turned off.
+    fn prepare_plugin_formats(&mut self) {
+        let colors = [
+            self.get_active_color().map(|info| info.color),
+            self.context_menu.and_then(|index| self.color_history.get(index)).map(|entry| entry.color),
+        ];
+        for color in colors.into_iter().flatten() {
+            self.plugins.prepare(color);
+        }
+        if let Some(e) = self.plugins.take_error() {
+            self.report(e);
+        }
+    }
+
+    /// Shows why a monitor's color profile was ignored, once per load.
+    fn report_profile_error(&mut self) {
+        if let Some(e) = self.color_profiles.take_error() {
+            self.report(e);
+        }
+    }
+
+    fn play_feedback_sound(&self) {
+        if self.settings.feedback_sound {
+            play_click();
+        }
+    }
+
+    fn write_clipboard(&mut self, text: String) -> Task<Message> {
+        self.clipboard_written = Some(text.clone());
+        iced::clipboard::write(text)
+    }
+
+    fn copy_image_to_clipboard(&mut self, image: &image::RgbaImage) -> Result<(), PeekerError> {
+        let clipboard = match &mut self.image_clipboard {
+            Some(clipboard) => clipboard,
+            None => self.image_clipboard.insert(arboard::Clipboard::new()?),
+        };
+        clipboard.set_image(arboard::ImageData {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            bytes: std::borrow::Cow::Borrowed(image.as_raw()),
+        })?;
+        Ok(())
+    }
+
+    /// Adds imported colors to the active palette, or to a new palette called
+    /// `name` when none is selected, so none are evicted like history would.
+    fn import_colors(&mut self, name: &str, colors: Vec<import::ImportedColor>) {
+        if self.palette_library.active().is_none() {
+            self.palette_library.create(name);
+        }
+        let count = colors.len();
+        for imported in colors {
+            self.palette_library.add_to_active(&HistoryEntry::from_import(imported));
+        }
+        self.palettes_changed();
+        let palette = self.palette_library.active().map(|palette| palette.name.clone()).unwrap_or_default();
+        let message = self.i18n.tr_with("imported-colors", &[("count", count.into()), ("palette", palette.into())]);
+        self.toast = Some(Toast { message, shown_at: Instant::now() });
+    }
+
+    /// The colors exports and copies work on: the active named palette when
+    /// one is selected, otherwise the history, with repeated variable names
+    /// made unique.
+    fn palette_swatches(&self) -> Vec<Swatch> {
+        export::unique_variables(match self.palette_library.active() {
+            Some(palette) => palette
+                .colors
+                .iter()
+                .enumerate()
+                .map(|(index, color)| HistoryEntry::from(color.clone()).swatch(index))
+                .collect(),
+            None => self.color_history.iter().enumerate().map(|(index, entry)| entry.swatch(index)).collect(),
+        })
+    }
+
+    fn add_to_history(&mut self, mut entry: HistoryEntry) {
+        entry.metadata.session.get_or_insert(self.session_started);
+        if self.settings.auto_label_history && entry.label.is_none() {
+            entry.label = Some(named_colors::describe(entry.color, &self.i18n));
+        }
+        if history::add(&mut self.color_history, entry, self.settings.dedupe_history, MAX_COLOR_HISTORY) {
+            self.selected_history = None;
+        }
+    }
+
+    /// Updates the current color from the pixel at `position`; returns
+    /// whether it could be captured. Repeated screen-capture failures clear
+    /// the preview and are reported, rather than leaving it stale.
+    fn capture_at_position(&mut self, position: (i32, i32)) -> bool {
+        let (x, y) = position;
+
+        if let Some(source) = &self.image_source {
+            let (x, y) = (x.max(0) as u32, y.max(0) as u32);
+            let Some(color) = source.color_at(x, y) else {
+                return false;
+            };
+            let preview = create_preview(&source.image, x, y, self.settings.preview_aspect.size());
+            self.current_color = Some(ColorInfo {
+                color,
+                position,
+                preview,
+                monitor: Some(source.name.clone()),
+                managed: None,
+                bit_depth: source.bit_depth,
+            });
+            return true;
+        }
+
+        let size = self.settings.preview_aspect.size();
+        match capture_screen_at(position, self.settings.capture_monitor.as_deref(), size) {
+            Ok(mut capture) => {
+                let (center_x, center_y) = capture.center;
+                let Some(raw) = extract_color_at(&capture.image, center_x, center_y) else {
+                    return false;
+                };
+                let profiles = &self.settings.monitor_profiles;
+                let profile = capture.monitor.as_deref().and_then(|name| self.color_profiles.get(name, profiles));
+                let managed = profile.map(|profile| {
+                    let [r, g, b] = profile.to_srgb([raw.r, raw.g, raw.b]);
+                    ManagedColor { raw, corrected: Color::from_rgb(r, g, b) }
+                });
+                let mut color = raw;
+                if let (Some(profile), Some(managed), true) = (profile, managed, self.settings.color_managed) {
+                    profile.convert_image(&mut capture.image);
+                    color = managed.corrected;
+                }
+                self.report_profile_error();
+                let preview = create_preview(&capture.image, center_x, center_y, size);
+                let info = ColorInfo { color, position, preview, monitor: capture.monitor, managed, bit_depth: 8 };
+                let black =
+                    info.preview.as_ref().is_some_and(|preview| preview.rgb_data.iter().all(|&value| value == 0));
+                self.black_frames.record(position, black);
+                if self.capture_backoff.is_failing() && matches!(self.error, Some(PeekerError::Capture(_))) {
+                    self.error = None;
+                }
+                self.capture_backoff.succeeded();
+                self.current_color = Some(info);
+                true
+            },
+            // Gaps between monitors of different sizes have nothing to capture.
+            Err(PeekerError::OffScreen { .. }) => false,
+            Err(e) => {
+                if self.capture_backoff.failed(Instant::now()) {
+                    self.report(e);
+                }
+                if self.capture_backoff.is_failing() {
+                    self.current_color = None;
+                }
+                false
+            },
+        }
+    }
+
+    fn create_command_palette(&self, query: &str) -> Element<'_, Message> {
+        let input = text_input(&self.tr("command-placeholder"), query)
+            .id(COMMAND_PALETTE_INPUT)
+            .on_input(Message::CommandQueryChanged)
+            .on_submit(Message::RunFirstCommand)
+            .size(self.text_size(14.0));
+
+        let mut results = Column::new().spacing(2);
+        for (label, message) in commands::rank(query, self.palette_commands()).into_iter().take(COMMAND_PALETTE_RESULTS)
+        {
+            results = results.push(
+                button(text(label).size(self.text_size(12.0)))
+                    .on_press(Message::RunCommand(Box::new(message)))
+                    .style(button::text)
+                    .width(Length::Fill),
+            );
+        }
+
+        let header = Row::new()
+            .spacing(10)
+            .push(input)
+            .push(button(text("×").size(self.text_size(12.0))).on_press(Message::CommandPaletteToggled(false)));
+        container(Column::new().spacing(5).push(header).push(results))
+            .padding(8)
+            .width(Length::Fixed(420.0))
+            .style(container::rounded_box)
+            .into()
+    }
+
+    fn create_error_banner(&self, error: &PeekerError) -> Element<'_, Message> {
+        let row = Row::new()
+            .spacing(10)
+            .push(text(error.localized(&self.i18n)).size(self.text_size(14.0)).width(Length::Fill))
+            .push(button(text("×").size(self.text_size(12.0))).on_press(Message::DismissError));
+
+        container(row)
+            .padding(8)
+            .width(Length::Fill)
+            .style(|theme: &Theme| {
+                let danger = theme.extended_palette().danger.weak;
+                container::Style {
+                    background: Some(Background::Color(danger.color)),
+                    text_color: Some(danger.text),
+                    border: Border { radius: 4.0.into(), ..Default::default() },
+                    ..Default::default()
+                }
+            })
+            .into()
+    }
+
+    fn create_update_banner(&self, release: &update::Release) -> Element<'_, Message> {
+        let version = release.tag_name.trim_start_matches('v').to_string();
+        let row = Row::new()
+            .spacing(10)
+            .push(
+                text(self.i18n.tr_with("update-available", &[("version", version.into())])).size(self.text_size(14.0)),
+            )
+            .push(button(text(self.tr("release-notes")).size(self.text_size(12.0))).on_press(Message::OpenReleasePage))
+            .push(button(text("×").size(self.text_size(12.0))).on_press(Message::DismissUpdate));
+
+        container(row).padding(8).style(container::rounded_box).into()
+    }
+
+    fn create_toast(&self, toast: &Toast) -> Element<'_, Message> {
+        let mut row = Row::new().spacing(10).push(text(toast.message.clone()).size(self.text_size(14.0)));
+        if !self.undo_stack.is_empty() {
+            row = row.push(button(text(self.tr("undo")).size(self.text_size(12.0))).on_press(Message::Undo));
+        }
+        row = row.push(button(text("×").size(self.text_size(12.0))).on_press(Message::DismissToast));
+
+        container(row).padding(8).style(container::rounded_box).into()
+    }
+
+    fn create_clipboard_offer(&self, color: Color) -> Element<'_, Message> {
+        let row = Row::new()
+            .spacing(10)
+            .push(self.create_color_swatch(color))
+            .push(
+                text(
+                    self.i18n.tr_with("clipboard-copied", &[("color", format_color(&color, &ColorFormat::Hex).into())]),
+                )
+                .size(self.text_size(14.0)),
+            )
+            .push(
+                button(text(self.tr("clipboard-load")).size(self.text_size(12.0)))
+                    .on_press(Message::LoadClipboardColor),
+            )
+            .push(
+                button(text(self.tr("use-as-reference")).size(self.text_size(12.0)))
+                    .on_press(Message::ClipboardColorAsReference),
+            )
+            .push(button(text("×").size(self.text_size(12.0))).on_press(Message::DismissClipboardColor));
+
+        container(row).padding(8).style(container::rounded_box).into()
+    }
+
+    fn create_workspace_selector(&self) -> Element<'_, Message> {
+        let active = self.settings.workspace.clone().unwrap_or_else(|| workspaces::DEFAULT_WORKSPACE.to_string());
+
+        Row::new()
+            .spacing(5)
+            .push(text(self.tr("workspace")).size(self.text_size(12.0)))
+            .push(
+                pick_list(self.workspaces.clone(), Some(active), Message::WorkspaceSelected)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                text_input(&self.tr("new-workspace"), &self.workspace_name_draft)
+                    .on_input(Message::WorkspaceNameDraftChanged)
+                    .on_submit(Message::CreateWorkspace)
+                    .size(self.text_size(12.0))
+                    .width(Length::Fixed(120.0)),
+            )
+            .push(button(text(self.tr("add")).size(self.text_size(12.0))).on_press(Message::CreateWorkspace))
+            .into()
+    }
+
+    fn create_theme_selector(&self) -> Element<'_, Message> {
+        Row::new()
+            .spacing(5)
+            .push(text(self.tr("theme")).size(self.text_size(12.0)))
+            .push(
+                pick_list(
+                    self.choices(&ThemePreference::ALL, |theme| self.tr(theme.message_id())),
+                    Some(Choice::new(self.settings.theme, self.tr(self.settings.theme.message_id()))),
+                    |choice| Message::ThemeSelected(choice.value),
+                )
+                .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.preferences_window.is_some())
+                    .label(self.tr("preferences"))
+                    .on_toggle(Message::PreferencesToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .into()
+    }
+
+    fn create_language_selector(&self) -> Element<'_, Message> {
+        let system = self.tr("language-system");
+        let selected = match &self.settings.language {
+            Some(_) => i18n::LOCALES
+                .iter()
+                .find(|(locale, _, _)| *locale == self.i18n.locale)
+                .map_or_else(|| system.clone(), |(_, name, _)| name.to_string()),
+            None => system.clone(),
+        };
+        let options: Vec<String> =
+            std::iter::once(system).chain(i18n::LOCALES.iter().map(|(_, name, _)| name.to_string())).collect();
+
+        Row::new()
+            .spacing(5)
+            .push(text(self.tr("language")).size(self.text_size(12.0)))
+            .push(pick_list(options, Some(selected), Message::LanguageSelected).text_size(self.text_size(12.0)))
+            .into()
+    }
+
+    fn create_appearance_section(&self) -> Element<'_, Message> {
+        let custom = &self.settings.custom_colors;
+        let mut row = Row::new().spacing(15);
+        for which in CustomColor::ALL {
+            let mut entry = Row::new().spacing(5).push(text(self.tr(which.message_id())).size(self.text_size(12.0)));
+            if let Some(color) = custom.color(which) {
+                entry = entry.push(self.create_color_swatch(color));
+            }
+            entry = entry.push(
+                text_input(&self.tr("theme-default"), custom.raw(which))
+                    .on_input(move |value| Message::CustomColorChanged(which, value))
+                    .size(self.text_size(12.0))
+                    .width(Length::Fixed(90.0)),
+            );
+            row = row.push(entry);
+        }
+
+        let sizes = Row::new()
+            .spacing(10)
+            .push(
+                text(self.i18n.tr_with("ui-scale", &[("scale", format!("{:.2}", self.settings.ui_scale).into())]))
+                    .size(self.text_size(12.0)),
+            )
+            .push(
+                iced::widget::slider(UI_SCALE_RANGE, self.settings.ui_scale, Message::UiScale)
+                    .step(0.05)
+                    .width(Length::Fixed(140.0)),
+            )
+            .push(
+                text(self.i18n.tr_with("font-size", &[("size", format!("{:.0}", self.settings.font_size).into())]))
+                    .size(self.text_size(12.0)),
+            )
+            .push(
+                iced::widget::slider(FONT_SIZE_RANGE, self.settings.font_size, Message::FontSize)
+                    .step(1.0)
+                    .width(Length::Fixed(140.0)),
+            );
+
+        let updates = Row::new()
+            .spacing(10)
+            .push(
+                toggler(self.settings.check_for_updates)
+                    .label(self.tr("check-updates-at-startup"))
+                    .on_toggle(Message::UpdateChecksToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                button(text(self.tr("check-now")).size(self.text_size(12.0)))
+                    .on_press_maybe(self.update_check.is_none().then_some(Message::CheckForUpdates)),
+            );
+
+        let aspect = Row::new()
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .push(text(self.tr("preview-aspect")).size(self.text_size(12.0)))
+            .push(
+                pick_list(PreviewAspect::ALL, Some(self.settings.preview_aspect), Message::PreviewAspectSelected)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(text(self.tr("preview-style")).size(self.text_size(12.0)))
+            .push(
+                pick_list(
+                    self.choices(&PreviewStyle::ALL, |style| self.tr(style.message_id())),
+                    Some(Choice::new(self.settings.preview_style, self.tr(self.settings.preview_style.message_id()))),
+                    |choice| Message::PreviewStyleSelected(choice.value),
+                )
+                .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.smooth_preview)
+                    .label(self.tr("smooth-preview"))
+                    .on_toggle(Message::SmoothPreviewToggled)
+                    .text_size(self.text_size(12.0)),
+            );
+
+        Column::new()
+            .spacing(5)
+            .push(row)
+            .push(sizes)
+            .push(aspect)
+            .push(self.create_language_selector())
+            .push(updates)
+            .into()
+    }
+
+    fn create_format_options_section(&self) -> Element<'_, Message> {
+        let options = &self.settings.format_options;
+        let mut section = Column::new()
+            .spacing(5)
+            .push(text(self.tr("format-options")).size(self.text_size(14.0)))
+            .push(text(self.tr("shown-formats")).size(self.text_size(12.0)))
+            .push(self.create_shown_formats())
+            .push(
+                toggler(options.hex_shorthand)
+                    .label(self.tr("hex-shorthand"))
+                    .on_toggle(Message::HexShorthandToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(options.hex_without_hash)
+                    .label(self.tr("hex-without-hash"))
+                    .on_toggle(Message::HexWithoutHashToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(iced::Alignment::Center)
+                    .push(text(self.tr("shader-decimals")).size(self.text_size(12.0)))
+                    .push(
+                        pick_list([1, 2, 3, 4, 5, 6], Some(options.shader_decimals), Message::ShaderDecimalsSelected)
+                            .text_size(self.text_size(12.0)),
+                    ),
+            );
+        for format in ColorFormat::ALL {
+            if let Some(style) = options.channels(format) {
+                section = section.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_y(iced::Alignment::Center)
+                        .push(
+                            text(self.i18n.tr_with("channel-style", &[("format", self.format_name(format).into())]))
+                                .size(self.text_size(12.0)),
+                        )
+                        .push(
+                            pick_list(ChannelStyle::ALL, Some(style), move |style| {
+                                Message::ChannelStyleSelected(format, style)
+                            })
+                            .text_size(self.text_size(12.0)),
+                        ),
+                );
+            }
+            if let Some(linearized) = options.linearized(format) {
+                section = section.push(
+                    toggler(linearized)
+                        .label(self.i18n.tr_with("linearize", &[("format", self.format_name(format).into())]))
+                        .on_toggle(move |enabled| Message::LinearizeToggled(format, enabled))
+                        .text_size(self.text_size(12.0)),
+                );
+            }
+        }
+        section.into()
+    }
+
+    /// A toggle per format for whether it is listed, wrapped in rows of three.
+    fn create_shown_formats(&self) -> Element<'_, Message> {
+        let mut rows = Column::new().spacing(5);
+        for formats in ColorFormat::ALL.chunks(3) {
+            let mut row = Row::new().spacing(10);
+            for &format in formats {
+                row = row.push(
+                    toggler(self.settings.visible_formats.contains(&format))
+                        .label(self.format_name(format))
+                        .on_toggle(move |shown| Message::FormatShownToggled(format, shown))
+                        .text_size(self.text_size(12.0))
+                        .width(Length::FillPortion(1)),
+                );
+            }
+            rows = rows.push(row);
+        }
+        rows.into()
+    }
+
+    fn create_named_colors_section(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(5)
+            .push(text(self.tr("named-colors")).size(self.text_size(14.0)))
+            .push(
+                toggler(self.settings.show_flutter)
+                    .label(self.tr("show-flutter"))
+                    .on_toggle(Message::FlutterNamesToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.show_x11)
+                    .label(self.tr("show-x11"))
+                    .on_toggle(Message::X11NamesToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.show_ansi)
+                    .label(self.tr("show-ansi"))
+                    .on_toggle(Message::AnsiNamesToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.show_web_safe)
+                    .label(self.tr("show-web-safe"))
+                    .on_toggle(Message::WebSafeToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.show_ral)
+                    .label(self.tr("show-ral"))
+                    .on_toggle(Message::RalToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.auto_label_history)
+                    .label(self.tr("auto-label-history"))
+                    .on_toggle(Message::AutoLabelHistoryToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .into()
+    }
+
+    fn create_auto_export_section(&self) -> Element<'_, Message> {
+        let auto_export = self.settings.auto_export.as_ref();
+        let format = auto_export.map_or(self.export_format, |auto_export| auto_export.format);
+        let mut row = Row::new()
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .push(
+                pick_list(
+                    self.choices(PaletteFormat::ALL, |format| self.tr(format.message_id())),
+                    Some(Choice::new(format, self.tr(format.message_id()))),
+                    |choice| Message::AutoExportFormatSelected(choice.value),
+                )
+                .text_size(self.text_size(12.0)),
+            )
+            .push(
+                button(text(self.tr("choose-file")).size(self.text_size(12.0)))
+                    .on_press(Message::ChooseAutoExportPath(format)),
+            );
+        if let Some(auto_export) = auto_export {
+            row = row
+                .push(text(auto_export.path.display().to_string()).size(self.text_size(12.0)))
+                .push(button(text(self.tr("off")).size(self.text_size(12.0))).on_press(Message::DisableAutoExport));
+        }
+        Column::new()
+            .spacing(5)
+            .push(text(self.tr("auto-export")).size(self.text_size(14.0)))
+            .push(text(self.tr("auto-export-hint")).size(self.text_size(12.0)))
+            .push(row)
+            .into()
+    }
+
+    fn create_color_management_section(&self) -> Element<'_, Message> {
+        let mut section = Column::new()
+            .spacing(5)
+            .push(text(self.tr("color-management")).size(self.text_size(14.0)))
+            .push(
+                toggler(self.settings.color_managed)
+                    .label(self.tr("color-managed"))
+                    .on_toggle(Message::ColorManagedToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.compare_raw_values)
+                    .label(self.tr("compare-raw"))
+                    .on_toggle(Message::CompareRawValuesToggled)
+                    .text_size(self.text_size(12.0)),
+            );
+        for display in &self.displays {
+            let chosen = self.settings.monitor_profiles.get(&display.name);
+            let status = match chosen {
+                Some(path) => {
+                    path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into())
+                },
+                None if self.color_profiles.has_profile(&display.name) => self.tr("profile-system"),
+                None => self.tr("profile-none"),
+            };
+            let mut row = Row::new()
+                .spacing(10)
+                .align_y(iced::Alignment::Center)
+                .push(text(format!("{}: {}", display.name, status)).size(self.text_size(12.0)))
+                .push(
+                    button(text(self.tr("choose-profile")).size(self.text_size(12.0)))
+                        .on_press(Message::ChooseMonitorProfile(display.name.clone())),
+                );
+            if chosen.is_some() {
+                row = row.push(
+                    button(text(self.tr("use-system-profile")).size(self.text_size(12.0)))
+                        .on_press(Message::ResetMonitorProfile(display.name.clone())),
+                );
+            }
+            section = section.push(row);
+        }
+        section.into()
+    }
+
+    fn create_title(&self) -> Element<'_, Message> {
+        text("Pixel Peeker").size(self.text_size(20.0)).style(heading_text).into()
+    }
+
+    fn create_preview_row(&self, color_info: &ColorInfo) -> Element<'_, Message> {
+        let preview_canvas: Element<'_, Message> = if let Some(preview) = &color_info.preview {
+            let canvas = preview_canvas_size(preview.width, preview.height);
+            let canvas = Canvas::new(PreviewRenderer {
+                rgb_data: preview.rgb_data.clone(),
+                width: preview.width,
+                height: preview.height,
+                zoom_factor: self.zoom_factor,
+                anchor: self.settings.zoom_anchor,
+                crosshair: preview.crosshair(),
+                circular: self.settings.preview_style == PreviewStyle::Circle,
+                smooth: self.settings.smooth_preview.then(|| preview.image()),
+            })
+            .width(Length::Fixed(canvas.width))
+            .height(Length::Fixed(canvas.height));
+            // Clicking a cell of a frozen preview samples it instead.
+            mouse_area(canvas).on_move(Message::PreviewHovered).on_press(Message::PreviewClicked).into()
+        } else {
+            Canvas::new(EmptyRenderer)
+                .width(Length::Fixed(PREVIEW_CANVAS_SIZE))
+                .height(Length::Fixed(PREVIEW_CANVAS_SIZE))
+                .into()
+        };
+
+        let preview_with_shadow: Element<'_, Message> = Container::new(preview_canvas)
+            .style(|_theme: &Theme| container::Style {
+                shadow: iced::Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
+                    offset: iced::Vector::new(4.0, 4.0),
+                    blur_radius: 8.0,
+                },
+                border: Border { color: Color::from_rgb(0.3, 0.3, 0.3), width: 1.0, radius: 6.0.into() },
+                background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))),
+                ..Default::default()
+            })
+            .padding(4)
+            .into();
+
+        let zoom_slider = self.create_zoom_slider();
+
+        let info_column = self.create_color_info_column(color_info);
+
+        Row::new().spacing(20).push(Column::new().push(preview_with_shadow).push(zoom_slider)).push(info_column).into()
+    }
+
+    /// The framebuffer value and its sRGB conversion side by side, to tell a
+    /// wrong color in the app under test from one made by the display pipeline.
+    fn create_raw_comparison(&self, managed: ManagedColor) -> Element<'_, Message> {
+        let hex = |color: &Color| format_color(color, &ColorFormat::Hex);
+        Row::new()
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .push(self.create_color_swatch(managed.raw))
+            .push(
+                text(self.i18n.tr_with("raw-value", &[("color", hex(&managed.raw).into())])).size(self.text_size(12.0)),
+            )
+            .push(self.create_color_swatch(managed.corrected))
+            .push(
+                text(self.i18n.tr_with("corrected-value", &[("color", hex(&managed.corrected).into())]))
+                    .size(self.text_size(12.0)),
+            )
+            .push(
+                text(format!("ΔE {:.2}", metrics::delta_e(managed.raw, managed.corrected))).size(self.text_size(12.0)),
+            )
+            .into()
+    }
+
+    /// The nearest entry of each named palette, and of the optional ones
+    /// that are switched on.
+    fn create_named_color_rows(&self, color: Color) -> Element<'_, Message> {
+        let mut column = Column::new().spacing(5).push(
+            text(self.i18n.tr_with("color-description", &[("name", named_colors::describe(color, &self.i18n).into())]))
+                .size(self.text_size(12.0)),
+        );
+        let palettes = [
+            (self.settings.show_flutter, &named_colors::FLUTTER),
+            (self.settings.show_x11, &named_colors::X11),
+            (self.settings.show_ansi, &named_colors::ANSI),
+            (self.settings.show_ral, &named_colors::RAL_CLASSIC),
+        ];
+        for palette in palettes.into_iter().filter_map(|(shown, palette)| shown.then_some(palette)) {
+            if let Some((name, distance)) = palette.nearest(color) {
+                column = column.push(
+                    text(self.i18n.tr_with(
+                        "nearest-named",
+                        &[
+                            ("palette", palette.name.into()),
+                            ("name", name.into()),
+                            ("distance", format!("{:.2}", distance).into()),
+                        ],
+                    ))
+                    .size(self.text_size(12.0)),
+                );
+            }
         }
+        if self.settings.show_web_safe {
+            let (safe, distance) = named_colors::nearest_web_safe(color);
+            column = column.push(
+                Row::new().spacing(10).align_y(iced::Alignment::Center).push(self.create_color_swatch(safe)).push(
+                    text(self.i18n.tr_with(
+                        "nearest-web-safe",
+                        &[
+                            ("color", format_color(&safe, &ColorFormat::Hex).into()),
+                            ("distance", format!("{:.2}", distance).into()),
+                        ],
+                    ))
+                    .size(self.text_size(12.0)),
+                ),
+            );
+        }
+        column.into()
     }
 
-    fn view(&self) -> Element<'_, Message> {
-        let mut content = Column::new().spacing(10).push(self.create_title());
+    fn create_color_info_column(&self, color_info: &ColorInfo) -> Element<'_, Message> {
+        let mut column = Column::new()
+            .spacing(5)
+            .push(text(self.tr("mouse-position-heading")).style(heading_text))
+            .push(text(format!("({}, {})", color_info.position.0, color_info.position.1)).size(self.text_size(14.0)))
+            .push(text(self.tr("picked-color")).style(heading_text))
+            .push(self.create_color_swatch(color_info.color));
+        if self.settings.compare_raw_values {
+            column = column.push(match color_info.managed {
+                Some(managed) => self.create_raw_comparison(managed),
+                None => text(self.tr("compare-raw-unavailable")).size(self.text_size(12.0)).into(),
+            });
+        }
 
-        let (display_x, display_y) = self.get_display_position();
-        content = content.push(text(format!("Mouse: ({}, {})", display_x, display_y)));
+        if color_info.bit_depth > 8 {
+            let [r, g, b] = channel_values(&color_info.color, color_info.bit_depth);
+            let values = format!("{}, {}, {}", r, g, b);
+            column = column.push(
+                text(
+                    self.i18n
+                        .tr_with("deep-channels", &[("bits", color_info.bit_depth.into()), ("values", values.into())]),
+                )
+                .size(self.text_size(12.0)),
+            );
+        }
+        for format in self.visible_formats() {
+            column = column.push(self.create_color_row(color_info, format));
+        }
+        for (index, _, formatted) in self.plugins.formats(color_info.color) {
+            column = column.push(
+                Row::new()
+                    .spacing(10)
+                    .push(text(formatted).width(Length::Fill))
+                    .push(button(text(self.tr("copy"))).on_press(Message::CopyPluginFormat(index, color_info.color))),
+            );
+        }
 
-        if let Some(color_info) = self.get_active_color() {
-            let preview_row = self.create_preview_row(color_info);
-            content = content.push(preview_row);
-        } else {
-            content = content.push(text("No preview available - checking monitors..."));
+        column = column.push(match tokens::nearest(&self.design_tokens, color_info.color) {
+            Some((token, distance)) => Row::new()
+                .spacing(10)
+                .push(self.create_color_swatch(token.color))
+                .push(
+                    text(self.i18n.tr_with(
+                        "nearest-token",
+                        &[("name", token.name.as_str().into()), ("distance", format!("{:.2}", distance).into())],
+                    ))
+                    .size(self.text_size(12.0)),
+                )
+                .push(button(text(self.tr("clear")).size(self.text_size(12.0))).on_press(Message::ClearDesignTokens)),
+            None => Row::new().push(
+                button(text(self.tr("load-design-tokens")).size(self.text_size(12.0)))
+                    .on_press(Message::LoadDesignTokens),
+            ),
+        });
+
+        column = column.push(self.create_named_color_rows(color_info.color));
+
+        let mut slots = Row::new()
+            .spacing(5)
+            .align_y(iced::Alignment::Center)
+            .push(text(self.tr("keep-in-slot")).size(self.text_size(12.0)));
+        for (slot, name) in SLOT_NAMES.iter().enumerate() {
+            slots = slots.push(button(text(*name).size(self.text_size(12.0))).on_press(Message::StoreSlot(slot)));
         }
+        column = column.push(slots);
 
-        content = content.push(self.create_status_text());
+        if !self.palette_library.palettes.is_empty() {
+            column = column.push(self.create_compliance_row(color_info.color));
+        }
 
-        if !self.color_history.is_empty() {
-            content = content.push(self.create_history_section());
+        if let Some(frozen) = self.picker.frozen().filter(|frozen| frozen.position == color_info.position) {
+            let mut row = Row::new()
+                .spacing(10)
+                .push(
+                    button(text(self.tr("measure-region")).size(self.text_size(12.0))).on_press(Message::MeasureRegion),
+                )
+                .push(
+                    button(text(self.tr("snapshot-before")).size(self.text_size(12.0)))
+                        .on_press_maybe(frozen.preview.is_some().then_some(Message::StoreSnapshot(0))),
+                )
+                .push(
+                    button(text(self.tr("snapshot-after")).size(self.text_size(12.0)))
+                        .on_press_maybe(frozen.preview.is_some().then_some(Message::StoreSnapshot(1))),
+                );
+            if let Some((_, region)) = self.region.filter(|(position, _)| *position == frozen.position) {
+                let at_least = if region.clipped { "≥ " } else { "" };
+                row = row.push(
+                    text(format!("{}{} × {} px", at_least, region.width, region.height)).size(self.text_size(12.0)),
+                );
+            }
+            column = column.push(row);
         }
 
-        Container::new(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .padding(20)
-            .style(if self.is_frozen() {
-                |_: &Theme| container::Style {
-                    background: Some(Background::Color(Color::from_rgb(0.05, 0.05, 0.05))),
-                    ..Default::default()
-                }
-            } else {
-                |_: &Theme| container::Style {
-                    background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.2))),
-                    ..Default::default()
-                }
+        column.into()
+    }
+
+    /// Active color and reference side by side: every format for each, with
+    /// their ΔE, contrast and channel deltas between them.
+    fn create_compare_panel(&self, active: Color, reference: Color) -> Element<'_, Message> {
+        let describe = |label: &str, color: Color| {
+            let mut column = Column::new()
+                .spacing(3)
+                .push(text(self.tr(label)).style(heading_text))
+                .push(self.create_color_swatch(color));
+            for format in self.visible_formats() {
+                column = column.push(text(self.format(&color, format)).size(self.text_size(12.0)));
+            }
+            column
+        };
+
+        let deltas = metrics::channel_deltas(active, reference);
+        let comparison = Column::new()
+            .spacing(3)
+            .push(text(self.tr("difference")).style(heading_text))
+            .push(
+                Canvas::new(SplitSwatchRenderer { first: active, second: reference })
+                    .width(Length::Fixed(120.0))
+                    .height(Length::Fixed(80.0)),
+            )
+            .push(text(format!("ΔE {:.2}", metrics::delta_e(active, reference))).size(self.text_size(12.0)))
+            .push(
+                text(self.i18n.tr_with(
+                    "contrast-ratio",
+                    &[("ratio", format!("{:.2}", metrics::contrast_ratio(active, reference)).into())],
+                ))
+                .size(self.text_size(12.0)),
+            )
+            .push(text(format!("ΔR {:+}  ΔG {:+}  ΔB {:+}", deltas.r, deltas.g, deltas.b)).size(self.text_size(12.0)))
+            .push(
+                text(format!("ΔH {:+.1}°  ΔS {:+.1}%  ΔL {:+.1}%", deltas.hue, deltas.saturation, deltas.lightness))
+                    .size(self.text_size(12.0)),
+            )
+            .push(
+                button(text(self.tr("clear-reference")).size(self.text_size(12.0))).on_press(Message::ClearReference),
+            );
+
+        container(
+            Row::new()
+                .spacing(20)
+                .push(describe("compare-active", active))
+                .push(describe("compare-reference", reference))
+                .push(comparison),
+        )
+        .padding(8)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// Brand check of `color` against the chosen named palette: green for an
+    /// exact match, yellow within the ΔE tolerance, red otherwise.
+    fn create_compliance_row(&self, color: Color) -> Element<'_, Message> {
+        let palette = self
+            .settings
+            .compliance_palette
+            .as_ref()
+            .and_then(|name| self.palette_library.palettes.iter().find(|palette| palette.name == *name));
+
+        let mut row = Row::new().spacing(10).push(text(self.tr("brand-check")).size(self.text_size(12.0))).push(
+            pick_list(self.palette_library.names(), palette.map(|palette| palette.name.clone()), |name| {
+                Message::CompliancePaletteSelected(Some(name))
             })
+            .placeholder(self.tr("off"))
+            .text_size(self.text_size(12.0)),
+        );
+
+        let Some(palette) = palette else {
+            return row.into();
+        };
+        let colors: Vec<Color> = palette.colors.iter().cloned().map(Color::from).collect();
+        let tolerance = self.settings.compliance_tolerance;
+
+        if let Some((index, distance, compliance)) = metrics::check_compliance(color, &colors, tolerance) {
+            let nearest = HistoryEntry::from(palette.colors[index].clone()).display_name();
+            let (verdict, tone) = match compliance {
+                metrics::Compliance::Exact => (format!("✔ {}", nearest), Tone::Good),
+                metrics::Compliance::WithinTolerance => (format!("≈ {} (ΔE {:.2})", nearest, distance), Tone::Caution),
+                metrics::Compliance::OffBrand => (
+                    self.i18n.tr_with(
+                        "off-brand",
+                        &[("name", nearest.into()), ("distance", format!("{:.2}", distance).into())],
+                    ),
+                    Tone::Bad,
+                ),
+            };
+            row =
+                row.push(text(verdict).size(self.text_size(12.0)).style(move |theme: &Theme| tone.style(theme, None)));
+        }
+
+        row.push(text(format!("ΔE ≤ {:.1}", tolerance)).size(self.text_size(12.0)))
+            .push(
+                iced::widget::slider(0.5..=10.0, tolerance, Message::ComplianceTolerance)
+                    .step(0.5)
+                    .width(Length::Fixed(80.0)),
+            )
+            .push(
+                button(text(self.tr("off")).size(self.text_size(12.0)))
+                    .on_press(Message::CompliancePaletteSelected(None)),
+            )
             .into()
     }
 
-    fn subscription(&self) -> Subscription<Message> {
-        Subscription::batch([iced::time::every(std::time::Duration::from_millis(33)).map(Message::Tick)])
+    fn create_color_swatch(&self, color: Color) -> Element<'_, Message> {
+        container(text("   "))
+            .style(move |_theme: &Theme| container::Style {
+                background: Some(Background::Color(color)),
+                border: Border { color: Color::from_rgb(0.5, 0.5, 0.5), width: 1.0, radius: 4.0.into() },
+                ..Default::default()
+            })
+            .width(Length::Fixed(60.0))
+            .height(Length::Fixed(30.0))
+            .into()
     }
 
-    fn update_color_picking(&mut self) {
-        let input_event = self.process_input();
-        let mouse_pos = self.get_mouse_position();
+    fn create_color_row(&self, info: &ColorInfo, format: ColorFormat) -> Element<'_, Message> {
+        let label = self.format_info(info, format);
+        let focused = self.keyboard_focus == Some(KeyboardFocus::CopyButton(format));
+        let star_focused = self.keyboard_focus == Some(KeyboardFocus::PreferredButton(format));
+        let preferred = self.settings.preferred_format == format;
 
-        match input_event {
-            InputEvent::Freeze => {
-                self.handle_freeze(mouse_pos);
-                return;
+        let preferred_button = button(text(if preferred { "★" } else { "☆" }).size(self.text_size(12.0)))
+            .on_press(Message::PreferredFormatSelected(format))
+            .style(move |theme: &Theme, status| {
+                let mut style = button::text(theme, status);
+                if star_focused {
+                    style.border = Border { color: focus_color(theme), width: 2.0, radius: 2.0.into() };
+                }
+                style
+            });
+        let hint = self.tr(if preferred { "preferred-format" } else { "make-preferred-format" });
+        // Only the first nine visible formats have a number key.
+        let index = self.visible_formats().iter().position(|&visible| visible == format).unwrap_or_default();
+        let copy_hint = if index < 9 {
+            self.i18n.tr_with("copy-shortcut", &[("key", (index + 1).into())])
+        } else {
+            self.i18n.tr_with("copy-as", &[("format", self.format_name(format).into())])
+        };
+
+        Row::new()
+            .spacing(10)
+            .push(text(label).width(Length::Fill))
+            .push(tooltip(preferred_button, text(hint).size(self.text_size(12.0)), tooltip::Position::Left))
+            .push(tooltip(
+                button(text(self.tr("copy"))).on_press(Message::CopyColor(format)).style(
+                    move |theme: &Theme, status| {
+                        let mut style = button::primary(theme, status);
+                        if focused {
+                            style.border = Border { color: focus_color(theme), width: 2.0, radius: 2.0.into() };
+                        }
+                        style
+                    },
+                ),
+                text(copy_hint).size(self.text_size(12.0)),
+                tooltip::Position::Right,
+            ))
+            .into()
+    }
+
+    fn create_zoom_slider(&self) -> Element<'_, Message> {
+        let zoom_ui = Column::new()
+            .spacing(10)
+            .push(iced::widget::Text::new(
+                self.i18n.tr_with("zoom", &[("zoom", format!("{:.1}", self.zoom_factor).into())]),
+            ))
+            .push(iced::widget::slider(1.0..=5.0, self.zoom_factor, Message::ZoomFactor).step(0.1))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_y(iced::Alignment::Center)
+                    .push(text(self.tr("zoom-anchor")).size(self.text_size(12.0)))
+                    .push(
+                        pick_list(
+                            self.choices(&ZoomAnchor::ALL, |anchor| self.tr(anchor.message_id())),
+                            Some(Choice::new(
+                                self.settings.zoom_anchor,
+                                self.tr(self.settings.zoom_anchor.message_id()),
+                            )),
+                            |choice| Message::ZoomAnchorSelected(choice.value),
+                        )
+                        .text_size(self.text_size(12.0)),
+                    ),
+            );
+        zoom_ui.into()
+    }
+
+    fn create_image_source_row(&self) -> Element<'_, Message> {
+        let Some(source) = &self.image_source else {
+            return Row::new()
+                .spacing(10)
+                .push(button(text(self.tr("pick-from-image")).size(self.text_size(12.0))).on_press(Message::OpenImage))
+                .push(button(text(self.tr("paste-image")).size(self.text_size(12.0))).on_press(Message::PasteImage))
+                .push(text(self.tr("drop-image")).size(self.text_size(12.0)))
+                .into();
+        };
+
+        let size = source.display_size();
+        let picture = iced::widget::image(source.handle.clone())
+            .width(Length::Fixed(size.width))
+            .height(Length::Fixed(size.height))
+            .content_fit(iced::ContentFit::Fill)
+            .filter_method(iced::widget::image::FilterMethod::Nearest);
+
+        Row::new()
+            .spacing(10)
+            .push(
+                mouse_area(picture)
+                    .on_move(Message::ImageHovered)
+                    .on_press(Message::ImageClicked)
+                    .interaction(mouse::Interaction::Crosshair),
+            )
+            .push(
+                Column::new()
+                    .spacing(5)
+                    .push(
+                        text(self.i18n.tr_with("picking-from", &[("name", source.name.as_str().into())]))
+                            .size(self.text_size(12.0)),
+                    )
+                    .push(
+                        text(format!("{} × {} px", source.image.width(), source.image.height()))
+                            .size(self.text_size(12.0)),
+                    )
+                    .push(
+                        button(text(self.tr("back-to-screen")).size(self.text_size(12.0)))
+                            .on_press(Message::CloseImage),
+                    ),
+            )
+            .into()
+    }
+
+    fn open_image_source(&mut self, source: image_source::ImageSource) {
+        self.transition(Transition::Unfreeze);
+        self.image_source = Some(source);
+    }
+
+    fn create_status_text(&self) -> Element<'_, Message> {
+        let custom = &self.settings.custom_colors;
+        let (status_text, tone, custom_color) = if self.picker.is_paused() {
+            (self.tr("status-paused"), Tone::Muted, None)
+        } else if self.is_frozen() {
+            (self.tr("status-frozen"), Tone::Info, custom.color(CustomColor::Frozen))
+        } else {
+            (self.tr("status-live"), Tone::Good, custom.color(CustomColor::Live))
+        };
+
+        Row::new()
+            .spacing(10)
+            .push(text(status_text).style(move |theme: &Theme| tone.style(theme, custom_color)))
+            .push(
+                button(
+                    text(self.tr(if self.picker.is_paused() { "resume" } else { "pause" })).size(self.text_size(12.0)),
+                )
+                .on_press(Message::TogglePause),
+            )
+            .into()
+    }
+
+    /// Explains displays that capture cannot make sense of and offers a
+    /// manual monitor choice; also shown while a monitor is chosen.
+    fn create_display_notice(&self) -> Option<Element<'_, Message>> {
+        let issue = capture::diagnose(&self.displays, self.black_frames.is_blank());
+        if issue.is_none() && self.settings.capture_monitor.is_none() {
+            return None;
+        }
+
+        let automatic = self.tr("monitor-automatic");
+        let options: Vec<String> = std::iter::once(automatic.clone())
+            .chain(self.displays.iter().map(|display| display.name.clone()))
+            .collect();
+        let selected = self.settings.capture_monitor.clone().unwrap_or(automatic);
+        let mut row = Row::new().spacing(10).align_y(iced::Alignment::Center);
+        if let Some(issue) = issue {
+            let message = match issue {
+                capture::DisplayIssue::NoMonitors => self.tr("display-no-monitors"),
+                capture::DisplayIssue::OddBounds(name) => {
+                    self.i18n.tr_with("display-odd-bounds", &[("name", name.into())])
+                },
+                capture::DisplayIssue::BlackFrames => self.tr("display-black-frames"),
+            };
+            row = row
+                .push(text(message).size(self.text_size(12.0)).style(|theme: &Theme| Tone::Caution.style(theme, None)));
+        }
+        row = row
+            .push(text(self.tr("capture-monitor")).size(self.text_size(12.0)))
+            .push(pick_list(options, Some(selected), Message::CaptureMonitorSelected).text_size(self.text_size(12.0)));
+        Some(row.into())
+    }
+
+    fn create_pick_options(&self) -> Element<'_, Message> {
+        let log_hint = match &self.settings.pick_log {
+            Some(path) => self.i18n.tr_with("log-appending", &[("path", path.display().to_string().into())]),
+            None => self.tr("log-hint"),
+        };
+
+        let row = Row::new()
+            .spacing(15)
+            .push(
+                toggler(self.settings.notify_on_pick)
+                    .label(self.tr("notify-on-pick"))
+                    .on_toggle(Message::NotifyOnPickToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.feedback_sound)
+                    .label(self.tr("sound"))
+                    .on_toggle(Message::FeedbackSoundToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(tooltip(
+                toggler(self.settings.pick_log.is_some())
+                    .label(self.tr("log-picks"))
+                    .on_toggle(Message::PickLogToggled)
+                    .text_size(self.text_size(12.0)),
+                container(text(log_hint).size(self.text_size(12.0))).padding(6).style(container::rounded_box),
+                tooltip::Position::Bottom,
+            ))
+            .push(
+                text_input(&self.tr("pick-command-placeholder"), self.settings.pick_command.as_deref().unwrap_or(""))
+                    .on_input(Message::PickCommandChanged)
+                    .size(self.text_size(12.0))
+                    .width(Length::Fixed(240.0)),
+            )
+            .push(
+                toggler(self.measure.is_some())
+                    .label(self.tr("measure"))
+                    .on_toggle(Message::MeasureToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.watch.is_some())
+                    .label(self.tr("watch"))
+                    .on_toggle(Message::WatchToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.loupe_window.is_some())
+                    .label(self.tr("loupe"))
+                    .on_toggle(Message::LoupeToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                button(text(self.tr("pick-anywhere")).size(self.text_size(12.0)))
+                    .on_press(Message::PickOverlayToggled(true)),
+            )
+            .push(
+                toggler(self.settings.low_power)
+                    .label(self.tr("battery-saver"))
+                    .on_toggle(Message::LowPowerToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.settings.watch_clipboard)
+                    .label(self.tr("clipboard-colors"))
+                    .on_toggle(Message::WatchClipboardToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                toggler(self.recorder.is_some())
+                    .label(self.tr("record"))
+                    .on_toggle(Message::RecordToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(
+                text(self.i18n.tr_with(
+                    "record-interval",
+                    &[("ms", self.settings.record_interval_ms.max(self.capture_tick_ms()).into())],
+                ))
+                .size(self.text_size(12.0)),
+            )
+            .push(
+                iced::widget::slider(
+                    self.capture_tick_ms()..=2000,
+                    self.settings.record_interval_ms.max(self.capture_tick_ms()),
+                    Message::RecordInterval,
+                )
+                .width(Length::Fixed(100.0)),
+            )
+            .push(
+                toggler(self.api.is_some())
+                    .label(self.i18n.tr_with("api-on", &[("port", self.settings.api_port.to_string().into())]))
+                    .on_toggle(Message::ApiToggled)
+                    .text_size(self.text_size(12.0)),
+            )
+            .push(self.api.as_ref().and(api::token_path()).map(|path| {
+                text(self.i18n.tr_with("api-token", &[("path", path.display().to_string().into())]))
+                    .size(self.text_size(12.0))
+            }));
+        #[cfg(target_os = "linux")]
+        let row = row.push(
+            toggler(self.dbus.is_some())
+                .label(self.tr("dbus-on"))
+                .on_toggle(Message::DbusToggled)
+                .text_size(self.text_size(12.0)),
+        );
+        row.into()
+    }
+
+    fn create_measure_section(&self, measurement: &measure::Measurement) -> Element<'_, Message> {
+        let mut row = Row::new()
+            .spacing(20)
+            .push(self.create_measure_point("A", measurement.a.as_ref()))
+            .push(self.create_measure_point("B", measurement.b.as_ref()));
+        if let Some(span) = measurement.span() {
+            row = row.push(
+                text(self.i18n.tr_with(
+                    "measure-span",
+                    &[
+                        ("dx", span.dx.into()),
+                        ("dy", span.dy.into()),
+                        ("distance", format!("{:.1}", span.distance).into()),
+                        ("angle", format!("{:.1}", span.angle).into()),
+                    ],
+                ))
+                .size(self.text_size(12.0)),
+            );
+            row = row.push(
+                toggler(self.ruler.is_some())
+                    .label(self.tr("ruler-overlay"))
+                    .on_toggle(Message::RulerToggled)
+                    .text_size(self.text_size(12.0)),
+            );
+        } else {
+            return row.into();
+        }
+
+        let mut sampling = Row::new()
+            .spacing(10)
+            .push(
+                text(self.i18n.tr_with("line-stops", &[("count", self.line_samples.into())]))
+                    .size(self.text_size(12.0)),
+            )
+            .push(
+                iced::widget::slider(2..=32, self.line_samples, Message::LineSamplesChanged)
+                    .width(Length::Fixed(120.0)),
+            )
+            .push(button(text(self.tr("sample-line")).size(self.text_size(12.0))).on_press(Message::SampleLine));
+        if !self.line_stops.is_empty() {
+            for color in &self.line_stops {
+                sampling = sampling.push(self.create_color_swatch(*color));
+            }
+            sampling = sampling.push(
+                button(text(self.tr("copy-gradient")).size(self.text_size(12.0))).on_press(Message::CopyGradient),
+            );
+        }
+
+        Column::new().spacing(5).push(row).push(sampling).into()
+    }
+
+    /// The kept colors as large swatches, each written in its own format.
+    fn create_slots_section(&self) -> Element<'_, Message> {
+        let mut row = Row::new().spacing(20);
+        for (index, (slot, name)) in self.slots.iter().zip(SLOT_NAMES).enumerate() {
+            let mut column = Column::new().spacing(5).push(text(name).style(heading_text));
+            match slot {
+                Some(slot) => {
+                    let color = slot.color;
+                    column = column
+                        .push(
+                            container(text(""))
+                                .style(move |theme: &Theme| container::Style {
+                                    background: Some(Background::Color(color)),
+                                    border: Border { color: Tone::Muted.color(theme), width: 1.0, radius: 6.0.into() },
+                                    ..Default::default()
+                                })
+                                .width(Length::Fixed(120.0))
+                                .height(Length::Fixed(80.0)),
+                        )
+                        .push(text(self.format(&color, slot.format)).size(self.text_size(12.0)))
+                        .push(
+                            pick_list(
+                                self.choices(&ColorFormat::ALL, |format| self.format_name(format)),
+                                Some(Choice::new(slot.format, self.format_name(slot.format))),
+                                move |choice| Message::SlotFormatSelected(index, choice.value),
+                            )
+                            .text_size(self.text_size(12.0)),
+                        )
+                        .push(
+                            Row::new()
+                                .spacing(5)
+                                .push(
+                                    button(text(self.tr("copy")).size(self.text_size(12.0)))
+                                        .on_press(Message::CopySlot(index)),
+                                )
+                                .push(
+                                    button(text(self.tr("clear")).size(self.text_size(12.0)))
+                                        .on_press(Message::ClearSlot(index)),
+                                ),
+                        );
+                },
+                None => column = column.push(text(self.tr("slot-empty")).size(self.text_size(12.0))),
+            }
+            row = row.push(column);
+        }
+        container(row).padding(8).style(container::rounded_box).into()
+    }
+
+    fn create_diff_section(&self) -> Element<'_, Message> {
+        let label = |id: &str, snapshot: &Option<ColorInfo>| match snapshot {
+            Some(info) => format!("{}: ({}, {})", self.tr(id), info.position.0, info.position.1),
+            None => self.i18n.tr_with("snapshot-not-stored", &[("name", self.tr(id).into())]),
+        };
+        let mut column = Column::new().spacing(5).push(
+            Row::new()
+                .spacing(10)
+                .push(text(label("snapshot-before", &self.snapshots[0])).size(self.text_size(12.0)))
+                .push(text(label("snapshot-after", &self.snapshots[1])).size(self.text_size(12.0)))
+                .push(button(text(self.tr("clear")).size(self.text_size(12.0))).on_press(Message::ClearSnapshots)),
+        );
+
+        let previews =
+            self.snapshots.each_ref().map(|snapshot| snapshot.as_ref().and_then(|info| info.preview.as_ref()));
+        let [Some(a), Some(b)] = previews else {
+            return column.into();
+        };
+        match diff::PreviewDiff::between(&a.rgb_data, &b.rgb_data, b.width, b.height) {
+            Some(diff) => {
+                let canvas = preview_canvas_size(b.width, b.height);
+                let summary = self.i18n.tr_with(
+                    "pixels-changed",
+                    &[
+                        ("changed", diff.changed().into()),
+                        ("total", diff.delta_e.len().into()),
+                        ("max", format!("{:.2}", diff.max()).into()),
+                    ],
+                );
+                column = column.push(
+                    Row::new()
+                        .spacing(20)
+                        .push(
+                            Canvas::new(DiffRenderer { rgb_data: b.rgb_data.clone(), diff })
+                                .width(Length::Fixed(canvas.width))
+                                .height(Length::Fixed(canvas.height)),
+                        )
+                        .push(text(summary).size(self.text_size(12.0))),
+                );
             },
-            InputEvent::Unfreeze => {
-                self.frozen_color = None;
+            None => column = column.push(text(self.tr("snapshots-size-mismatch")).size(self.text_size(12.0))),
+        }
+        column.into()
+    }
+
+    fn create_watch_section(&self, watch: &watch::Watch) -> Element<'_, Message> {
+        let mut row = Row::new().spacing(10).push(
+            text(self.i18n.tr_with("watching", &[("x", watch.position.0.into()), ("y", watch.position.1.into())]))
+                .size(self.text_size(12.0)),
+        );
+        if let Some(color) = watch.last {
+            row =
+                row.push(self.create_color_swatch(color)).push(text(export::to_hex(color)).size(self.text_size(12.0)));
+        }
+        row.push(
+            text_input(&self.tr("watch-target-placeholder"), &self.watch_target_draft)
+                .on_input(Message::WatchTargetChanged)
+                .size(self.text_size(12.0))
+                .width(Length::Fixed(150.0)),
+        )
+        .push(
+            text_input(&self.tr("watch-command-placeholder"), self.settings.watch_command.as_deref().unwrap_or(""))
+                .on_input(Message::WatchCommandChanged)
+                .size(self.text_size(12.0))
+                .width(Length::Fixed(240.0)),
+        )
+        .into()
+    }
+
+    /// Samples the watched pixel when due and notifies (and runs the watch
+    /// command) on a change or target match.
+    fn check_watch(&mut self, now: Instant) {
+        let Some(watch) = self.watch.as_mut() else {
+            return;
+        };
+        if !watch.due(now) {
+            return;
+        }
+        let (x, y) = watch.position;
+        let image = capture_screen_region(x, y, 1, 1, &self.settings, &mut self.color_profiles);
+        self.report_profile_error();
+        let image = match image {
+            Ok(image) => image,
+            Err(e) => {
+                self.report(e);
                 return;
             },
-            InputEvent::None => {},
+        };
+        let Some(color) = extract_color_at(&image, 0, 0) else {
+            return;
+        };
+
+        let Some(watch) = self.watch.as_mut() else {
+            return;
+        };
+        let hex = export::to_hex(color);
+        let message = match watch.observe(color) {
+            Some(watch::Trigger::Changed) => "watch-changed",
+            Some(watch::Trigger::Matched) => "watch-matched",
+            None => return,
+        };
+        show_notification(self.i18n.tr_with(message, &[("x", x.into()), ("y", y.into()), ("color", hex.into())]));
+        if let Some(command) = &self.settings.watch_command {
+            run_pick_command(command, &ColorInfo::solid(color, (x, y)));
         }
+    }
 
-        if self.is_frozen() {
+    fn capture_tick_ms(&self) -> u16 {
+        if self.settings.low_power { LOW_POWER_TICK_MS } else { CAPTURE_TICK_MS }
+    }
+
+    fn create_record_section(&self, recorder: &recorder::Recorder) -> Element<'_, Message> {
+        Row::new()
+            .spacing(10)
+            .push(
+                text(self.i18n.tr_with(
+                    "recording",
+                    &[("count", recorder.samples.into()), ("path", recorder.path.display().to_string().into())],
+                ))
+                .size(self.text_size(12.0)),
+            )
+            .push(button(text(self.tr("stop")).size(self.text_size(12.0))).on_press(Message::RecordToggled(false)))
+            .into()
+    }
+
+    /// Records the frozen pixel, or the one under the cursor, when a sample
+    /// is due. A write failure ends the recording.
+    fn record_sample(&mut self, now: Instant) {
+        let position = self.picker.frozen().map_or_else(|| self.get_mouse_position(), |info| info.position);
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+        if !recorder.due(now) {
             return;
         }
 
-        self.capture_at_position(mouse_pos);
+        let color = capture_screen_region(position.0, position.1, 1, 1, &self.settings, &mut self.color_profiles)
+            .ok()
+            .and_then(|image| extract_color_at(&image, 0, 0));
+        self.report_profile_error();
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+        if let Some(color) = color
+            && let Err(source) = recorder.record(now, position, color)
+        {
+            let path = recorder.path.clone();
+            self.report(PeekerError::WriteFile { path, source });
+            self.stop_recording();
+        }
     }
 
-    fn get_active_color(&self) -> Option<&ColorInfo> {
-        self.frozen_color.as_ref().or(self.current_color.as_ref())
+    fn stop_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            let path = recorder.path.clone();
+            if let Err(source) = recorder.finish() {
+                self.report(PeekerError::WriteFile { path, source });
+            }
+        }
     }
 
-    fn get_display_position(&self) -> (i32, i32) {
-        self.get_active_color().map(|info| info.position).unwrap_or_else(|| self.get_mouse_position())
+    fn start_ruler(&mut self) {
+        let Some((Some(a), Some(b))) = self.measure.as_ref().map(|m| (m.a.as_ref(), m.b.as_ref())) else {
+            return;
+        };
+        let points = [a.position.0, a.position.1, b.position.0, b.position.1].map(|value| value.to_string());
+
+        // The overlay exits when its stdin closes, even if this process dies
+        // without stopping it.
+        let result = std::env::current_exe().and_then(|exe| {
+            std::process::Command::new(exe).arg("ruler").args(points).stdin(std::process::Stdio::piped()).spawn()
+        });
+        match result {
+            Ok(child) => self.ruler = Some(child),
+            Err(e) => self.report(PeekerError::Ruler(e)),
+        }
     }
 
-    fn is_frozen(&self) -> bool {
-        self.frozen_color.is_some()
+    fn stop_ruler(&mut self) {
+        if let Some(mut child) = self.ruler.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
     }
 
-    fn get_mouse_position(&self) -> (i32, i32) {
-        let mouse = self.input_state.device_state.get_mouse();
-        (mouse.coords.0, mouse.coords.1)
+    fn create_measure_point(&self, label: &str, point: Option<&ColorInfo>) -> Element<'_, Message> {
+        match point {
+            Some(info) => Row::new()
+                .spacing(5)
+                .push(text(format!("{}: ({}, {})", label, info.position.0, info.position.1)).size(self.text_size(12.0)))
+                .push(self.create_color_swatch(info.color))
+                .push(text(format_color(&info.color, &ColorFormat::Hex)).size(self.text_size(12.0)))
+                .into(),
+            None => text(self.i18n.tr_with("measure-point-unset", &[("name", label.into())]))
+                .size(self.text_size(12.0))
+                .into(),
+        }
     }
 
-    fn process_input(&mut self) -> InputEvent {
-        let keys = self.input_state.device_state.get_keys();
-        let space_pressed = keys.contains(&Keycode::Space);
-        let esc_pressed = keys.contains(&Keycode::Escape);
+    fn create_history_section(&self) -> Element<'_, Message> {
+        let mut pinned_row = Row::new().spacing(5);
+        let mut unpinned = Vec::new();
 
-        let just_pressed = space_pressed && !self.input_state.space_pressed_last_frame;
-        self.input_state.space_pressed_last_frame = space_pressed;
+        for index in self.filtered_history_indices() {
+            let entry = &self.color_history[index];
+            if entry.pinned {
+                pinned_row = pinned_row.push(self.create_history_swatch(index, entry));
+            } else {
+                unpinned.push(index);
+            }
+        }
+        let history_grid = self.create_history_groups(&unpinned);
+
+        let mut selection_actions = Row::new().spacing(5);
+        if let Some((index, entry)) =
+            self.selected_history.and_then(|index| self.color_history.get(index).map(|entry| (index, entry)))
+        {
+            selection_actions = selection_actions
+                .push(
+                    text_input(&self.tr("label"), entry.label.as_deref().unwrap_or(""))
+                        .on_input(Message::HistoryLabelChanged)
+                        .size(self.text_size(12.0))
+                        .width(Length::Fixed(180.0)),
+                )
+                .push(
+                    button(text(self.tr(if entry.pinned { "unpin" } else { "pin" })).size(self.text_size(12.0)))
+                        .on_press(Message::TogglePinned(index)),
+                );
+        }
+        selection_actions = selection_actions
+            .push(button(text(self.tr("clear")).size(self.text_size(12.0))).on_press(Message::ClearHistory))
+            .push(
+                toggler(self.settings.dedupe_history)
+                    .label(self.tr("no-duplicates"))
+                    .on_toggle(Message::DedupeHistoryToggled)
+                    .text_size(self.text_size(12.0)),
+            );
+
+        let mut actions = Row::new()
+            .spacing(5)
+            .push(
+                pick_list(
+                    self.choices(PaletteFormat::ALL, |format| self.tr(format.message_id())),
+                    Some(Choice::new(self.export_format, self.tr(self.export_format.message_id()))),
+                    |choice| Message::ExportFormatSelected(choice.value),
+                )
+                .text_size(self.text_size(12.0)),
+            )
+            .push(
+                button(text(self.tr("export")).size(self.text_size(12.0)))
+                    .on_press(Message::ExportPalette(self.export_format)),
+            )
+            .push(button(text(self.tr("copy-sheet")).size(self.text_size(12.0))).on_press(Message::CopyPaletteImage))
+            .push(
+                button(text(self.tr("copy-coolors-link")).size(self.text_size(12.0)))
+                    .on_press(Message::CopyCoolorsLink),
+            );
+        for (index, name) in self.plugins.exporters() {
+            actions = actions.push(
+                button(text(format!("{}…", name)).size(self.text_size(12.0)))
+                    .on_press(Message::ExportWithPlugin(index)),
+            );
+        }
 
-        if just_pressed {
-            InputEvent::Freeze
-        } else if esc_pressed {
-            InputEvent::Unfreeze
-        } else {
-            InputEvent::None
+        let mut section = Column::new().spacing(5);
+        if let Some(menu) = self.context_menu.and_then(|index| self.create_history_context_menu(index)) {
+            section = section.push(menu);
         }
-    }
+        if self.color_history.iter().any(|entry| entry.pinned) {
+            section = section.push(text(self.tr("pinned")).style(heading_text)).push(pinned_row.wrap());
+        }
+        section = section
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(text(self.tr("color-history")).style(heading_text))
+                    .push(
+                        text_input(&self.tr("history-filter"), &self.history_filter)
+                            .on_input(Message::HistoryFilterChanged)
+                            .size(self.text_size(12.0))
+                            .width(Length::Fixed(220.0)),
+                    )
+                    .push(text(self.tr("swatch-size")).size(self.text_size(12.0)))
+                    .push(
+                        iced::widget::slider(16.0..=48.0, self.settings.swatch_size, Message::SwatchSize)
+                            .step(2.0)
+                            .width(Length::Fixed(100.0)),
+                    )
+                    .push(
+                        pick_list(
+                            self.choices(&HistoryGrouping::ALL, |grouping| self.tr(grouping.message_id())),
+                            Some(Choice::new(
+                                self.settings.history_grouping,
+                                self.tr(self.settings.history_grouping.message_id()),
+                            )),
+                            |choice| Message::HistoryGroupingSelected(choice.value),
+                        )
+                        .text_size(self.text_size(12.0)),
+                    ),
+            )
+            .push(container(scrollable(history_grid).width(Length::Fill)).max_height(self.settings.swatch_size * 8.0))
+            .push(selection_actions)
+            .push(actions)
+            .push(self.create_import_row());
 
-    fn handle_freeze(&mut self, position: (i32, i32)) {
-        if self.is_frozen() {
-            self.frozen_color = None;
-            self.capture_at_position(position);
+        if self.export_format == PaletteFormat::Tailwind {
+            section = section.push(
+                toggler(self.export_options.tailwind_scales)
+                    .label(self.tr("tailwind-scales"))
+                    .on_toggle(Message::TailwindScalesToggled)
+                    .text_size(self.text_size(12.0)),
+            );
         }
 
-        if let Some(current) = &self.current_color {
-            self.frozen_color = Some(current.clone());
-            self.add_to_history(current.color);
-            self.save_settings_if_dirty();
+        if self.export_format.uses_variable_names() {
+            section = section.push(self.create_variable_name_editor());
         }
+
+        section.into()
     }
 
-    fn add_to_history(&mut self, color: Color) {
-        if self.color_history.last().copied() != Some(color) {
-            self.color_history.push(color);
-            if self.color_history.len() > MAX_COLOR_HISTORY {
-                self.color_history.remove(0);
-            }
-        }
+    /// The group key of the history entry at `index` under the chosen grouping.
+    fn history_group(&self, index: usize) -> String {
+        let metadata = &self.color_history[index].metadata;
+        history_groups::group_key(self.settings.history_grouping, metadata.picked_at, metadata.session, &chrono::Local)
     }
 
-    fn capture_at_position(&mut self, position: (i32, i32)) {
-        let (x, y) = position;
+    /// The history entries shown under the `key` group header: those the
+    /// filter matches that are not pinned, in history order.
+    fn history_group_members(&self, key: &str) -> Vec<usize> {
+        let mut members: Vec<usize> = self
+            .filtered_history_indices()
+            .into_iter()
+            .filter(|&index| !self.color_history[index].pinned && self.history_group(index) == key)
+            .collect();
+        members.sort_unstable();
+        members
+    }
 
-        if let Ok(monitors) = Monitor::all() {
-            for monitor in monitors {
-                if let Some(region) = self.calculate_capture_region(&monitor, x, y) {
-                    if let Ok(image) =
-                        monitor.capture_region(region.x as u32, region.y as u32, region.width, region.height)
-                    {
-                        let center_x = PREVIEW_SIZE / 2 - region.offset_x;
-                        let center_y = PREVIEW_SIZE / 2 - region.offset_y;
-
-                        if let Some(color) = extract_color_at(&image, center_x, center_y) {
-                            let preview = create_preview(&image, center_x, center_y);
-                            self.current_color = Some(ColorInfo { color, position, preview });
-                        }
-                        return;
-                    }
-                }
+    /// The unpinned history swatches, under a collapsible header per group
+    /// with its own export and clear actions when the history is grouped.
+    fn create_history_groups(&self, indices: &[usize]) -> Element<'_, Message> {
+        let swatches = |members: &[usize]| {
+            let mut row = Row::new().spacing(5);
+            for &index in members {
+                row = row.push(self.create_history_swatch(index, &self.color_history[index]));
             }
+            row.wrap()
+        };
+        if self.settings.history_grouping == HistoryGrouping::None {
+            return swatches(indices).into();
         }
-    }
-
-    fn calculate_capture_region(&self, monitor: &Monitor, x: i32, y: i32) -> Option<CaptureRegion> {
-        let bounds = MonitorBounds::from_monitor(monitor)?;
 
-        let half_size = (PREVIEW_SIZE / 2) as i32;
+        let mut column = Column::new().spacing(5);
+        for (key, members) in history_groups::group(indices, |index| self.history_group(index)) {
+            let collapsed = self.collapsed_groups.contains(&key);
+            let title = if key.is_empty() { self.tr("undated") } else { key.clone() };
+            let header = Row::new()
+                .spacing(5)
+                .align_y(iced::Alignment::Center)
+                .push(
+                    button(
+                        text(format!("{} {} ({})", if collapsed { "▸" } else { "▾" }, title, members.len()))
+                            .size(self.text_size(12.0)),
+                    )
+                    .style(button::text)
+                    .on_press(Message::HistoryGroupToggled(key.clone())),
+                )
+                .push(
+                    button(text(self.tr("export")).size(self.text_size(12.0)))
+                        .on_press(Message::ExportHistoryGroup(key.clone())),
+                )
+                .push(
+                    button(text(self.tr("clear")).size(self.text_size(12.0))).on_press(Message::ClearHistoryGroup(key)),
+                );
+            column = column.push(header);
+            if !collapsed {
+                column = column.push(swatches(&members));
+            }
+        }
+        column.into()
+    }
 
-        let region_x = x - half_size;
-        let region_y = y - half_size;
+    /// Indices of the history entries matching the filter box, in display
+    /// order; see [`history::filter`].
+    fn filtered_history_indices(&self) -> Vec<usize> {
+        history::filter(&self.color_history, &self.history_filter)
+    }
 
-        let clamped_x = region_x.max(bounds.x).min(bounds.x + bounds.width as i32 - PREVIEW_SIZE as i32);
-        let clamped_y = region_y.max(bounds.y).min(bounds.y + bounds.height as i32 - PREVIEW_SIZE as i32);
+    fn create_history_context_menu(&self, index: usize) -> Option<Element<'_, Message>> {
+        let entry = self.color_history.get(index)?;
+        let item = |label: String, message: Message| {
+            button(text(label).size(self.text_size(12.0))).on_press(message).width(Length::Fill)
+        };
 
-        let offset_x = (clamped_x - region_x).max(0) as u32;
-        let offset_y = (clamped_y - region_y).max(0) as u32;
+        let mut menu = Column::new().spacing(2).width(Length::Fixed(200.0));
+        for format in self.visible_formats() {
+            menu = menu.push(item(
+                self.i18n.tr_with("copy-as", &[("format", self.format_name(format).into())]),
+                Message::CopyHistoryColor(index, format),
+            ));
+        }
+        for (plugin, name, _) in self.plugins.formats(entry.color) {
+            menu = menu.push(item(
+                self.i18n.tr_with("copy-as", &[("format", name.into())]),
+                Message::CopyPluginFormat(plugin, entry.color),
+            ));
+        }
+        menu = menu
+            .push(item(self.tr("copy-as-image"), Message::CopyColorImage(entry.color)))
+            .push(item(self.tr(if entry.pinned { "unpin" } else { "pin" }), Message::TogglePinned(index)))
+            .push(item(self.tr("rename"), Message::RenameHistoryEntry(index)))
+            .push(item(self.tr("delete"), Message::DeleteHistoryEntry(index)))
+            .push(item(self.tr("set-reference"), Message::SetReferenceColor(index)))
+            .push(item(self.tr("close"), Message::CloseContextMenu));
 
-        Some(CaptureRegion {
-            x: clamped_x,
-            y: clamped_y,
-            width: PREVIEW_SIZE,
-            height: PREVIEW_SIZE,
-            offset_x,
-            offset_y,
-        })
+        Some(container(menu).padding(4).style(container::rounded_box).into())
     }
 
-    fn create_title(&self) -> Element<'_, Message> {
-        text("Pixel Peeker").size(20).color(Color::from_rgb(1.0, 1.0, 0.8)).into()
+    fn create_history_swatch(&self, index: usize, entry: &HistoryEntry) -> Element<'_, Message> {
+        let selected =
+            self.selected_history == Some(index) || self.keyboard_focus == Some(KeyboardFocus::HistorySwatch(index));
+        self.create_draggable_swatch(
+            SwatchSlot::History(index),
+            entry.color,
+            selected,
+            Some(entry.describe(&self.i18n)),
+        )
+        .on_right_press(Message::HistoryContextMenu(index))
+        .into()
     }
 
-    fn create_preview_row(&self, color_info: &ColorInfo) -> Element<'_, Message> {
-        let preview_canvas: Element<'_, Message> = if let Some(preview) = &color_info.preview {
-            Canvas::new(PreviewRenderer {
-                rgb_data: preview.rgb_data.clone(),
-                width: preview.width,
-                height: preview.height,
-                zoom_factor: self.zoom_factor,
-            })
-            .width(Length::Fixed(PREVIEW_CANVAS_SIZE))
-            .height(Length::Fixed(PREVIEW_CANVAS_SIZE))
-            .into()
-        } else {
-            Canvas::new(EmptyRenderer)
-                .width(Length::Fixed(PREVIEW_CANVAS_SIZE))
-                .height(Length::Fixed(PREVIEW_CANVAS_SIZE))
-                .into()
-        };
-
-        let preview_with_shadow: Element<'_, Message> = Container::new(preview_canvas)
-            .style(|_theme: &Theme| container::Style {
-                shadow: iced::Shadow {
-                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
-                    offset: iced::Vector::new(4.0, 4.0),
-                    blur_radius: 8.0,
+    /// A swatch that can be clicked, or dragged onto another swatch to
+    /// reorder it. A press and release on the same swatch counts as a click.
+    fn create_draggable_swatch(
+        &self,
+        slot: SwatchSlot,
+        color: Color,
+        selected: bool,
+        details: Option<String>,
+    ) -> MouseArea<'_, Message> {
+        let drop_target = self.drag.is_some_and(|drag| drag.source != slot && drag.hover == Some(slot));
+        let swatch = container(text("   "))
+            .style(move |theme: &Theme| container::Style {
+                background: Some(Background::Color(color)),
+                border: Border {
+                    color: if drop_target {
+                        Tone::Info.color(theme)
+                    } else if selected {
+                        focus_color(theme)
+                    } else {
+                        Tone::Muted.color(theme)
+                    },
+                    width: if selected || drop_target { 2.0 } else { 1.0 },
+                    radius: 3.0.into(),
                 },
-                border: Border { color: Color::from_rgb(0.3, 0.3, 0.3), width: 1.0, radius: 6.0.into() },
-                background: Some(Background::Color(Color::from_rgb(0.1, 0.1, 0.1))),
                 ..Default::default()
             })
-            .padding(4)
-            .into();
-
-        let zoom_slider = self.create_zoom_slider();
+            .width(Length::Fixed(self.settings.swatch_size))
+            .height(Length::Fixed(self.settings.swatch_size * 0.75));
 
-        let info_column = self.create_color_info_column(color_info);
+        let content: Element<'_, Message> = match details {
+            Some(details) => {
+                let details =
+                    container(text(details).size(self.text_size(12.0))).padding(6).style(container::rounded_box);
+                tooltip(swatch, details, tooltip::Position::Top).into()
+            },
+            None => swatch.into(),
+        };
 
-        Row::new().spacing(20).push(Column::new().push(preview_with_shadow).push(zoom_slider)).push(info_column).into()
+        mouse_area(content)
+            .on_press(Message::SwatchPressed(slot))
+            .on_enter(Message::SwatchHovered(slot))
+            .on_release(Message::SwatchReleased(slot))
     }
 
-    fn create_color_info_column(&self, color_info: &ColorInfo) -> Element<'_, Message> {
-        let mut column = Column::new()
+    fn create_palette_section(&self) -> Element<'_, Message> {
+        let active = self.palette_library.active();
+
+        let mut controls = Row::new()
             .spacing(5)
-            .push(text("Mouse Position:").color(Color::from_rgb(1.0, 1.0, 0.8)))
-            .push(text(format!("({}, {})", color_info.position.0, color_info.position.1)).size(14))
-            .push(text("Picked Color:").color(Color::from_rgb(1.0, 1.0, 0.8)))
-            .push(self.create_color_swatch(color_info.color));
+            .push(
+                pick_list(
+                    self.palette_library.names(),
+                    active.map(|palette| palette.name.clone()),
+                    Message::PaletteSelected,
+                )
+                .placeholder(self.tr("no-palette"))
+                .text_size(self.text_size(12.0)),
+            )
+            .push(button(text(self.tr("new")).size(self.text_size(12.0))).on_press(Message::NewPalette));
+
+        let shared = match &self.shared_palette {
+            Some(shared) => Row::new()
+                .spacing(5)
+                .align_y(iced::Alignment::Center)
+                .push(
+                    text(self.i18n.tr_with("shared-palette", &[("path", shared.path.display().to_string().into())]))
+                        .size(self.text_size(12.0)),
+                )
+                .push(
+                    button(text(self.tr("unlink")).size(self.text_size(12.0))).on_press(Message::UnlinkSharedPalette),
+                ),
+            None => Row::new().push(
+                button(text(self.tr("link-shared-palette")).size(self.text_size(12.0)))
+                    .on_press(Message::LinkSharedPalette),
+            ),
+        };
+
+        let Some(palette) = active else {
+            return Column::new()
+                .spacing(5)
+                .push(text(self.tr("palettes")).style(heading_text))
+                .push(controls)
+                .push(shared)
+                .into();
+        };
+
+        controls = controls
+            .push(
+                text_input(&self.tr("palette-name"), &self.palette_name_draft)
+                    .on_input(Message::PaletteNameDraftChanged)
+                    .on_submit(Message::RenamePalette)
+                    .size(self.text_size(12.0))
+                    .width(Length::Fixed(140.0)),
+            )
+            .push(button(text(self.tr("rename")).size(self.text_size(12.0))).on_press(Message::RenamePalette))
+            .push(button(text(self.tr("delete")).size(self.text_size(12.0))).on_press(Message::DeletePalette));
 
-        for format in [ColorFormat::Rgb, ColorFormat::Hex, ColorFormat::Hsv, ColorFormat::Hsl, ColorFormat::Oklch] {
-            column = column.push(self.create_color_row(&color_info.color, format));
+        let mut swatches = Row::new().spacing(5);
+        for (index, entry) in palette.colors.iter().enumerate() {
+            let selected = self.selected_palette_color == Some(index)
+                || self.keyboard_focus == Some(KeyboardFocus::PaletteSwatch(index));
+            swatches = swatches.push(self.create_draggable_swatch(
+                SwatchSlot::Palette(index),
+                Color::from(entry.clone()),
+                selected,
+                None,
+            ));
+        }
+        if self.drag.is_some_and(|drag| matches!(drag.source, SwatchSlot::History(_))) {
+            let end = SwatchSlot::Palette(palette.colors.len());
+            swatches = swatches.push(
+                mouse_area(
+                    container(text("+").size(self.text_size(12.0))).padding([0, 6]).style(container::rounded_box),
+                )
+                .on_enter(Message::SwatchHovered(end))
+                .on_release(Message::SwatchReleased(end)),
+            );
         }
 
-        column.into()
-    }
+        let mut color_actions = Row::new().spacing(5).push(
+            button(text(self.tr("add-current-color")).size(self.text_size(12.0)))
+                .on_press_maybe(self.get_active_color().map(|_| Message::AddActiveColorToPalette)),
+        );
+        if let Some(selected) = self.selected_palette_color.and_then(|index| palette.colors.get(index)) {
+            let targets: Vec<String> =
+                self.palette_library.names().into_iter().filter(|name| *name != palette.name).collect();
+            color_actions = color_actions
+                .push(
+                    pick_list(targets, None::<String>, Message::MovePaletteColor)
+                        .placeholder(self.tr("move-to"))
+                        .text_size(self.text_size(12.0)),
+                )
+                .push(
+                    button(text(self.tr("copy-as-image")).size(self.text_size(12.0)))
+                        .on_press(Message::CopyColorImage(Color::from(selected.clone()))),
+                )
+                .push(button(text(self.tr("remove")).size(self.text_size(12.0))).on_press(Message::RemovePaletteColor));
+        }
 
-    fn create_color_swatch(&self, color: Color) -> Element<'_, Message> {
-        container(text("   "))
-            .style(move |_theme: &Theme| container::Style {
-                background: Some(Background::Color(color)),
-                border: Border { color: Color::from_rgb(0.5, 0.5, 0.5), width: 1.0, radius: 4.0.into() },
-                ..Default::default()
-            })
-            .width(Length::Fixed(60.0))
-            .height(Length::Fixed(30.0))
+        Column::new()
+            .spacing(5)
+            .push(text(self.tr("palettes")).style(heading_text))
+            .push(controls)
+            .push(swatches)
+            .push(color_actions)
+            .push(shared)
             .into()
     }
 
-    fn create_color_row(&self, color: &Color, format: ColorFormat) -> Element<'_, Message> {
-        let label = format_color(color, &format);
-
+    fn create_import_row(&self) -> Element<'_, Message> {
         Row::new()
-            .spacing(10)
-            .push(text(label).width(Length::Fill))
-            .push(button("Copy").on_press(Message::CopyColor(format)))
+            .spacing(5)
+            .push(button(text(self.tr("import")).size(self.text_size(12.0))).on_press(Message::ImportPalette))
+            .push(
+                button(text(self.tr("paste-coolors-link")).size(self.text_size(12.0)))
+                    .on_press(Message::PasteCoolorsLink),
+            )
+            .push(
+                button(text(self.tr("export-history")).size(self.text_size(12.0)))
+                    .on_press_maybe((!self.color_history.is_empty()).then_some(Message::ExportHistory)),
+            )
             .into()
     }
 
-    fn create_zoom_slider(&self) -> Element<'_, Message> {
-        let zoom_ui = Column::new()
-            .spacing(10)
-            .push(iced::widget::Text::new(format!("Zoom: {:.1}×", self.zoom_factor)))
-            .push(iced::widget::slider(1.0..=5.0, self.zoom_factor, Message::ZoomFactor).step(0.1));
-        zoom_ui.into()
-    }
-
-    fn create_status_text(&self) -> Element<'_, Message> {
-        let (status_text, status_color) = if self.is_frozen() {
-            ("Frozen (press ESC to unfreeze)", Color::from_rgb(0.4, 0.7, 1.0))
-        } else {
-            ("Live (press SPACE to freeze)", Color::from_rgb(0.4, 1.0, 0.6))
-        };
-
-        text(status_text).color(status_color).into()
-    }
+    fn create_variable_name_editor(&self) -> Element<'_, Message> {
+        let mut editor = Column::new().spacing(3);
+        // Auto labels repeat often ("vivid red"), so show the names exports
+        // will actually use.
+        let swatches = export::unique_variables(
+            self.color_history.iter().enumerate().map(|(index, entry)| entry.swatch(index)).collect(),
+        );
 
-    fn create_history_section(&self) -> Element<'_, Message> {
-        let mut history_row = Row::new().spacing(5);
-
-        for &color in &self.color_history {
-            let color_button = button(text("   "))
-                .on_press(Message::HistoryColorClicked(color))
-                .style(move |_theme: &Theme, _status| button::Style {
-                    background: Some(Background::Color(color)),
-                    border: Border { color: Color::from_rgb(0.5, 0.5, 0.5), width: 1.0, radius: 3.0.into() },
-                    text_color: Color::BLACK,
-                    ..Default::default()
-                })
-                .width(Length::Fixed(24.0))
-                .height(Length::Fixed(18.0));
-            history_row = history_row.push(color_button);
+        for ((index, entry), swatch) in self.color_history.iter().enumerate().zip(&swatches) {
+            let name = entry.variable_name.as_deref().unwrap_or("");
+            let placeholder = &swatch.variable;
+            editor = editor.push(
+                Row::new().spacing(10).push(self.create_color_swatch(entry.color)).push(
+                    text_input(placeholder, name)
+                        .on_input(move |name| Message::VariableNameChanged(index, name))
+                        .size(self.text_size(12.0)),
+                ),
+            );
         }
 
-        Column::new().push(text("Color History:").color(Color::from_rgb(1.0, 1.0, 0.8))).push(history_row).into()
+        scrollable(editor).height(Length::Fixed(120.0)).into()
     }
 }
 
 #[derive(Debug)]
 enum InputEvent {
-    Freeze,
+    /// Freeze, keeping the color in this slot or else the next empty one.
+    Freeze(Option<usize>),
     Unfreeze,
+    TogglePause,
     None,
 }
 
@@ -600,12 +5597,13 @@ struct MonitorBounds {
 
 impl MonitorBounds {
     fn from_monitor(monitor: &Monitor) -> Option<Self> {
-        Some(Self {
-            x: monitor.x().ok()?,
-            y: monitor.y().ok()?,
-            width: monitor.width().ok()?,
-            height: monitor.height().ok()?,
-        })
+        let (width, height) =
+            capture::oriented_size(monitor.width().ok()?, monitor.height().ok()?, monitor.rotation().unwrap_or(0.0));
+        Some(Self { x: monitor.x().ok()?, y: monitor.y().ok()?, width, height })
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width as i32 && y < self.y + self.height as i32
     }
 }
 
@@ -615,11 +5613,68 @@ struct CaptureRegion {
     y: i32,
     width: u32,
     height: u32,
-    offset_x: u32,
-    offset_y: u32,
 }
 
-fn extract_color_at(image: &xcap::image::RgbaImage, x: u32, y: u32) -> Option<Color> {
+/// The `width` × `height` region centered on `(x, y)`, shifted to stay on the monitor.
+fn calculate_capture_region(bounds: &MonitorBounds, x: i32, y: i32, (width, height): (u32, u32)) -> CaptureRegion {
+    let max_x = bounds.x + bounds.width.saturating_sub(width) as i32;
+    let max_y = bounds.y + bounds.height.saturating_sub(height) as i32;
+    CaptureRegion {
+        x: (x - (width / 2) as i32).clamp(bounds.x, max_x),
+        y: (y - (height / 2) as i32).clamp(bounds.y, max_y),
+        width: width.min(bounds.width),
+        height: height.min(bounds.height),
+    }
+}
+
+/// The preview-sized capture around a position, as the framebuffer holds it.
+struct ScreenCapture {
+    image: xcap::image::RgbaImage,
+    /// Where the requested position is in `image`.
+    center: (u32, u32),
+    monitor: Option<String>,
+}
+
+/// Captures the preview around `position` from the monitor containing it,
+/// or from the monitor named `preferred`, where positions off it are moved
+/// to its nearest edge.
+fn capture_screen_at(
+    position: (i32, i32),
+    preferred: Option<&str>,
+    size: (u32, u32),
+) -> Result<ScreenCapture, PeekerError> {
+    let (x, y) = position;
+    let monitors = Monitor::all()?;
+    let preferred = preferred.and_then(|name| monitors.iter().find(|monitor| monitor.name().is_ok_and(|n| n == name)));
+    let (monitor, bounds) = match preferred {
+        Some(monitor) => (monitor, MonitorBounds::from_monitor(monitor).ok_or(PeekerError::OffScreen { x, y })?),
+        None => monitors
+            .iter()
+            .find_map(|monitor| {
+                let bounds = MonitorBounds::from_monitor(monitor).filter(|bounds| bounds.contains(x, y))?;
+                Some((monitor, bounds))
+            })
+            .ok_or(PeekerError::OffScreen { x, y })?,
+    };
+    let x = x.clamp(bounds.x, bounds.x + bounds.width.max(1) as i32 - 1);
+    let y = y.clamp(bounds.y, bounds.y + bounds.height.max(1) as i32 - 1);
+
+    let region = calculate_capture_region(&bounds, x, y, size);
+    let image = capture::capture_region(
+        monitor,
+        (region.x - bounds.x) as u32,
+        (region.y - bounds.y) as u32,
+        region.width,
+        region.height,
+    )?;
+    let center = ((x - region.x) as u32, (y - region.y) as u32);
+    if center.0 >= image.width() || center.1 >= image.height() {
+        return Err(PeekerError::Capture("The captured region is smaller than requested".into()));
+    }
+    Ok(ScreenCapture { image, center, monitor: monitor.name().ok() })
+}
+
+pub(crate) fn extract_color_at(image: &xcap::image::RgbaImage, x: u32, y: u32) -> Option<Color> {
     if x < image.width() && y < image.height() {
         let pixel = image.get_pixel(x, y);
         Some(Color::from_rgb(pixel[0] as f32 / 255.0, pixel[1] as f32 / 255.0, pixel[2] as f32 / 255.0))
@@ -628,12 +5683,17 @@ fn extract_color_at(image: &xcap::image::RgbaImage, x: u32, y: u32) -> Option<Co
     }
 }
 
-fn create_preview(image: &xcap::image::RgbaImage, center_x: u32, center_y: u32) -> Option<PreviewData> {
-    let half_size = (PREVIEW_SIZE / 2) as i32;
-    let mut rgb_data = Vec::with_capacity((PREVIEW_SIZE * PREVIEW_SIZE * 3) as usize);
+fn create_preview(
+    image: &xcap::image::RgbaImage,
+    center_x: u32,
+    center_y: u32,
+    (width, height): (u32, u32),
+) -> Option<PreviewData> {
+    let (half_width, half_height) = ((width / 2) as i32, (height / 2) as i32);
+    let mut rgb_data = Vec::with_capacity((width * height * 3) as usize);
 
-    for dy in -half_size..=half_size {
-        for dx in -half_size..=half_size {
+    for dy in -half_height..=half_height {
+        for dx in -half_width..=half_width {
             let sample_x = center_x as i32 + dx;
             let sample_y = center_y as i32 + dy;
 
@@ -652,40 +5712,314 @@ fn create_preview(image: &xcap::image::RgbaImage, center_x: u32, center_y: u32)
         }
     }
 
-    Some(PreviewData { rgb_data, width: PREVIEW_SIZE, height: PREVIEW_SIZE })
+    Some(PreviewData { rgb_data, width, height, crosshair: None, image: Default::default() })
 }
 
-fn format_color(color: &Color, format: &ColorFormat) -> String {
-    let r = (color.r * 255.0).round() as u8;
-    let g = (color.g * 255.0).round() as u8;
-    let b = (color.b * 255.0).round() as u8;
+/// Converts `image`, captured from `monitor`, to sRGB when captures are
+/// color-managed and the monitor has a profile.
+pub(crate) fn color_manage(
+    image: &mut xcap::image::RgbaImage,
+    monitor: &Monitor,
+    settings: &Settings,
+    profiles: &mut icc::Profiles,
+) {
+    if let Ok(name) = monitor.name() {
+        color_manage_on(image, &name, settings, profiles);
+    }
+}
 
-    match format {
-        ColorFormat::Rgb => format!("rgb({}, {}, {})", r, g, b),
-        ColorFormat::Hex => format!("#{:02X}{:02X}{:02X}", r, g, b),
-        ColorFormat::Hsv => {
-            let hsv: Hsv = Srgb::new(color.r, color.g, color.b).into_color();
-            format!(
-                "hsv({:.0}deg, {:.0}%, {:.0}%)",
-                hsv.hue.into_positive_degrees(),
-                hsv.saturation * 100.0,
-                hsv.value * 100.0
-            )
+/// [`color_manage`] for a capture from the monitor called `monitor`.
+fn color_manage_on(
+    image: &mut xcap::image::RgbaImage,
+    monitor: &str,
+    settings: &Settings,
+    profiles: &mut icc::Profiles,
+) {
+    if !settings.color_managed {
+        return;
+    }
+    if let Some(profile) = profiles.get(monitor, &settings.monitor_profiles) {
+        profile.convert_image(image);
+    }
+}
+
+/// Captures a rectangle given in global coordinates from the monitor that
+/// contains its top-left corner, color-managed like live sampling.
+pub(crate) fn capture_screen_region(
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    settings: &Settings,
+    profiles: &mut icc::Profiles,
+) -> Result<xcap::image::RgbaImage, PeekerError> {
+    let monitor = Monitor::from_point(x, y)?;
+    let bounds = MonitorBounds::from_monitor(&monitor)
+        .ok_or_else(|| PeekerError::Capture("Could not read monitor bounds".into()))?;
+    let (left, top) = ((x - bounds.x) as u32, (y - bounds.y) as u32);
+    let width = width.min(bounds.width.saturating_sub(left));
+    let height = height.min(bounds.height.saturating_sub(top));
+    if width == 0 || height == 0 {
+        return Err(PeekerError::OffScreen { x, y });
+    }
+    let mut image = capture::capture_region(&monitor, left, top, width, height)?;
+    color_manage(&mut image, &monitor, settings, profiles);
+    Ok(image)
+}
+
+/// Colors at `count` evenly spaced points from `a` to `b`, read from a single
+/// capture of the line's bounding box. Points off the monitor containing the
+/// box's top-left corner are skipped.
+fn sample_line(
+    a: (i32, i32),
+    b: (i32, i32),
+    count: usize,
+    settings: &Settings,
+    profiles: &mut icc::Profiles,
+) -> Result<Vec<Color>, PeekerError> {
+    let (left, top) = (a.0.min(b.0), a.1.min(b.1));
+    let (width, height) = (a.0.abs_diff(b.0) + 1, a.1.abs_diff(b.1) + 1);
+    let image = capture_screen_region(left, top, width, height, settings, profiles)?;
+
+    Ok(measure::points_along(a, b, count)
+        .into_iter()
+        .filter_map(|(x, y)| extract_color_at(&image, (x - left) as u32, (y - top) as u32))
+        .collect())
+}
+
+/// Captures up to `radius` pixels on each side of `position`, clamped to the
+/// monitor containing it, with the name of that monitor. The capture is not
+/// color-managed.
+fn capture_around(position: (i32, i32), radius: i32) -> Result<(Surroundings, Option<String>), PeekerError> {
+    let (x, y) = position;
+    let monitor = Monitor::from_point(x, y)?;
+    let bounds = MonitorBounds::from_monitor(&monitor)
+        .ok_or_else(|| PeekerError::Capture("Could not read monitor bounds".into()))?;
+
+    let left = (x - radius).max(bounds.x);
+    let top = (y - radius).max(bounds.y);
+    let right = (x + radius + 1).min(bounds.x + bounds.width as i32);
+    let bottom = (y + radius + 1).min(bounds.y + bounds.height as i32);
+    if right <= left || bottom <= top {
+        return Err(PeekerError::OffScreen { x, y });
+    }
+
+    let image = capture::capture_region(
+        &monitor,
+        (left - bounds.x) as u32,
+        (top - bounds.y) as u32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    )?;
+    let start = ((x - left) as u32, (y - top) as u32);
+    Ok((Surroundings { position, image, start }, monitor.name().ok()))
+}
+
+/// Title of the topmost visible window under the given screen position,
+/// ignoring the picker's own window.
+fn window_title_at(x: i32, y: i32) -> Option<String> {
+    let windows = xcap::Window::all().ok()?;
+    windows.into_iter().filter(|window| !window.is_minimized().unwrap_or(true)).find_map(|window| {
+        let (wx, wy) = (window.x().ok()?, window.y().ok()?);
+        let (width, height) = (window.width().ok()? as i32, window.height().ok()? as i32);
+        let title = window.title().ok()?;
+        let contains = x >= wx && y >= wy && x < wx + width && y < wy + height;
+        (contains && !title.is_empty() && title != "Pixel Peeker").then_some(title)
+    })
+}
+
+/// Shows the notification from a background thread, since some platforms
+/// block until the notification server answers.
+fn show_notification(body: String) {
+    std::thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new().summary("Pixel Peeker").body(&body).show() {
+            eprintln!("Failed to show notification: {}", e);
+        }
+    });
+}
+
+/// Opens `url` in the default browser without waiting for it.
+fn open_url(url: &str) {
+    let mut process = if cfg!(windows) {
+        let mut process = std::process::Command::new("cmd");
+        process.args(["/C", "start", "", url]);
+        process
+    } else if cfg!(target_os = "macos") {
+        let mut process = std::process::Command::new("open");
+        process.arg(url);
+        process
+    } else {
+        let mut process = std::process::Command::new("xdg-open");
+        process.arg(url);
+        process
+    };
+    match process.spawn() {
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
         },
-        ColorFormat::Hsl => {
-            let hsl: Hsl = Srgb::new(color.r, color.g, color.b).into_color();
-            format!(
-                "hsl({:.0}deg, {:.0}%, {:.0}%)",
-                hsl.hue.into_positive_degrees(),
-                hsl.saturation * 100.0,
-                hsl.lightness * 100.0
-            )
+        Err(e) => eprintln!("Failed to open {}: {}", url, e),
+    }
+}
+
+/// Runs the user's pick hook through the platform shell without waiting for
+/// it, exposing the color as `PP_HEX`, `PP_R`, `PP_G`, `PP_B` and the screen
+/// position as `PP_X`, `PP_Y`.
+fn run_pick_command(command: &str, info: &ColorInfo) {
+    let [r, g, b, _] = info.color.into_rgba8();
+    let mut process = if cfg!(windows) {
+        let mut process = std::process::Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = std::process::Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+    process
+        .env("PP_HEX", format_color(&info.color, &ColorFormat::Hex))
+        .env("PP_R", r.to_string())
+        .env("PP_G", g.to_string())
+        .env("PP_B", b.to_string())
+        .env("PP_X", info.position.0.to_string())
+        .env("PP_Y", info.position.1.to_string());
+
+    match process.spawn() {
+        Ok(mut child) => {
+            // Reap the child so finished hooks don't linger as zombies.
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        },
+        Err(e) => eprintln!("Failed to run pick command '{}': {}", command, e),
+    }
+}
+
+/// Plays a short, quiet tone on the default output device. The stream has to
+/// outlive the sound, so it is opened and held on its own thread.
+fn play_click() {
+    use rodio::Source;
+
+    std::thread::spawn(|| {
+        let result = rodio::OutputStream::try_default().map_err(|e| e.to_string()).and_then(|(_stream, handle)| {
+            let sink = rodio::Sink::try_new(&handle).map_err(|e| e.to_string())?;
+            sink.append(
+                rodio::source::SineWave::new(1200.0).take_duration(std::time::Duration::from_millis(30)).amplify(0.15),
+            );
+            sink.sleep_until_end();
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to play feedback sound: {}", e);
+        }
+    });
+}
+
+/// Opens or closes the window in `slot`, doing nothing if it is already in
+/// the requested state.
+fn toggle_window(
+    slot: &mut Option<window::Id>,
+    show: bool,
+    settings: impl FnOnce() -> window::Settings,
+) -> Task<Message> {
+    match (*slot, show) {
+        (None, true) => {
+            let (id, open) = window::open(settings());
+            *slot = Some(id);
+            open.discard()
         },
-        ColorFormat::Oklch => {
-            let oklch: Oklch = Srgb::new(color.r, color.g, color.b).into_color();
-            format!("oklch({:.2} {:.2} {:.1}deg)", oklch.l, oklch.chroma, oklch.hue.into_positive_degrees())
+        (Some(id), false) => {
+            *slot = None;
+            window::close(id)
         },
+        _ => Task::none(),
+    }
+}
+
+/// Fills a secondary window with the theme background.
+fn window_background<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(20)
+        .style(|theme: &Theme| container::Style {
+            background: Some(Background::Color(theme.palette().background)),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// Section labels: pale yellow on dark themes, dark amber on light ones.
+fn heading_text(theme: &Theme) -> text::Style {
+    let color = if theme.extended_palette().is_dark {
+        Color::from_rgb(1.0, 1.0, 0.8)
+    } else {
+        Color::from_rgb(0.45, 0.35, 0.0)
+    };
+    text::Style { color: Some(color) }
+}
+
+/// Verdict and status colors, each a lighter shade on dark themes and a
+/// darker one on light themes so it stays readable on both.
+#[derive(Debug, Clone, Copy)]
+enum Tone {
+    Good,
+    Caution,
+    Bad,
+    Info,
+    Muted,
+}
+
+impl Tone {
+    fn color(self, theme: &Theme) -> Color {
+        let dark = theme.extended_palette().is_dark;
+        match (self, dark) {
+            (Tone::Good, true) => Color::from_rgb(0.4, 1.0, 0.6),
+            (Tone::Good, false) => Color::from_rgb(0.0, 0.45, 0.2),
+            (Tone::Caution, true) => Color::from_rgb(1.0, 0.85, 0.3),
+            (Tone::Caution, false) => Color::from_rgb(0.6, 0.4, 0.0),
+            (Tone::Bad, true) => Color::from_rgb(1.0, 0.4, 0.4),
+            (Tone::Bad, false) => Color::from_rgb(0.75, 0.1, 0.1),
+            (Tone::Info, true) => Color::from_rgb(0.4, 0.7, 1.0),
+            (Tone::Info, false) => Color::from_rgb(0.1, 0.35, 0.7),
+            (Tone::Muted, true) => Color::from_rgb(0.6, 0.6, 0.6),
+            (Tone::Muted, false) => Color::from_rgb(0.4, 0.4, 0.4),
+        }
+    }
+
+    /// The tone's text style, unless the user picked `custom` instead.
+    fn style(self, theme: &Theme, custom: Option<Color>) -> text::Style {
+        text::Style { color: Some(custom.unwrap_or_else(|| self.color(theme))) }
+    }
+}
+
+fn system_prefers_dark() -> bool {
+    !matches!(dark_light::detect(), Ok(dark_light::Mode::Light))
+}
+
+fn read_clipboard_image() -> Result<image::RgbaImage, PeekerError> {
+    let data = arboard::Clipboard::new()?.get_image()?;
+    if data.width == 0 || data.height == 0 {
+        return Err(PeekerError::Clipboard("The clipboard image is empty".into()));
     }
+    image::RgbaImage::from_raw(data.width as u32, data.height as u32, data.bytes.into_owned())
+        .ok_or_else(|| PeekerError::Clipboard("The clipboard image has an unexpected size".into()))
+}
+
+fn default_variable_name(index: usize) -> String {
+    format!("color-{}", index + 1)
+}
+
+/// Lowercases `value` and joins its alphanumeric runs with `-`, so a label
+/// like "Primary button (hover)" becomes `primary-button-hover`.
+fn slugify(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
 }
 
 struct PreviewRenderer {
@@ -693,6 +6027,41 @@ struct PreviewRenderer {
     width: u32,
     height: u32,
     zoom_factor: f32,
+    anchor: ZoomAnchor,
+    crosshair: (u32, u32),
+    /// Only draw the cells inside the circle around the canvas center.
+    circular: bool,
+    /// Draw the grid style as this image, linearly filtered.
+    smooth: Option<iced::widget::image::Handle>,
+}
+
+/// The size of a preview cell and the offset of the grid within `canvas`.
+/// The unzoomed grid is centered; zooming keeps whatever is at `anchor` there.
+fn preview_layout(canvas: Size, width: u32, height: u32, zoom_factor: f32, anchor: Point) -> (f32, Point) {
+    let base = (canvas.width / width as f32).min(canvas.height / height as f32);
+    let unzoomed = Point::new((canvas.width - width as f32 * base) / 2.0, (canvas.height - height as f32 * base) / 2.0);
+    let cell_size = base * zoom_factor;
+    let offset =
+        Point::new(anchor.x - (anchor.x - unzoomed.x) * zoom_factor, anchor.y - (anchor.y - unzoomed.y) * zoom_factor);
+    (cell_size, offset)
+}
+
+/// The size of the preview canvas in the main window: as tall as
+/// `PREVIEW_CANVAS_SIZE` and as wide as the preview's aspect needs.
+fn preview_canvas_size(width: u32, height: u32) -> Size {
+    let cell_size = PREVIEW_CANVAS_SIZE / PREVIEW_SIZE as f32;
+    Size::new(width as f32 * cell_size, height as f32 * cell_size)
+}
+
+/// The preview cell under `point` on the main window's preview canvas.
+fn preview_cell_at(point: Point, preview: &PreviewData, zoom_factor: f32, anchor: ZoomAnchor) -> Option<(u32, u32)> {
+    let canvas = preview_canvas_size(preview.width, preview.height);
+    let anchor = anchor.point(canvas, preview.width, preview.height, preview.crosshair(), Some(point));
+    let (cell_size, offset) = preview_layout(canvas, preview.width, preview.height, zoom_factor, anchor);
+    let x = ((point.x - offset.x) / cell_size).floor();
+    let y = ((point.y - offset.y) / cell_size).floor();
+    let inside = |value: f32, len: u32| value >= 0.0 && value < len as f32;
+    (inside(x, preview.width) && inside(y, preview.height)).then_some((x as u32, y as u32))
 }
 
 impl<Message> canvas::Program<Message> for PreviewRenderer {
@@ -704,18 +6073,33 @@ impl<Message> canvas::Program<Message> for PreviewRenderer {
         renderer: &Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<iced::widget::canvas::Geometry> {
         let mut frame = iced::widget::canvas::Frame::new(renderer, bounds.size());
 
-        let base_cell_size = bounds.width / self.width as f32;
-        let zoomed_cell_size = base_cell_size * self.zoom_factor;
-
-        let total_grid_width = self.width as f32 * zoomed_cell_size;
-        let total_grid_height = self.height as f32 * zoomed_cell_size;
+        let anchor =
+            self.anchor.point(bounds.size(), self.width, self.height, self.crosshair, cursor.position_in(bounds));
+        let (zoomed_cell_size, offset) =
+            preview_layout(bounds.size(), self.width, self.height, self.zoom_factor, anchor);
+        let (offset_x, offset_y) = (offset.x, offset.y);
+        let center = Point::new(bounds.width / 2.0, bounds.height / 2.0);
+        let radius = bounds.width.min(bounds.height) / 2.0;
 
-        let offset_x = (bounds.width - total_grid_width) / 2.0;
-        let offset_y = (bounds.height - total_grid_height) / 2.0;
+        // The circular mask works cell by cell, so it always draws blocks.
+        if let Some(handle) = self.smooth.clone().filter(|_| !self.circular) {
+            let grid = Size::new(self.width as f32 * zoomed_cell_size, self.height as f32 * zoomed_cell_size);
+            frame.draw_image(
+                Rectangle::new(offset, grid),
+                iced::widget::canvas::Image::new(handle).filter_method(iced::widget::image::FilterMethod::Linear),
+            );
+            let (x, y) = self.crosshair;
+            let cell = Rectangle::new(
+                Point::new(offset_x + x as f32 * zoomed_cell_size, offset_y + y as f32 * zoomed_cell_size),
+                Size::new(zoomed_cell_size, zoomed_cell_size),
+            );
+            self.draw_crosshair(&mut frame, cell, zoomed_cell_size);
+            return vec![frame.into_geometry()];
+        }
 
         for y in 0..self.height {
             for x in 0..self.width {
@@ -731,16 +6115,25 @@ impl<Message> canvas::Program<Message> for PreviewRenderer {
                         Point::new(offset_x + x as f32 * zoomed_cell_size, offset_y + y as f32 * zoomed_cell_size),
                         Size::new(zoomed_cell_size, zoomed_cell_size),
                     );
+                    if self.circular && cell_rect.center().distance(center) > radius {
+                        continue;
+                    }
 
                     frame.fill_rectangle(cell_rect.position(), cell_rect.size(), color);
 
-                    if x == self.width / 2 && y == self.height / 2 {
+                    if (x, y) == self.crosshair {
                         self.draw_crosshair(&mut frame, cell_rect, zoomed_cell_size);
                     }
                 }
             }
         }
 
+        if self.circular {
+            let rim =
+                iced::widget::canvas::Stroke::default().with_color(Color::from_rgb(0.5, 0.5, 0.5)).with_width(2.0);
+            frame.stroke(&iced::widget::canvas::Path::circle(center, radius - 1.0), rim);
+        }
+
         vec![frame.into_geometry()]
     }
 }
@@ -790,6 +6183,97 @@ impl PreviewRenderer {
     }
 }
 
+/// Snapshot B with unchanged pixels dimmed and changed ones outlined in the
+/// theme's red; the hovered pixel's ΔE is shown in the corner.
+struct DiffRenderer {
+    rgb_data: Vec<u8>,
+    diff: diff::PreviewDiff,
+}
+
+impl<Message> canvas::Program<Message> for DiffRenderer {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<iced::widget::canvas::Geometry> {
+        let mut frame = iced::widget::canvas::Frame::new(renderer, bounds.size());
+        let cell_size = bounds.width / self.diff.width as f32;
+        let outline = iced::widget::canvas::Stroke::default().with_color(Tone::Bad.color(theme)).with_width(1.0);
+
+        for (index, pixel) in self.rgb_data.chunks_exact(3).enumerate() {
+            let (x, y) = (index as u32 % self.diff.width, index as u32 / self.diff.width);
+            let position = Point::new(x as f32 * cell_size, y as f32 * cell_size);
+            let cell = Size::new(cell_size, cell_size);
+            let color = Color::from_rgb8(pixel[0], pixel[1], pixel[2]);
+
+            if self.diff.at(x, y).is_some_and(|delta| delta > diff::CHANGE_THRESHOLD) {
+                frame.fill_rectangle(position, cell, color);
+                frame.stroke(&iced::widget::canvas::Path::rectangle(position, cell), outline);
+            } else {
+                frame.fill_rectangle(position, cell, Color { a: 0.25, ..color });
+            }
+        }
+
+        if let Some(hover) = cursor.position_in(bounds) {
+            let (x, y) = ((hover.x / cell_size) as u32, (hover.y / cell_size) as u32);
+            if let Some(delta) = self.diff.at(x, y) {
+                frame.fill_text(iced::widget::canvas::Text {
+                    content: format!("ΔE {:.2}", delta),
+                    position: Point::new(4.0, 4.0),
+                    color: Color::WHITE,
+                    size: 12.0.into(),
+                    ..iced::widget::canvas::Text::default()
+                });
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Two colors meeting on the diagonal, so a subtle mismatch shows along
+/// the edge: `first` in the upper left, `second` in the lower right.
+struct SplitSwatchRenderer {
+    first: Color,
+    second: Color,
+}
+
+impl<Message> canvas::Program<Message> for SplitSwatchRenderer {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<iced::widget::canvas::Geometry> {
+        let mut frame = iced::widget::canvas::Frame::new(renderer, bounds.size());
+        let (width, height) = (bounds.width, bounds.height);
+        let triangle = |corner: Point| {
+            iced::widget::canvas::Path::new(|path| {
+                path.move_to(Point::new(width, 0.0));
+                path.line_to(corner);
+                path.line_to(Point::new(0.0, height));
+                path.close();
+            })
+        };
+        frame.fill(&triangle(Point::ORIGIN), self.first);
+        frame.fill(&triangle(Point::new(width, height)), self.second);
+        frame.stroke(
+            &iced::widget::canvas::Path::rectangle(Point::ORIGIN, bounds.size()),
+            iced::widget::canvas::Stroke::default().with_color(Color::from_rgb(0.5, 0.5, 0.5)).with_width(1.0),
+        );
+        vec![frame.into_geometry()]
+    }
+}
+
 struct EmptyRenderer;
 
 impl<Message> canvas::Program<Message> for EmptyRenderer {
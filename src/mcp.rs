@@ -0,0 +1,234 @@
+use crate::error::PeekerError;
+use crate::export::{self, HistoryFormat};
+use crate::{HistoryEntry, Settings, capture_screen_region, history_store, icc};
+use iced::Color;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Largest region `get_screen_region_colors` will capture, per side.
+const MAX_REGION_SIZE: u32 = 2048;
+const DEFAULT_MAX_COLORS: usize = 16;
+
+/// Serves the Model Context Protocol over stdin/stdout: one JSON-RPC message
+/// per line in, one response per line out. Runs headless, without the UI.
+pub fn run() -> Result<(), PeekerError> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(request) => handle(&request),
+            Err(e) => Some(error_response(Value::Null, -32700, &format!("Parse error: {}", e))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answers one JSON-RPC message; notifications (no `id`) get no response.
+fn handle(request: &Value) -> Option<Value> {
+    let id = request.get("id")?.clone();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match request.get("method").and_then(Value::as_str) {
+        Some("initialize") => Ok(json!({
+            "protocolVersion": params.get("protocolVersion").and_then(Value::as_str).unwrap_or(PROTOCOL_VERSION),
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "pixel-peeker", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        Some("ping") => Ok(json!({})),
+        Some("tools/list") => Ok(json!({ "tools": tool_definitions() })),
+        Some("tools/call") => Ok(call_tool(&params)),
+        Some(method) => Err((-32601, format!("Method not found: {}", method))),
+        None => Err((-32600, "Invalid request".to_string())),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tool_definitions() -> Value {
+    let integer = json!({ "type": "integer" });
+    json!([
+        {
+            "name": "pick_color_at",
+            "description": "Color of the screen pixel at the given global coordinates.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "x": integer, "y": integer },
+                "required": ["x", "y"],
+            },
+        },
+        {
+            "name": "get_screen_region_colors",
+            "description": "Most common colors in a screen rectangle, with pixel counts.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "x": integer,
+                    "y": integer,
+                    "width": integer,
+                    "height": integer,
+                    "max_colors": integer,
+                },
+                "required": ["x", "y", "width", "height"],
+            },
+        },
+        {
+            "name": "get_history",
+            "description": "The picker's saved color history for the active workspace.",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+    ])
+}
+
+/// Runs a tool and wraps its JSON output as MCP text content. Tool failures
+/// are reported in the result rather than as protocol errors.
+fn call_tool(params: &Value) -> Value {
+    let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+    let output = match params.get("name").and_then(Value::as_str) {
+        Some("pick_color_at") => pick_color_at(&arguments),
+        Some("get_screen_region_colors") => get_screen_region_colors(&arguments),
+        Some("get_history") => get_history(),
+        Some(name) => Err(PeekerError::InvalidInput(format!("Unknown tool: {}", name))),
+        None => Err(PeekerError::InvalidInput("Missing tool name".to_string())),
+    };
+
+    match output {
+        Ok(value) => json!({ "content": [{ "type": "text", "text": value.to_string() }], "isError": false }),
+        Err(e) => json!({ "content": [{ "type": "text", "text": e.to_string() }], "isError": true }),
+    }
+}
+
+fn pick_color_at(arguments: &Value) -> Result<Value, PeekerError> {
+    let (x, y) = (integer_argument(arguments, "x")?, integer_argument(arguments, "y")?);
+    let image = capture_managed(x as i32, y as i32, 1, 1)?;
+    let pixel = image.get_pixel(0, 0);
+    Ok(color_json(Color::from_rgb8(pixel[0], pixel[1], pixel[2]), json!({ "x": x, "y": y })))
+}
+
+fn get_screen_region_colors(arguments: &Value) -> Result<Value, PeekerError> {
+    let (x, y) = (integer_argument(arguments, "x")?, integer_argument(arguments, "y")?);
+    let (width, height) = (integer_argument(arguments, "width")?, integer_argument(arguments, "height")?);
+    let max_colors = arguments.get("max_colors").and_then(Value::as_u64).map_or(DEFAULT_MAX_COLORS, |max| max as usize);
+    if width <= 0 || height <= 0 || width > MAX_REGION_SIZE as i64 || height > MAX_REGION_SIZE as i64 {
+        return Err(PeekerError::InvalidInput(format!("width and height must be between 1 and {}", MAX_REGION_SIZE)));
+    }
+
+    let image = capture_managed(x as i32, y as i32, width as u32, height as u32)?;
+    let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+    for pixel in image.pixels() {
+        *counts.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+    }
+
+    let total = (image.width() * image.height()) as f64;
+    let mut counts: Vec<([u8; 3], usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let colors: Vec<Value> = counts
+        .into_iter()
+        .take(max_colors)
+        .map(|([r, g, b], count)| {
+            color_json(Color::from_rgb8(r, g, b), json!({ "count": count, "fraction": count as f64 / total }))
+        })
+        .collect();
+
+    Ok(json!({ "x": x, "y": y, "width": width, "height": height, "colors": colors }))
+}
+
+/// [`capture_screen_region`] with the app's color management settings. A
+/// monitor profile that cannot be used is reported on stderr.
+fn capture_managed(x: i32, y: i32, width: u32, height: u32) -> Result<xcap::image::RgbaImage, PeekerError> {
+    let mut profiles = icc::Profiles::default();
+    let image = capture_screen_region(x, y, width, height, &Settings::load(), &mut profiles);
+    if let Some(e) = profiles.take_error() {
+        eprintln!("{}", e);
+    }
+    image
+}
+
+fn get_history() -> Result<Value, PeekerError> {
+    let settings = Settings::load();
+    let stored = history_store::load(settings.workspace.as_deref(), &settings.color_history);
+    let entries: Vec<HistoryEntry> = stored.into_iter().map(HistoryEntry::from).collect();
+    serde_json::from_slice(&export::export_history(HistoryFormat::Json, &entries)?)
+        .map_err(|e| PeekerError::Export(e.to_string()))
+}
+
+fn integer_argument(arguments: &Value, name: &str) -> Result<i64, PeekerError> {
+    arguments
+        .get(name)
+        .and_then(Value::as_i64)
+        .ok_or_else(|| PeekerError::InvalidInput(format!("Missing integer argument '{}'", name)))
+}
+
+/// `{hex, r, g, b}` merged with `extra`.
+fn color_json(color: Color, extra: Value) -> Value {
+    let [r, g, b, _] = color.into_rgba8();
+    let mut value = json!({ "hex": export::to_hex(color), "r": r, "g": g, "b": b });
+    if let (Some(object), Value::Object(extra)) = (value.as_object_mut(), extra) {
+        object.extend(extra);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn notifications_get_no_response() {
+        assert_eq!(handle(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })), None);
+    }
+
+    #[test]
+    fn initialize_echoes_protocol_version() {
+        let response = handle(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-03-26" },
+        }))
+        .unwrap();
+
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "pixel-peeker");
+    }
+
+    #[test]
+    fn unknown_tools_are_tool_errors() {
+        let response = handle(&json!({
+            "jsonrpc": "2.0",
+            "id": "a",
+            "method": "tools/call",
+            "params": { "name": "nope", "arguments": {} },
+        }))
+        .unwrap();
+
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["result"]["isError"], true);
+    }
+
+    #[test]
+    fn unknown_methods_are_protocol_errors() {
+        let response = handle(&json!({ "jsonrpc": "2.0", "id": 2, "method": "resources/list" })).unwrap();
+        assert_eq!(response["error"]["code"], -32601);
+    }
+}
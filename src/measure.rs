@@ -0,0 +1,152 @@
+use crate::ColorInfo;
+use std::collections::VecDeque;
+use xcap::image::RgbaImage;
+
+/// Two marked screen points. While measuring, SPACE marks A, then B, then
+/// starts over at A.
+#[derive(Debug, Clone, Default)]
+pub struct Measurement {
+    pub a: Option<ColorInfo>,
+    pub b: Option<ColorInfo>,
+}
+
+impl Measurement {
+    pub fn mark(&mut self, point: ColorInfo) {
+        if self.a.is_none() || self.b.is_some() {
+            self.a = Some(point);
+            self.b = None;
+        } else {
+            self.b = Some(point);
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        Some(Span::between(self.a.as_ref()?.position, self.b.as_ref()?.position))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub dx: i32,
+    pub dy: i32,
+    pub distance: f32,
+    /// Degrees counterclockwise from the positive x axis, with y pointing up
+    /// as on paper rather than down as in screen coordinates.
+    pub angle: f32,
+}
+
+impl Span {
+    pub fn between(a: (i32, i32), b: (i32, i32)) -> Self {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        Self { dx, dy, distance: (dx as f32).hypot(dy as f32), angle: (-dy as f32).atan2(dx as f32).to_degrees() }
+    }
+}
+
+/// `count` evenly spaced pixel positions from `a` to `b`, both included.
+pub fn points_along(a: (i32, i32), b: (i32, i32), count: usize) -> Vec<(i32, i32)> {
+    if count < 2 {
+        return vec![a];
+    }
+    (0..count)
+        .map(|i| {
+            let t = i as f32 / (count - 1) as f32;
+            let lerp = |from: i32, to: i32| (from as f32 + (to - from) as f32 * t).round() as i32;
+            (lerp(a.0, b.0), lerp(a.1, b.1))
+        })
+        .collect()
+}
+
+/// Bounding box of a contiguous same-color region, in image pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    /// The region reaches the edge of the image, so it may extend further.
+    pub clipped: bool,
+}
+
+/// Flood-fills from `start` over 4-connected pixels with exactly the same RGB
+/// value and returns the bounding box of everything reached.
+pub fn contiguous_region(image: &RgbaImage, start: (u32, u32)) -> Option<Region> {
+    let (width, height) = image.dimensions();
+    if start.0 >= width || start.1 >= height {
+        return None;
+    }
+
+    let rgb = |x: u32, y: u32| {
+        let pixel = image.get_pixel(x, y);
+        [pixel[0], pixel[1], pixel[2]]
+    };
+    let target = rgb(start.0, start.1);
+    let mut visited = vec![false; (width * height) as usize];
+    let mut queue = VecDeque::from([start]);
+    visited[(start.1 * width + start.0) as usize] = true;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (start.0, start.1, start.0, start.1);
+
+    while let Some((x, y)) = queue.pop_front() {
+        (min_x, min_y, max_x, max_y) = (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y));
+
+        let neighbors = [
+            x.checked_sub(1).map(|x| (x, y)),
+            (x + 1 < width).then_some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            (y + 1 < height).then_some((x, y + 1)),
+        ];
+        for (nx, ny) in neighbors.into_iter().flatten() {
+            let index = (ny * width + nx) as usize;
+            if !visited[index] && rgb(nx, ny) == target {
+                visited[index] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    Some(Region {
+        left: min_x,
+        top: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+        clipped: min_x == 0 || min_y == 0 || max_x == width - 1 || max_y == height - 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use xcap::image::Rgba;
+
+    #[test]
+    fn points_along_include_both_ends() {
+        assert_eq!(points_along((0, 0), (10, -5), 3), vec![(0, 0), (5, -3), (10, -5)]);
+        assert_eq!(points_along((4, 4), (8, 8), 1), vec![(4, 4)]);
+    }
+
+    #[test]
+    fn region_around_button() {
+        let mut image = RgbaImage::from_pixel(10, 8, Rgba([255, 255, 255, 255]));
+        for y in 2..5 {
+            for x in 3..9 {
+                image.put_pixel(x, y, Rgba([58, 123, 213, 255]));
+            }
+        }
+
+        assert_eq!(
+            contiguous_region(&image, (4, 3)),
+            Some(Region { left: 3, top: 2, width: 6, height: 3, clipped: false })
+        );
+        assert!(contiguous_region(&image, (0, 0)).unwrap().clipped);
+        assert_eq!(contiguous_region(&image, (10, 0)), None);
+    }
+
+    #[test]
+    fn span_between_points() {
+        let span = Span::between((10, 20), (13, 16));
+        assert_eq!((span.dx, span.dy, span.distance), (3, -4, 5.0));
+        assert!((span.angle - 53.13).abs() < 0.01);
+
+        assert!((Span::between((0, 0), (0, 10)).angle + 90.0).abs() < 0.01);
+    }
+}
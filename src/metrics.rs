@@ -0,0 +1,107 @@
+use iced::Color;
+use palette::color_difference::Ciede2000;
+use palette::{Hsl, IntoColor, Lab, Srgb};
+
+/// CIEDE2000 color difference; values below ~1 are imperceptible.
+pub fn delta_e(a: Color, b: Color) -> f32 {
+    let a: Lab = Srgb::new(a.r, a.g, a.b).into_color();
+    let b: Lab = Srgb::new(b.r, b.g, b.b).into_color();
+    a.difference(b)
+}
+
+/// WCAG 2.1 contrast ratio between two colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: Color, b: Color) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    let linear = |c: f32| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+/// Per-channel differences from `b` to `a`: 8-bit RGB, hue in degrees
+/// (shortest way round), saturation and lightness in percentage points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelDeltas {
+    pub r: i16,
+    pub g: i16,
+    pub b: i16,
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+}
+
+pub fn channel_deltas(a: Color, b: Color) -> ChannelDeltas {
+    let ([ar, ag, ab, _], [br, bg, bb, _]) = (a.into_rgba8(), b.into_rgba8());
+    let ha: Hsl = Srgb::new(a.r, a.g, a.b).into_color();
+    let hb: Hsl = Srgb::new(b.r, b.g, b.b).into_color();
+    let hue = (ha.hue.into_positive_degrees() - hb.hue.into_positive_degrees() + 540.0).rem_euclid(360.0) - 180.0;
+
+    ChannelDeltas {
+        r: ar as i16 - br as i16,
+        g: ag as i16 - bg as i16,
+        b: ab as i16 - bb as i16,
+        hue,
+        saturation: (ha.saturation - hb.saturation) * 100.0,
+        lightness: (ha.lightness - hb.lightness) * 100.0,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compliance {
+    /// Identical to a palette color at 8 bits per channel.
+    Exact,
+    WithinTolerance,
+    OffBrand,
+}
+
+/// Checks `color` against a reference palette: the index of the nearest
+/// palette color, its ΔE, and whether that is within `tolerance`.
+pub fn check_compliance(color: Color, palette: &[Color], tolerance: f32) -> Option<(usize, f32, Compliance)> {
+    let (index, distance) =
+        palette.iter().map(|&candidate| delta_e(color, candidate)).enumerate().min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    let compliance = if palette[index].into_rgba8() == color.into_rgba8() {
+        Compliance::Exact
+    } else if distance <= tolerance {
+        Compliance::WithinTolerance
+    } else {
+        Compliance::OffBrand
+    };
+    Some((index, distance, compliance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn compliance_with_an_empty_palette_is_unknown() {
+        assert_eq!(check_compliance(Color::WHITE, &[], 5.0), None);
+    }
+
+    #[test]
+    fn compliance_picks_the_nearest_palette_color() {
+        let palette = [Color::BLACK, Color::from_rgb8(250, 250, 250), Color::from_rgb8(255, 0, 0)];
+        let (index, distance, compliance) = check_compliance(Color::from_rgb8(250, 250, 250), &palette, 0.0).unwrap();
+        assert_eq!((index, distance, compliance), (1, 0.0, Compliance::Exact));
+
+        let (index, _, _) = check_compliance(Color::from_rgb8(240, 10, 10), &palette, 0.0).unwrap();
+        assert_eq!(index, 2);
+    }
+
+    #[test]
+    fn compliance_tolerance_is_inclusive() {
+        let (color, reference) = (Color::from_rgb8(100, 100, 100), Color::from_rgb8(104, 100, 100));
+        let distance = delta_e(color, reference);
+        assert!(distance > 0.0);
+
+        let check = |tolerance| check_compliance(color, &[reference], tolerance).map(|(_, _, compliance)| compliance);
+        assert_eq!(check(distance), Some(Compliance::WithinTolerance));
+        assert_eq!(check(distance + 0.01), Some(Compliance::WithinTolerance));
+        assert_eq!(check(distance - 0.01), Some(Compliance::OffBrand));
+    }
+}
@@ -0,0 +1,589 @@
+use crate::{i18n, metrics};
+use iced::Color;
+use palette::{IntoColor, Oklch, Srgb};
+
+/// A fixed set of named colors that picks can be matched against.
+pub struct NamedPalette {
+    /// Shown in the readout, e.g. "Flutter".
+    pub name: &'static str,
+    /// Names with their colors as `0xRRGGBB`.
+    pub colors: &'static [(&'static str, u32)],
+}
+
+/// Flutter's `Colors` constants, the Material primaries at their 500 shade.
+pub const FLUTTER: NamedPalette = NamedPalette {
+    name: "Flutter",
+    colors: &[
+        ("Colors.red", 0xF44336),
+        ("Colors.pink", 0xE91E63),
+        ("Colors.purple", 0x9C27B0),
+        ("Colors.deepPurple", 0x673AB7),
+        ("Colors.indigo", 0x3F51B5),
+        ("Colors.blue", 0x2196F3),
+        ("Colors.lightBlue", 0x03A9F4),
+        ("Colors.cyan", 0x00BCD4),
+        ("Colors.teal", 0x009688),
+        ("Colors.green", 0x4CAF50),
+        ("Colors.lightGreen", 0x8BC34A),
+        ("Colors.lime", 0xCDDC39),
+        ("Colors.yellow", 0xFFEB3B),
+        ("Colors.amber", 0xFFC107),
+        ("Colors.orange", 0xFF9800),
+        ("Colors.deepOrange", 0xFF5722),
+        ("Colors.brown", 0x795548),
+        ("Colors.grey", 0x9E9E9E),
+        ("Colors.blueGrey", 0x607D8B),
+        ("Colors.black", 0x000000),
+        ("Colors.white", 0xFFFFFF),
+    ],
+};
+
+/// The classic X11 `rgb.txt` names. Where CSS took a name over but changed
+/// its value (`Gray`, `Green`, `Maroon`, `Purple`) this has the X11 value.
+pub const X11: NamedPalette = NamedPalette {
+    name: "X11",
+    colors: &[
+        ("AliceBlue", 0xF0F8FF),
+        ("AntiqueWhite", 0xFAEBD7),
+        ("Aquamarine", 0x7FFFD4),
+        ("Azure", 0xF0FFFF),
+        ("Beige", 0xF5F5DC),
+        ("Bisque", 0xFFE4C4),
+        ("Black", 0x000000),
+        ("BlanchedAlmond", 0xFFEBCD),
+        ("Blue", 0x0000FF),
+        ("BlueViolet", 0x8A2BE2),
+        ("Brown", 0xA52A2A),
+        ("BurlyWood", 0xDEB887),
+        ("CadetBlue", 0x5F9EA0),
+        ("Chartreuse", 0x7FFF00),
+        ("Chocolate", 0xD2691E),
+        ("Coral", 0xFF7F50),
+        ("CornflowerBlue", 0x6495ED),
+        ("Cornsilk", 0xFFF8DC),
+        ("Cyan", 0x00FFFF),
+        ("DarkBlue", 0x00008B),
+        ("DarkCyan", 0x008B8B),
+        ("DarkGoldenrod", 0xB8860B),
+        ("DarkGray", 0xA9A9A9),
+        ("DarkGreen", 0x006400),
+        ("DarkKhaki", 0xBDB76B),
+        ("DarkMagenta", 0x8B008B),
+        ("DarkOliveGreen", 0x556B2F),
+        ("DarkOrange", 0xFF8C00),
+        ("DarkOrchid", 0x9932CC),
+        ("DarkRed", 0x8B0000),
+        ("DarkSalmon", 0xE9967A),
+        ("DarkSeaGreen", 0x8FBC8F),
+        ("DarkSlateBlue", 0x483D8B),
+        ("DarkSlateGray", 0x2F4F4F),
+        ("DarkTurquoise", 0x00CED1),
+        ("DarkViolet", 0x9400D3),
+        ("DeepPink", 0xFF1493),
+        ("DeepSkyBlue", 0x00BFFF),
+        ("DimGray", 0x696969),
+        ("DodgerBlue", 0x1E90FF),
+        ("FireBrick", 0xB22222),
+        ("FloralWhite", 0xFFFAF0),
+        ("ForestGreen", 0x228B22),
+        ("Gainsboro", 0xDCDCDC),
+        ("GhostWhite", 0xF8F8FF),
+        ("Gold", 0xFFD700),
+        ("Goldenrod", 0xDAA520),
+        ("Gray", 0xBEBEBE),
+        ("Green", 0x00FF00),
+        ("GreenYellow", 0xADFF2F),
+        ("Honeydew", 0xF0FFF0),
+        ("HotPink", 0xFF69B4),
+        ("IndianRed", 0xCD5C5C),
+        ("Ivory", 0xFFFFF0),
+        ("Khaki", 0xF0E68C),
+        ("Lavender", 0xE6E6FA),
+        ("LavenderBlush", 0xFFF0F5),
+        ("LawnGreen", 0x7CFC00),
+        ("LemonChiffon", 0xFFFACD),
+        ("LightBlue", 0xADD8E6),
+        ("LightCoral", 0xF08080),
+        ("LightCyan", 0xE0FFFF),
+        ("LightGoldenrod", 0xEEDD82),
+        ("LightGoldenrodYellow", 0xFAFAD2),
+        ("LightGray", 0xD3D3D3),
+        ("LightGreen", 0x90EE90),
+        ("LightPink", 0xFFB6C1),
+        ("LightSalmon", 0xFFA07A),
+        ("LightSeaGreen", 0x20B2AA),
+        ("LightSkyBlue", 0x87CEFA),
+        ("LightSlateBlue", 0x8470FF),
+        ("LightSlateGray", 0x778899),
+        ("LightSteelBlue", 0xB0C4DE),
+        ("LightYellow", 0xFFFFE0),
+        ("LimeGreen", 0x32CD32),
+        ("Linen", 0xFAF0E6),
+        ("Magenta", 0xFF00FF),
+        ("Maroon", 0xB03060),
+        ("MediumAquamarine", 0x66CDAA),
+        ("MediumBlue", 0x0000CD),
+        ("MediumOrchid", 0xBA55D3),
+        ("MediumPurple", 0x9370DB),
+        ("MediumSeaGreen", 0x3CB371),
+        ("MediumSlateBlue", 0x7B68EE),
+        ("MediumSpringGreen", 0x00FA9A),
+        ("MediumTurquoise", 0x48D1CC),
+        ("MediumVioletRed", 0xC71585),
+        ("MidnightBlue", 0x191970),
+        ("MintCream", 0xF5FFFA),
+        ("MistyRose", 0xFFE4E1),
+        ("Moccasin", 0xFFE4B5),
+        ("NavajoWhite", 0xFFDEAD),
+        ("Navy", 0x000080),
+        ("OldLace", 0xFDF5E6),
+        ("OliveDrab", 0x6B8E23),
+        ("Orange", 0xFFA500),
+        ("OrangeRed", 0xFF4500),
+        ("Orchid", 0xDA70D6),
+        ("PaleGoldenrod", 0xEEE8AA),
+        ("PaleGreen", 0x98FB98),
+        ("PaleTurquoise", 0xAFEEEE),
+        ("PaleVioletRed", 0xDB7093),
+        ("PapayaWhip", 0xFFEFD5),
+        ("PeachPuff", 0xFFDAB9),
+        ("Peru", 0xCD853F),
+        ("Pink", 0xFFC0CB),
+        ("Plum", 0xDDA0DD),
+        ("PowderBlue", 0xB0E0E6),
+        ("Purple", 0xA020F0),
+        ("Red", 0xFF0000),
+        ("RosyBrown", 0xBC8F8F),
+        ("RoyalBlue", 0x4169E1),
+        ("SaddleBrown", 0x8B4513),
+        ("Salmon", 0xFA8072),
+        ("SandyBrown", 0xF4A460),
+        ("SeaGreen", 0x2E8B57),
+        ("Seashell", 0xFFF5EE),
+        ("Sienna", 0xA0522D),
+        ("SkyBlue", 0x87CEEB),
+        ("SlateBlue", 0x6A5ACD),
+        ("SlateGray", 0x708090),
+        ("Snow", 0xFFFAFA),
+        ("SpringGreen", 0x00FF7F),
+        ("SteelBlue", 0x4682B4),
+        ("Tan", 0xD2B48C),
+        ("Thistle", 0xD8BFD8),
+        ("Tomato", 0xFF6347),
+        ("Turquoise", 0x40E0D0),
+        ("Violet", 0xEE82EE),
+        ("VioletRed", 0xD02090),
+        ("Wheat", 0xF5DEB3),
+        ("White", 0xFFFFFF),
+        ("WhiteSmoke", 0xF5F5F5),
+        ("Yellow", 0xFFFF00),
+        ("YellowGreen", 0x9ACD32),
+    ],
+};
+
+/// The 16 standard terminal colors by index and name, at xterm's default
+/// values; other terminals' themes vary.
+pub const ANSI: NamedPalette = NamedPalette {
+    name: "ANSI",
+    colors: &[
+        ("0 black", 0x000000),
+        ("1 red", 0xCD0000),
+        ("2 green", 0x00CD00),
+        ("3 yellow", 0xCDCD00),
+        ("4 blue", 0x0000EE),
+        ("5 magenta", 0xCD00CD),
+        ("6 cyan", 0x00CDCD),
+        ("7 white", 0xE5E5E5),
+        ("8 bright black", 0x7F7F7F),
+        ("9 bright red", 0xFF0000),
+        ("10 bright green", 0x00FF00),
+        ("11 bright yellow", 0xFFFF00),
+        ("12 bright blue", 0x5C5CFF),
+        ("13 bright magenta", 0xFF00FF),
+        ("14 bright cyan", 0x00FFFF),
+        ("15 bright white", 0xFFFFFF),
+    ],
+};
+
+/// The RAL Classic collection as the sRGB approximations commonly published
+/// for it; RAL defines the colors physically, so these are only a guide.
+pub const RAL_CLASSIC: NamedPalette = NamedPalette {
+    name: "RAL Classic",
+    colors: &[
+        ("RAL 1000 Green beige", 0xBEBD7F),
+        ("RAL 1001 Beige", 0xC2B078),
+        ("RAL 1002 Sand yellow", 0xC6A664),
+        ("RAL 1003 Signal yellow", 0xE5BE01),
+        ("RAL 1004 Golden yellow", 0xCDA434),
+        ("RAL 1005 Honey yellow", 0xA98307),
+        ("RAL 1006 Maize yellow", 0xE4A010),
+        ("RAL 1007 Daffodil yellow", 0xDC9D00),
+        ("RAL 1011 Brown beige", 0x8A6642),
+        ("RAL 1012 Lemon yellow", 0xC7B446),
+        ("RAL 1013 Oyster white", 0xEAE6CA),
+        ("RAL 1014 Ivory", 0xE1CC4F),
+        ("RAL 1015 Light ivory", 0xE6D690),
+        ("RAL 1016 Sulfur yellow", 0xEDFF21),
+        ("RAL 1017 Saffron yellow", 0xF5D033),
+        ("RAL 1018 Zinc yellow", 0xF8F32B),
+        ("RAL 1019 Grey beige", 0x9E9764),
+        ("RAL 1020 Olive yellow", 0x999950),
+        ("RAL 1021 Rape yellow", 0xF3DA0B),
+        ("RAL 1023 Traffic yellow", 0xFAD201),
+        ("RAL 1024 Ochre yellow", 0xAEA04B),
+        ("RAL 1026 Luminous yellow", 0xFFFF00),
+        ("RAL 1027 Curry", 0x9D9101),
+        ("RAL 1028 Melon yellow", 0xF4A900),
+        ("RAL 1032 Broom yellow", 0xD6AE01),
+        ("RAL 1033 Dahlia yellow", 0xF3A505),
+        ("RAL 1034 Pastel yellow", 0xEFA94A),
+        ("RAL 1035 Pearl beige", 0x6A5D4D),
+        ("RAL 1036 Pearl gold", 0x705335),
+        ("RAL 1037 Sun yellow", 0xF39F18),
+        ("RAL 2000 Yellow orange", 0xED760E),
+        ("RAL 2001 Red orange", 0xC93C20),
+        ("RAL 2002 Vermilion", 0xCB2821),
+        ("RAL 2003 Pastel orange", 0xFF7514),
+        ("RAL 2004 Pure orange", 0xF44611),
+        ("RAL 2005 Luminous orange", 0xFF2301),
+        ("RAL 2007 Luminous bright orange", 0xFFA420),
+        ("RAL 2008 Bright red orange", 0xF75E25),
+        ("RAL 2009 Traffic orange", 0xF54021),
+        ("RAL 2010 Signal orange", 0xD84B20),
+        ("RAL 2011 Deep orange", 0xEC7C26),
+        ("RAL 2012 Salmon orange", 0xE55137),
+        ("RAL 2013 Pearl orange", 0xC35831),
+        ("RAL 3000 Flame red", 0xAF2B1E),
+        ("RAL 3001 Signal red", 0xA52019),
+        ("RAL 3002 Carmine red", 0xA2231D),
+        ("RAL 3003 Ruby red", 0x9B111E),
+        ("RAL 3004 Purple red", 0x75151E),
+        ("RAL 3005 Wine red", 0x5E2129),
+        ("RAL 3007 Black red", 0x412227),
+        ("RAL 3009 Oxide red", 0x642424),
+        ("RAL 3011 Brown red", 0x781F19),
+        ("RAL 3012 Beige red", 0xC1876B),
+        ("RAL 3013 Tomato red", 0xA12312),
+        ("RAL 3014 Antique pink", 0xD36E70),
+        ("RAL 3015 Light pink", 0xEA899A),
+        ("RAL 3016 Coral red", 0xB32821),
+        ("RAL 3017 Rose", 0xE63244),
+        ("RAL 3018 Strawberry red", 0xD53032),
+        ("RAL 3020 Traffic red", 0xCC0605),
+        ("RAL 3022 Salmon pink", 0xD95030),
+        ("RAL 3024 Luminous red", 0xF80000),
+        ("RAL 3026 Luminous bright red", 0xFE0000),
+        ("RAL 3027 Raspberry red", 0xC51D34),
+        ("RAL 3028 Pure red", 0xCB3234),
+        ("RAL 3031 Orient red", 0xB32428),
+        ("RAL 3032 Pearl ruby red", 0x721422),
+        ("RAL 3033 Pearl pink", 0xB44C43),
+        ("RAL 4001 Red lilac", 0x6D3F5B),
+        ("RAL 4002 Red violet", 0x922B3E),
+        ("RAL 4003 Heather violet", 0xDE4C8A),
+        ("RAL 4004 Claret violet", 0x641C34),
+        ("RAL 4005 Blue lilac", 0x6C4675),
+        ("RAL 4006 Traffic purple", 0xA03472),
+        ("RAL 4007 Purple violet", 0x4A192C),
+        ("RAL 4008 Signal violet", 0x924E7D),
+        ("RAL 4009 Pastel violet", 0xA18594),
+        ("RAL 4010 Telemagenta", 0xCF3476),
+        ("RAL 4011 Pearl violet", 0x8673A1),
+        ("RAL 4012 Pearl blackberry", 0x6C6874),
+        ("RAL 5000 Violet blue", 0x354D73),
+        ("RAL 5001 Green blue", 0x1F3438),
+        ("RAL 5002 Ultramarine blue", 0x20214F),
+        ("RAL 5003 Sapphire blue", 0x1D1E33),
+        ("RAL 5004 Black blue", 0x18171C),
+        ("RAL 5005 Signal blue", 0x1E2460),
+        ("RAL 5007 Brilliant blue", 0x3E5F8A),
+        ("RAL 5008 Grey blue", 0x26252D),
+        ("RAL 5009 Azure blue", 0x025669),
+        ("RAL 5010 Gentian blue", 0x0E294B),
+        ("RAL 5011 Steel blue", 0x231A24),
+        ("RAL 5012 Light blue", 0x3B83BD),
+        ("RAL 5013 Cobalt blue", 0x1E213D),
+        ("RAL 5014 Pigeon blue", 0x606E8C),
+        ("RAL 5015 Sky blue", 0x2271B3),
+        ("RAL 5017 Traffic blue", 0x063971),
+        ("RAL 5018 Turquoise blue", 0x3F888F),
+        ("RAL 5019 Capri blue", 0x1B5583),
+        ("RAL 5020 Ocean blue", 0x1D334A),
+        ("RAL 5021 Water blue", 0x256D7B),
+        ("RAL 5022 Night blue", 0x252850),
+        ("RAL 5023 Distant blue", 0x49678D),
+        ("RAL 5024 Pastel blue", 0x5D9B9B),
+        ("RAL 5025 Pearl gentian blue", 0x2A6478),
+        ("RAL 5026 Pearl night blue", 0x102C54),
+        ("RAL 6000 Patina green", 0x316650),
+        ("RAL 6001 Emerald green", 0x287233),
+        ("RAL 6002 Leaf green", 0x2D572C),
+        ("RAL 6003 Olive green", 0x424632),
+        ("RAL 6004 Blue green", 0x1F3A3D),
+        ("RAL 6005 Moss green", 0x2F4538),
+        ("RAL 6006 Grey olive", 0x3E3B32),
+        ("RAL 6007 Bottle green", 0x343B29),
+        ("RAL 6008 Brown green", 0x39352A),
+        ("RAL 6009 Fir green", 0x31372B),
+        ("RAL 6010 Grass green", 0x35682D),
+        ("RAL 6011 Reseda green", 0x587246),
+        ("RAL 6012 Black green", 0x343E40),
+        ("RAL 6013 Reed green", 0x6C7156),
+        ("RAL 6014 Yellow olive", 0x47402E),
+        ("RAL 6015 Black olive", 0x3B3C36),
+        ("RAL 6016 Turquoise green", 0x1E5945),
+        ("RAL 6017 May green", 0x4C9141),
+        ("RAL 6018 Yellow green", 0x57A639),
+        ("RAL 6019 Pastel green", 0xBDECB6),
+        ("RAL 6020 Chrome green", 0x2E3A23),
+        ("RAL 6021 Pale green", 0x89AC76),
+        ("RAL 6022 Olive drab", 0x25221B),
+        ("RAL 6024 Traffic green", 0x308446),
+        ("RAL 6025 Fern green", 0x3D642D),
+        ("RAL 6026 Opal green", 0x015D52),
+        ("RAL 6027 Light green", 0x84C3BE),
+        ("RAL 6028 Pine green", 0x2C5545),
+        ("RAL 6029 Mint green", 0x20603D),
+        ("RAL 6032 Signal green", 0x317F43),
+        ("RAL 6033 Mint turquoise", 0x497E76),
+        ("RAL 6034 Pastel turquoise", 0x7FB5B5),
+        ("RAL 6035 Pearl dark green", 0x1C542D),
+        ("RAL 6036 Pearl opal green", 0x193737),
+        ("RAL 6037 Pure green", 0x008F39),
+        ("RAL 6038 Luminous green", 0x00BB2D),
+        ("RAL 7000 Squirrel grey", 0x78858B),
+        ("RAL 7001 Silver grey", 0x8A9597),
+        ("RAL 7002 Olive grey", 0x7E7B52),
+        ("RAL 7003 Moss grey", 0x6C7059),
+        ("RAL 7004 Signal grey", 0x969992),
+        ("RAL 7005 Mouse grey", 0x646B63),
+        ("RAL 7006 Beige grey", 0x6D6552),
+        ("RAL 7008 Khaki grey", 0x6A5F31),
+        ("RAL 7009 Green grey", 0x4D5645),
+        ("RAL 7010 Tarpaulin grey", 0x4C514A),
+        ("RAL 7011 Iron grey", 0x434B4D),
+        ("RAL 7012 Basalt grey", 0x4E5754),
+        ("RAL 7013 Brown grey", 0x464531),
+        ("RAL 7015 Slate grey", 0x434750),
+        ("RAL 7016 Anthracite grey", 0x293133),
+        ("RAL 7021 Black grey", 0x23282B),
+        ("RAL 7022 Umbra grey", 0x332F2C),
+        ("RAL 7023 Concrete grey", 0x686C5E),
+        ("RAL 7024 Graphite grey", 0x474A51),
+        ("RAL 7026 Granite grey", 0x2F353B),
+        ("RAL 7030 Stone grey", 0x8B8C7A),
+        ("RAL 7031 Blue grey", 0x474B4E),
+        ("RAL 7032 Pebble grey", 0xB8B799),
+        ("RAL 7033 Cement grey", 0x7D8471),
+        ("RAL 7034 Yellow grey", 0x8F8B66),
+        ("RAL 7035 Light grey", 0xD7D7D7),
+        ("RAL 7036 Platinum grey", 0x7F7679),
+        ("RAL 7037 Dusty grey", 0x7D7F7D),
+        ("RAL 7038 Agate grey", 0xB5B8B1),
+        ("RAL 7039 Quartz grey", 0x6C6960),
+        ("RAL 7040 Window grey", 0x9DA1AA),
+        ("RAL 7042 Traffic grey A", 0x8D948D),
+        ("RAL 7043 Traffic grey B", 0x4E5452),
+        ("RAL 7044 Silk grey", 0xCAC4B0),
+        ("RAL 7045 Telegrey 1", 0x909090),
+        ("RAL 7046 Telegrey 2", 0x82898F),
+        ("RAL 7047 Telegrey 4", 0xD0D0D0),
+        ("RAL 7048 Pearl mouse grey", 0x898176),
+        ("RAL 8000 Green brown", 0x826C34),
+        ("RAL 8001 Ochre brown", 0x955F20),
+        ("RAL 8002 Signal brown", 0x6C3B2A),
+        ("RAL 8003 Clay brown", 0x734222),
+        ("RAL 8004 Copper brown", 0x8E402A),
+        ("RAL 8007 Fawn brown", 0x59351F),
+        ("RAL 8008 Olive brown", 0x6F4F28),
+        ("RAL 8011 Nut brown", 0x5B3A29),
+        ("RAL 8012 Red brown", 0x592321),
+        ("RAL 8014 Sepia brown", 0x382C1E),
+        ("RAL 8015 Chestnut brown", 0x633A34),
+        ("RAL 8016 Mahogany brown", 0x4C2F27),
+        ("RAL 8017 Chocolate brown", 0x45322E),
+        ("RAL 8019 Grey brown", 0x403A3A),
+        ("RAL 8022 Black brown", 0x212121),
+        ("RAL 8023 Orange brown", 0xA65E2E),
+        ("RAL 8024 Beige brown", 0x79553D),
+        ("RAL 8025 Pale brown", 0x755C48),
+        ("RAL 8028 Terra brown", 0x4E3B31),
+        ("RAL 8029 Pearl copper", 0x763C28),
+        ("RAL 9001 Cream", 0xFDF4E3),
+        ("RAL 9002 Grey white", 0xE7EBDA),
+        ("RAL 9003 Signal white", 0xF4F4F4),
+        ("RAL 9004 Signal black", 0x282828),
+        ("RAL 9005 Jet black", 0x0A0A0A),
+        ("RAL 9006 White aluminium", 0xA5A5A5),
+        ("RAL 9007 Grey aluminium", 0x8F8F8F),
+        ("RAL 9010 Pure white", 0xFFFFFF),
+        ("RAL 9011 Graphite black", 0x1C1C1C),
+        ("RAL 9016 Traffic white", 0xF6F6F6),
+        ("RAL 9017 Traffic black", 0x1E1E1E),
+        ("RAL 9018 Papyrus white", 0xD7D7D7),
+        ("RAL 9022 Pearl light grey", 0x9C9C9C),
+        ("RAL 9023 Pearl dark grey", 0x828282),
+    ],
+};
+
+/// The web-safe color closest to `color`, each channel snapped to a
+/// multiple of `0x33`, and its CIEDE2000 distance.
+pub fn nearest_web_safe(color: Color) -> (Color, f32) {
+    let snap = |channel: f32| ((channel.clamp(0.0, 1.0) * 5.0).round() * 51.0) as u8;
+    let safe = Color::from_rgb8(snap(color.r), snap(color.g), snap(color.b));
+    (safe, metrics::delta_e(safe, color))
+}
+
+/// OKLCH hue bands up to the given angle, with the lightness below which
+/// the band looks dark and its names when dark, in the middle and light.
+/// Yellows only look dark much lighter than blues do. Names are the ends of
+/// `color-word-*` messages.
+const HUES: [(f32, f32, &str, &str, &str); 14] = [
+    (15.0, 0.45, "wine", "rose", "pink"),
+    (40.0, 0.45, "maroon", "red", "salmon"),
+    (65.0, 0.55, "brown", "orange", "apricot"),
+    (85.0, 0.55, "bronze", "amber", "cream"),
+    (115.0, 0.55, "olive", "yellow", "lemon"),
+    (135.0, 0.55, "moss", "lime", "pistachio"),
+    (165.0, 0.45, "forest", "green", "mint"),
+    (215.0, 0.45, "teal", "teal", "aqua"),
+    (245.0, 0.4, "navy", "azure", "sky"),
+    (275.0, 0.35, "navy", "blue", "periwinkle"),
+    (300.0, 0.35, "indigo", "violet", "lavender"),
+    (320.0, 0.4, "plum", "purple", "lilac"),
+    (345.0, 0.45, "plum", "magenta", "orchid"),
+    (360.0, 0.45, "wine", "rose", "pink"),
+];
+
+/// A short description such as "dusty teal" or "pale apricot" in the
+/// translator's language, from the color's OKLCH lightness, chroma and hue.
+pub fn describe(color: Color, i18n: &i18n::Translator) -> String {
+    let word = |word: &str| i18n.tr(&format!("color-word-{}", word));
+    match words(color) {
+        (Some(modifier), name) => {
+            i18n.tr_with("color-modified", &[("modifier", word(modifier).into()), ("name", word(name).into())])
+        },
+        (None, name) => word(name),
+    }
+}
+
+/// The modifier, if any, and name that [`describe`] translates.
+fn words(color: Color) -> (Option<&'static str>, &'static str) {
+    let oklch: Oklch = Srgb::new(color.r, color.g, color.b).into_color();
+    let (lightness, chroma) = (oklch.l, oklch.chroma);
+    if chroma < 0.02 {
+        let name = match lightness {
+            l if l > 0.95 => "white",
+            l if l > 0.75 => "light-gray",
+            l if l > 0.45 => "gray",
+            l if l > 0.2 => "charcoal",
+            _ => "black",
+        };
+        return (None, name);
+    }
+
+    let hue = oklch.hue.into_positive_degrees();
+    let &(_, dark_below, dark, middle, light) = HUES.iter().find(|(end, ..)| hue < *end).unwrap_or(&HUES[0]);
+    let name = if lightness < dark_below {
+        dark
+    } else if lightness > 0.8 && chroma < 0.15 {
+        light
+    } else {
+        middle
+    };
+    let modifier = if chroma < 0.06 {
+        Some(if lightness > 0.8 { "pale" } else { "dusty" })
+    } else if lightness < 0.3 {
+        Some("deep")
+    } else if chroma > 0.2 {
+        Some("vivid")
+    } else {
+        None
+    };
+    (modifier, name)
+}
+
+fn to_color(rgb: u32) -> Color {
+    Color::from_rgb8((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+}
+
+impl NamedPalette {
+    /// The entry closest to `color` and its CIEDE2000 distance.
+    pub fn nearest(&self, color: Color) -> Option<(&'static str, f32)> {
+        self.colors
+            .iter()
+            .map(|&(name, rgb)| (name, metrics::delta_e(to_color(rgb), color)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn nearest_flutter_color() {
+        let (name, distance) = FLUTTER.nearest(Color::from_rgb8(0x3F, 0x51, 0xB5)).unwrap();
+        assert_eq!(name, "Colors.indigo");
+        assert!(distance < 0.01);
+        assert_eq!(FLUTTER.nearest(Color::from_rgb8(250, 70, 60)).map(|(name, _)| name), Some("Colors.red"));
+        assert_eq!(FLUTTER.nearest(Color::from_rgb8(10, 10, 10)).map(|(name, _)| name), Some("Colors.black"));
+    }
+
+    #[test]
+    fn web_safe_colors_snap_each_channel() {
+        let (safe, distance) = nearest_web_safe(Color::from_rgb8(0x30, 0x6A, 0xA0));
+        assert_eq!(safe.into_rgba8(), [0x33, 0x66, 0x99, 255]);
+        assert!(distance > 0.0);
+        assert_eq!(nearest_web_safe(Color::from_rgb8(0xCC, 0xFF, 0x00)).0.into_rgba8(), [0xCC, 0xFF, 0x00, 255]);
+    }
+
+    #[test]
+    fn nearest_terminal_color() {
+        let name = |r, g, b| ANSI.nearest(Color::from_rgb8(r, g, b)).map(|(name, _)| name);
+        assert_eq!(name(200, 10, 10), Some("1 red"));
+        assert_eq!(name(250, 250, 250), Some("15 bright white"));
+        assert_eq!(name(120, 120, 130), Some("8 bright black"));
+    }
+
+    #[test]
+    fn nearest_ral_code() {
+        assert_eq!(RAL_CLASSIC.colors.len(), 213);
+        let (name, distance) = RAL_CLASSIC.nearest(Color::from_rgb8(0x22, 0x70, 0xB4)).unwrap();
+        assert_eq!(name, "RAL 5015 Sky blue");
+        assert!(distance < 1.0);
+    }
+
+    #[test]
+    fn descriptions_name_hue_lightness_and_chroma() {
+        let english = i18n::Translator::new(Some("en-US"));
+        let describe = |r, g, b| describe(Color::from_rgb8(r, g, b), &english);
+        assert_eq!(describe(255, 255, 255), "white");
+        assert_eq!(describe(200, 200, 200), "light gray");
+        assert_eq!(describe(128, 128, 128), "gray");
+        assert_eq!(describe(255, 0, 0), "vivid red");
+        assert_eq!(describe(0, 0, 128), "deep navy");
+        assert_eq!(describe(95, 140, 140), "dusty teal");
+        assert_eq!(describe(255, 220, 190), "pale apricot");
+        assert_eq!(describe(255, 255, 0), "vivid yellow");
+        assert_eq!(describe(139, 69, 19), "brown");
+
+        let german = i18n::Translator::new(Some("de"));
+        assert_eq!(super::describe(Color::from_rgb8(95, 140, 140), &german), "gedecktes Petrol");
+        for (_, _, dark, middle, light) in HUES {
+            for word in [dark, middle, light] {
+                let id = format!("color-word-{}", word);
+                assert_ne!(english.tr(&id), id);
+            }
+        }
+    }
+
+    #[test]
+    fn x11_names_keep_their_own_values() {
+        let name = |r, g, b| X11.nearest(Color::from_rgb8(r, g, b)).map(|(name, _)| name);
+        assert_eq!(name(190, 190, 190), Some("Gray"));
+        assert_eq!(name(0, 255, 0), Some("Green"));
+        assert_eq!(name(160, 32, 240), Some("Purple"));
+    }
+}
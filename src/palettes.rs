@@ -0,0 +1,151 @@
+use crate::error::PeekerError;
+use crate::{HistoryEntry, SerializableColor, Settings, write_json};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedPalette {
+    pub name: String,
+    pub colors: Vec<SerializableColor>,
+}
+
+/// User-managed named palettes, stored in their own file next to the settings
+/// so that window geometry writes never touch them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaletteLibrary {
+    pub palettes: Vec<NamedPalette>,
+    pub active: Option<usize>,
+}
+
+impl PaletteLibrary {
+    pub fn load() -> Self {
+        if let Some(path) = Self::get_path()
+            && let Ok(contents) = std::fs::read_to_string(&path)
+            && let Ok(mut library) = serde_json::from_str::<PaletteLibrary>(&contents)
+        {
+            library.active = library.active.filter(|&index| index < library.palettes.len());
+            return library;
+        }
+        Self::default()
+    }
+
+    pub fn save(&self) -> Result<(), PeekerError> {
+        let path = Self::get_path().ok_or(PeekerError::NoConfigDir("palettes"))?;
+        write_json(&path, self)
+    }
+
+    fn get_path() -> Option<std::path::PathBuf> {
+        Settings::get_settings_path().map(|path| path.with_file_name("palettes.json"))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.palettes.iter().map(|palette| palette.name.clone()).collect()
+    }
+
+    pub fn active(&self) -> Option<&NamedPalette> {
+        self.active.and_then(|index| self.palettes.get(index))
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut NamedPalette> {
+        self.active.and_then(|index| self.palettes.get_mut(index))
+    }
+
+    pub fn select(&mut self, name: &str) {
+        self.active = self.position(name);
+    }
+
+    /// The index of the palette called `name`, created empty if there is none.
+    pub fn index_or_create(&mut self, name: &str) -> usize {
+        self.position(name).unwrap_or_else(|| {
+            self.palettes.push(NamedPalette { name: name.to_string(), colors: Vec::new() });
+            self.palettes.len() - 1
+        })
+    }
+
+    pub fn create(&mut self, name: &str) {
+        let name = self.unique_name(name, None);
+        self.palettes.push(NamedPalette { name, colors: Vec::new() });
+        self.active = Some(self.palettes.len() - 1);
+    }
+
+    pub fn rename_active(&mut self, name: &str) {
+        if let Some(index) = self.active {
+            let name = self.unique_name(name, Some(index));
+            self.palettes[index].name = name;
+        }
+    }
+
+    pub fn delete_active(&mut self) {
+        if let Some(index) = self.active.take() {
+            self.palettes.remove(index);
+            if !self.palettes.is_empty() {
+                self.active = Some(index.min(self.palettes.len() - 1));
+            }
+        }
+    }
+
+    pub fn add_to_active(&mut self, entry: &HistoryEntry) {
+        if let Some(palette) = self.active_mut() {
+            palette.colors.push(SerializableColor::from(entry));
+        }
+    }
+
+    pub fn insert_into_active(&mut self, index: usize, entry: &HistoryEntry) {
+        if let Some(palette) = self.active_mut() {
+            let index = index.min(palette.colors.len());
+            palette.colors.insert(index, SerializableColor::from(entry));
+        }
+    }
+
+    pub fn reorder_active(&mut self, from: usize, to: usize) {
+        if let Some(palette) = self.active_mut()
+            && from < palette.colors.len()
+            && to < palette.colors.len()
+        {
+            let color = palette.colors.remove(from);
+            palette.colors.insert(to, color);
+        }
+    }
+
+    /// Moves the color at `index` in the active palette to the end of the palette named `target`.
+    pub fn move_from_active(&mut self, index: usize, target: &str) {
+        let (Some(from), Some(to)) = (self.active, self.position(target)) else {
+            return;
+        };
+        if from == to || index >= self.palettes[from].colors.len() {
+            return;
+        }
+
+        let color = self.palettes[from].colors.remove(index);
+        self.palettes[to].colors.push(color);
+    }
+
+    pub fn remove_from_active(&mut self, index: usize) {
+        if let Some(palette) = self.active_mut()
+            && index < palette.colors.len()
+        {
+            palette.colors.remove(index);
+        }
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.palettes.iter().position(|palette| palette.name == name)
+    }
+
+    /// Palettes are selected by name, so two palettes may never share one.
+    /// Appends a counter to `name` until it no longer collides with any
+    /// palette other than `except`.
+    fn unique_name(&self, name: &str, except: Option<usize>) -> String {
+        let base = if name.trim().is_empty() { "Untitled" } else { name.trim() };
+        let taken = |candidate: &str| {
+            self.palettes.iter().enumerate().any(|(index, palette)| Some(index) != except && palette.name == candidate)
+        };
+
+        let mut candidate = base.to_string();
+        let mut counter = 2;
+        while taken(&candidate) {
+            candidate = format!("{} {}", base, counter);
+            counter += 1;
+        }
+        candidate
+    }
+}
@@ -0,0 +1,124 @@
+use crate::ColorInfo;
+
+/// What the picker is doing. Every change goes through [`PickerState::apply`],
+/// so the combinations that can occur are the ones listed here.
+#[derive(Debug, Clone, Default)]
+pub enum PickerState {
+    /// Following the cursor.
+    #[default]
+    Live,
+    /// Holding a picked (or loaded) color.
+    Frozen(ColorInfo),
+    /// Not capturing at all, still showing the frozen color if there was one.
+    Paused(Option<ColorInfo>),
+    /// The pick overlay is open and the cursor is followed until it is
+    /// clicked; `previous` is restored if picking is cancelled.
+    Picking { previous: Option<ColorInfo> },
+}
+
+#[derive(Debug, Clone)]
+pub enum Transition {
+    /// A color was picked or loaded from history, a palette or a URL.
+    Freeze(ColorInfo),
+    /// Drops the frozen color; ignored while picking from the overlay.
+    Unfreeze,
+    TogglePause,
+    StartPicking,
+    CancelPicking,
+}
+
+impl PickerState {
+    pub fn new(frozen: Option<ColorInfo>) -> Self {
+        frozen.map_or(Self::Live, Self::Frozen)
+    }
+
+    pub fn apply(self, transition: Transition) -> Self {
+        match (self, transition) {
+            (Self::Paused(_), Transition::Freeze(info)) => Self::Paused(Some(info)),
+            (_, Transition::Freeze(info)) => Self::Frozen(info),
+
+            (Self::Frozen(_), Transition::Unfreeze) => Self::Live,
+            (Self::Paused(_), Transition::Unfreeze) => Self::Paused(None),
+
+            (Self::Live, Transition::TogglePause) => Self::Paused(None),
+            (Self::Frozen(info), Transition::TogglePause) => Self::Paused(Some(info)),
+            (Self::Paused(frozen), Transition::TogglePause) => Self::new(frozen),
+
+            (Self::Live, Transition::StartPicking) => Self::Picking { previous: None },
+            (Self::Frozen(info), Transition::StartPicking) => Self::Picking { previous: Some(info) },
+            (Self::Paused(frozen), Transition::StartPicking) => Self::Picking { previous: frozen },
+
+            (Self::Picking { previous }, Transition::CancelPicking) => Self::new(previous),
+
+            (state, _) => state,
+        }
+    }
+
+    /// The color being held instead of the live one.
+    pub fn frozen(&self) -> Option<&ColorInfo> {
+        match self {
+            Self::Frozen(info) | Self::Paused(Some(info)) => Some(info),
+            _ => None,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self, Self::Paused(_))
+    }
+
+    pub fn is_picking(&self) -> bool {
+        matches!(self, Self::Picking { .. })
+    }
+
+    /// Whether each tick captures what is under the cursor.
+    pub fn is_capturing(&self) -> bool {
+        matches!(self, Self::Live | Self::Picking { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::Color;
+    use pretty_assertions::assert_eq;
+
+    fn info(x: i32) -> ColorInfo {
+        ColorInfo::solid(Color::BLACK, (x, 0))
+    }
+
+    fn frozen_at(state: &PickerState) -> Option<i32> {
+        state.frozen().map(|info| info.position.0)
+    }
+
+    #[test]
+    fn pausing_keeps_the_frozen_color() {
+        let state = PickerState::Live.apply(Transition::Freeze(info(1)));
+        assert_eq!(frozen_at(&state), Some(1));
+
+        let paused = state.apply(Transition::TogglePause);
+        assert!(paused.is_paused() && !paused.is_capturing());
+        assert_eq!(frozen_at(&paused), Some(1));
+
+        let paused = paused.apply(Transition::Freeze(info(2)));
+        assert!(paused.is_paused());
+        let resumed = paused.apply(Transition::TogglePause);
+        assert!(matches!(resumed, PickerState::Frozen(_)));
+        assert_eq!(frozen_at(&resumed), Some(2));
+
+        assert!(matches!(resumed.apply(Transition::Unfreeze), PickerState::Live));
+    }
+
+    #[test]
+    fn cancelled_picking_restores_the_previous_color() {
+        let picking = PickerState::new(Some(info(1))).apply(Transition::StartPicking);
+        assert!(picking.is_picking() && picking.is_capturing());
+        assert_eq!(frozen_at(&picking), None);
+
+        let picking = picking.apply(Transition::Unfreeze).apply(Transition::TogglePause);
+        assert!(picking.is_picking());
+        assert_eq!(frozen_at(&picking.clone().apply(Transition::CancelPicking)), Some(1));
+        assert_eq!(frozen_at(&picking.apply(Transition::Freeze(info(3)))), Some(3));
+
+        assert!(matches!(PickerState::Live.apply(Transition::CancelPicking), PickerState::Live));
+    }
+}
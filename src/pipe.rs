@@ -0,0 +1,73 @@
+use std::io::BufRead;
+use std::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipeCommand {
+    /// `pick` or `pick <x> <y>`: freeze the pixel under the cursor or at a position.
+    Pick(Option<(i32, i32)>),
+    History,
+    Quit,
+}
+
+pub fn parse_command(line: &str) -> Result<PipeCommand, String> {
+    let mut words = line.split_whitespace();
+    let command = match (words.next(), words.next(), words.next()) {
+        (Some("pick"), None, _) => PipeCommand::Pick(None),
+        (Some("pick"), Some(x), Some(y)) => match (x.parse(), y.parse()) {
+            (Ok(x), Ok(y)) => PipeCommand::Pick(Some((x, y))),
+            _ => return Err(format!("invalid coordinates: {} {}", x, y)),
+        },
+        (Some("history"), None, _) => PipeCommand::History,
+        (Some("quit"), None, _) => PipeCommand::Quit,
+        _ => return Err(format!("unknown command: {}", line.trim())),
+    };
+    match words.next() {
+        Some(_) => Err(format!("unknown command: {}", line.trim())),
+        None => Ok(command),
+    }
+}
+
+/// Newline-delimited commands read from stdin on a background thread. Each
+/// command gets exactly one JSON line on stdout, written by the app.
+pub struct StdinCommands {
+    commands: mpsc::Receiver<Result<PipeCommand, String>>,
+}
+
+impl StdinCommands {
+    pub fn start() -> Self {
+        let (sender, commands) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if !line.trim().is_empty() && sender.send(parse_command(&line)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { commands }
+    }
+
+    pub fn pending_commands(&self) -> Vec<Result<PipeCommand, String>> {
+        self.commands.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse_command("pick"), Ok(PipeCommand::Pick(None)));
+        assert_eq!(parse_command("  pick 100 -200 "), Ok(PipeCommand::Pick(Some((100, -200)))));
+        assert_eq!(parse_command("history"), Ok(PipeCommand::History));
+        assert_eq!(parse_command("quit"), Ok(PipeCommand::Quit));
+        assert!(parse_command("pick 100").is_err());
+        assert!(parse_command("pick a b").is_err());
+        assert!(parse_command("quit now").is_err());
+        assert!(parse_command("zoom").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+#[cfg(feature = "lua")]
+mod lua;
+#[cfg(feature = "plugins")]
+mod wasm;
+
+use crate::Settings;
+use crate::error::PeekerError;
+use crate::export::Swatch;
+use iced::Color;
+use std::collections::{HashMap, HashSet};
+
+/// Colors whose plugin formats are kept; live picking passes through many.
+const MAX_CACHED_COLORS: usize = 4096;
+
+/// An extension loaded from the plugins directory. Every capability is
+/// optional; a plugin only overrides what it provides.
+pub trait Plugin {
+    fn name(&self) -> &str;
+
+    /// The color in the plugin's own format, if it provides one.
+    fn format(&self, _color: Color) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        Ok(None)
+    }
+
+    /// File extension of the plugin's palette exporter, if it provides one.
+    fn export_extension(&self) -> Option<&str> {
+        None
+    }
+
+    fn export(&self, _swatches: &[Swatch]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Err(format!("{} does not export palettes", self.name()).into())
+    }
+
+    /// Called whenever a color is frozen.
+    fn on_pick(&self, _color: Color, _position: (i32, i32)) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct Plugins {
+    plugins: Vec<Box<dyn Plugin>>,
+    /// Plugins that failed, which are not called again this run.
+    disabled: HashSet<usize>,
+    /// `(plugin index, formatted color)` by color, filled by [`Self::prepare`]
+    /// so drawing a frame never runs plugin code.
+    formats: HashMap<[u8; 4], Vec<(usize, String)>>,
+    error: Option<PeekerError>,
+}
+
+impl Plugins {
+    /// Loads WASM modules from `<config dir>/plugins` and Lua scripts from
+    /// `<config dir>/scripts`. Both runtimes are optional cargo features;
+    /// without them nothing loads.
+    #[cfg_attr(not(any(feature = "plugins", feature = "lua")), allow(unused_mut))]
+    pub fn load() -> Self {
+        let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
+        #[cfg(feature = "plugins")]
+        if let Some(dir) = Self::get_dir("plugins") {
+            plugins.extend(wasm::load_all(&dir));
+        }
+        #[cfg(feature = "lua")]
+        if let Some(dir) = Self::get_dir("scripts") {
+            plugins.extend(lua::load_all(&dir));
+        }
+        Self { plugins, ..Self::default() }
+    }
+
+    /// A directory next to the settings file.
+    #[cfg_attr(not(any(feature = "plugins", feature = "lua")), allow(dead_code))]
+    fn get_dir(name: &str) -> Option<std::path::PathBuf> {
+        Settings::get_settings_path().and_then(|path| path.parent().map(|parent| parent.join(name)))
+    }
+
+    /// Runs every plugin format on `color`, once per color. A plugin that
+    /// fails, or runs too long, is turned off and the failure kept for
+    /// [`Self::take_error`].
+    pub fn prepare(&mut self, color: Color) {
+        let key = color.into_rgba8();
+        if self.plugins.is_empty() || self.formats.contains_key(&key) {
+            return;
+        }
+        if self.formats.len() >= MAX_CACHED_COLORS {
+            self.formats.clear();
+        }
+        let mut formats = Vec::new();
+        for index in 0..self.plugins.len() {
+            if self.disabled.contains(&index) {
+                continue;
+            }
+            match self.plugins[index].format(color) {
+                Ok(formatted) => formats.extend(formatted.map(|formatted| (index, formatted))),
+                Err(e) => self.disable(index, e),
+            }
+        }
+        self.formats.insert(key, formats);
+    }
+
+    /// `(plugin index, format name, formatted color)` for every plugin format
+    /// of `color`, if it was prepared.
+    pub fn formats(&self, color: Color) -> Vec<(usize, &str, &str)> {
+        self.formats
+            .get(&color.into_rgba8())
+            .into_iter()
+            .flatten()
+            .map(|(index, formatted)| (*index, self.plugins[*index].name(), formatted.as_str()))
+            .collect()
+    }
+
+    pub fn format(&self, index: usize, color: Color) -> Option<&str> {
+        self.formats(color).into_iter().find(|(plugin, _, _)| *plugin == index).map(|(_, _, formatted)| formatted)
+    }
+
+    pub fn take_error(&mut self) -> Option<PeekerError> {
+        self.error.take()
+    }
+
+    /// `(plugin index, exporter name)` for every plugin exporter.
+    pub fn exporters(&self) -> Vec<(usize, &str)> {
+        self.plugins
+            .iter()
+            .enumerate()
+            .filter(|(_, plugin)| plugin.export_extension().is_some())
+            .map(|(index, plugin)| (index, plugin.name()))
+            .collect()
+    }
+
+    pub fn export_extension(&self, index: usize) -> Option<&str> {
+        self.plugins.get(index)?.export_extension()
+    }
+
+    pub fn on_pick(&mut self, color: Color, position: (i32, i32)) {
+        for index in 0..self.plugins.len() {
+            if self.disabled.contains(&index) {
+                continue;
+            }
+            if let Err(e) = self.plugins[index].on_pick(color, position) {
+                self.disable(index, e);
+            }
+        }
+    }
+
+    /// Turns off the plugin at `index` after it failed with `reason`.
+    fn disable(&mut self, index: usize, reason: Box<dyn std::error::Error>) {
+        self.disabled.insert(index);
+        self.error =
+            Some(PeekerError::Plugin { name: self.plugins[index].name().to_string(), reason: reason.to_string() });
+    }
+
+    pub fn export(&self, index: usize, swatches: &[Swatch]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        self.plugins.get(index).ok_or("No such plugin")?.export(swatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Counting {
+        calls: Rc<Cell<usize>>,
+        fails: bool,
+    }
+
+    impl Plugin for Counting {
+        fn name(&self) -> &str {
+            if self.fails { "broken" } else { "counting" }
+        }
+
+        fn format(&self, _color: Color) -> Result<Option<String>, Box<dyn std::error::Error>> {
+            self.calls.set(self.calls.get() + 1);
+            if self.fails { Err("out of fuel".into()) } else { Ok(Some("formatted".to_string())) }
+        }
+    }
+
+    #[test]
+    fn formats_run_once_per_color_and_failing_plugins_turn_off() {
+        let (working, broken) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let mut plugins = Plugins {
+            plugins: vec![
+                Box::new(Counting { calls: working.clone(), fails: false }),
+                Box::new(Counting { calls: broken.clone(), fails: true }),
+            ],
+            ..Plugins::default()
+        };
+        assert_eq!(plugins.formats(Color::WHITE), []);
+
+        plugins.prepare(Color::WHITE);
+        plugins.prepare(Color::WHITE);
+        plugins.prepare(Color::BLACK);
+        assert_eq!(plugins.formats(Color::WHITE), [(0, "counting", "formatted")]);
+        assert_eq!((working.get(), broken.get()), (2, 1));
+        assert_eq!(
+            plugins.take_error().map(|e| e.to_string()).as_deref(),
+            Some("Plugin broken failed and was turned off: out of fuel")
+        );
+        assert!(plugins.take_error().is_none());
+    }
+}
@@ -0,0 +1,126 @@
+//! Lua scripts: every `*.lua` file in the scripts directory is one plugin.
+//! A script may define `name` and either of these global functions, where
+//! `color` is a table `{ r, g, b, hex }` with 0-255 channels:
+//!
+//! - `format(color)`: returns the color as a string in the script's format.
+//! - `on_pick(color, x, y)`: called whenever a color is frozen.
+//!
+//! Each call may run `INSTRUCTIONS_PER_CALL` instructions; a script that
+//! loops past that fails with an error instead of hanging the application.
+
+use super::Plugin;
+use crate::export;
+use iced::Color;
+use mlua::{Function, HookTriggers, IntoLuaMulti, Lua, VmState};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Roughly a tenth of a second of script.
+const INSTRUCTIONS_PER_CALL: u32 = 10_000_000;
+/// How often the instruction limit is checked.
+const HOOK_INTERVAL: u32 = 10_000;
+
+struct LuaScript {
+    name: String,
+    lua: Lua,
+    /// Instructions left for the running call, in `HOOK_INTERVAL`s.
+    budget: Rc<Cell<u32>>,
+}
+
+pub fn load_all(dir: &std::path::Path) -> Vec<Box<dyn Plugin>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut scripts: Vec<Box<dyn Plugin>> = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("lua") {
+            continue;
+        }
+        match LuaScript::load(&path) {
+            Ok(script) => scripts.push(Box::new(script)),
+            Err(e) => eprintln!("Failed to load script {}: {}", path.display(), e),
+        }
+    }
+    scripts
+}
+
+impl LuaScript {
+    fn load(path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let source = std::fs::read_to_string(path)?;
+        let file_stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("script").to_string();
+
+        let lua = Lua::new();
+        let budget = Rc::new(Cell::new(INSTRUCTIONS_PER_CALL / HOOK_INTERVAL));
+        let remaining = budget.clone();
+        lua.set_hook(HookTriggers::new().every_nth_instruction(HOOK_INTERVAL), move |_, _| {
+            match remaining.get().checked_sub(1) {
+                Some(left) => {
+                    remaining.set(left);
+                    Ok(VmState::Continue)
+                },
+                None => Err(mlua::Error::runtime("instruction limit reached")),
+            }
+        });
+        lua.load(&source).set_name(file_stem.as_str()).exec()?;
+        let name = lua.globals().get::<Option<String>>("name")?.unwrap_or(file_stem);
+
+        Ok(Self { name, lua, budget })
+    }
+
+    /// Calls `function` with a fresh instruction budget.
+    fn call<R: mlua::FromLuaMulti>(&self, function: &Function, args: impl IntoLuaMulti) -> mlua::Result<R> {
+        self.budget.set(INSTRUCTIONS_PER_CALL / HOOK_INTERVAL);
+        function.call(args)
+    }
+
+    fn hook(&self, name: &str) -> Option<Function> {
+        self.lua.globals().get::<Option<Function>>(name).ok().flatten()
+    }
+
+    fn color_table(&self, color: Color) -> mlua::Result<mlua::Table> {
+        let [r, g, b, _] = color.into_rgba8();
+        let table = self.lua.create_table()?;
+        table.set("r", r)?;
+        table.set("g", g)?;
+        table.set("b", b)?;
+        table.set("hex", export::to_hex(color))?;
+        Ok(table)
+    }
+}
+
+impl Plugin for LuaScript {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn format(&self, color: Color) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        let Some(format) = self.hook("format") else {
+            return Ok(None);
+        };
+        Ok(Some(self.color_table(color).and_then(|color| self.call::<String>(&format, color))?))
+    }
+
+    fn on_pick(&self, color: Color, position: (i32, i32)) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(on_pick) = self.hook("on_pick") else {
+            return Ok(());
+        };
+        Ok(self.color_table(color).and_then(|color| self.call::<()>(&on_pick, (color, position.0, position.1)))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_looping_script_hits_the_instruction_limit() {
+        let path = std::env::temp_dir().join(format!("pixel-peeker-loop-{}.lua", std::process::id()));
+        std::fs::write(&path, "name = 'loop'\nfunction format(color) while true do end end\n").unwrap();
+        let script = LuaScript::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let error = script.unwrap().format(Color::WHITE).unwrap_err();
+        assert!(error.to_string().contains("instruction limit reached"), "{}", error);
+    }
+}
@@ -0,0 +1,142 @@
+//! WASM plugins: every `*.wasm` file in the plugins directory is one plugin.
+//!
+//! Strings cross the boundary as a pointer and length into the module's
+//! exported `memory`, packed into one `i64` as `(ptr << 32) | len`; `0` means
+//! "nothing". A module exports:
+//!
+//! - `manifest() -> i64`: UTF-8 JSON `{"name": "...", "format": true,
+//!   "export_extension": "txt"}`, where both capabilities are optional.
+//! - `format(r: i32, g: i32, b: i32) -> i64`: the formatted color, if
+//!   `format` is set.
+//! - `alloc(len: i32) -> i32` and `export(ptr: i32, len: i32) -> i64`, if
+//!   `export_extension` is set. The input is a JSON array of
+//!   `{"name", "variable", "hex", "r", "g", "b"}` and the output is the file
+//!   contents.
+//!
+//! Every call may run `FUEL_PER_CALL` instructions; a plugin that loops past
+//! that traps instead of hanging the application.
+
+use super::Plugin;
+use crate::export::{self, Swatch};
+use iced::Color;
+use std::cell::RefCell;
+use wasmi::{Config, Engine, Instance, Linker, Memory, Module, Store};
+
+/// Roughly a tenth of a second of plugin code.
+const FUEL_PER_CALL: u64 = 10_000_000;
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct Manifest {
+    name: String,
+    #[serde(default)]
+    format: bool,
+    #[serde(default)]
+    export_extension: Option<String>,
+}
+
+struct WasmPlugin {
+    manifest: Manifest,
+    store: RefCell<Store<()>>,
+    instance: Instance,
+    memory: Memory,
+}
+
+pub fn load_all(dir: &std::path::Path) -> Vec<Box<dyn Plugin>> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let engine = Engine::new(Config::default().consume_fuel(true));
+    let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("wasm") {
+            continue;
+        }
+        match WasmPlugin::load(&engine, &path) {
+            Ok(plugin) => plugins.push(Box::new(plugin)),
+            Err(e) => eprintln!("Failed to load plugin {}: {}", path.display(), e),
+        }
+    }
+    plugins
+}
+
+impl WasmPlugin {
+    fn load(engine: &Engine, path: &std::path::Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let module = Module::new(engine, &std::fs::read(path)?[..])?;
+        let mut store = Store::new(engine, ());
+        let instance = Linker::<()>::new(engine).instantiate(&mut store, &module)?.start(&mut store)?;
+        let memory = instance.get_memory(&store, "memory").ok_or("Plugin does not export memory")?;
+
+        let mut plugin = Self { manifest: Manifest::default(), store: RefCell::new(store), instance, memory };
+        let packed = plugin.call::<(), i64>("manifest", ())?;
+        plugin.manifest = serde_json::from_slice(&plugin.read(packed)?)?;
+        Ok(plugin)
+    }
+
+    fn call<Params: wasmi::WasmParams, Results: wasmi::WasmResults>(
+        &self,
+        name: &str,
+        params: Params,
+    ) -> Result<Results, Box<dyn std::error::Error>> {
+        let mut store = self.store.borrow_mut();
+        let remaining = store.consume_fuel(0).map_err(wasmi::Error::from)?;
+        store.add_fuel(FUEL_PER_CALL.saturating_sub(remaining)).map_err(wasmi::Error::from)?;
+        let function = self.instance.get_typed_func::<Params, Results>(&*store, name)?;
+        Ok(function.call(&mut *store, params)?)
+    }
+
+    fn read(&self, packed: i64) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let (ptr, len) = ((packed as u64 >> 32) as usize, (packed as u64 & 0xFFFF_FFFF) as usize);
+        let store = self.store.borrow();
+        let bytes = self.memory.data(&*store).get(ptr..ptr + len).ok_or("Plugin returned an out-of-bounds string")?;
+        Ok(bytes.to_vec())
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    fn format(&self, color: Color) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if !self.manifest.format {
+            return Ok(None);
+        }
+        let [r, g, b, _] = color.into_rgba8();
+        let packed = self.call::<(i32, i32, i32), i64>("format", (r.into(), g.into(), b.into()))?;
+        if packed == 0 {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&self.read(packed)?).into_owned()))
+    }
+
+    fn export_extension(&self) -> Option<&str> {
+        self.manifest.export_extension.as_deref()
+    }
+
+    fn export(&self, swatches: &[Swatch]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let input: Vec<serde_json::Value> = swatches
+            .iter()
+            .map(|swatch| {
+                let [r, g, b, _] = swatch.color.into_rgba8();
+                serde_json::json!({
+                    "name": swatch.name,
+                    "variable": swatch.variable,
+                    "hex": export::to_hex(swatch.color),
+                    "r": r,
+                    "g": g,
+                    "b": b,
+                })
+            })
+            .collect();
+        let input = serde_json::to_vec(&input)?;
+
+        let ptr = self.call::<i32, i32>("alloc", input.len() as i32)?;
+        self.memory.write(&mut *self.store.borrow_mut(), ptr as usize, &input).map_err(wasmi::Error::from)?;
+        let packed = self.call::<(i32, i32), i64>("export", (ptr, input.len() as i32))?;
+        if packed == 0 {
+            return Err(format!("{} produced no output", self.manifest.name).into());
+        }
+        self.read(packed)
+    }
+}
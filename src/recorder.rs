@@ -0,0 +1,82 @@
+use crate::export;
+use iced::Color;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+pub const CSV_HEADER: &str = "timestamp,elapsed_ms,x,y,hex,r,g,b\n";
+
+/// Samples one pixel at a fixed interval and streams each sample to a CSV
+/// file, for profiling animations and blinking indicators.
+pub struct Recorder {
+    pub path: std::path::PathBuf,
+    pub samples: usize,
+    writer: std::io::BufWriter<std::fs::File>,
+    interval: Duration,
+    started: Instant,
+    next_sample: Instant,
+}
+
+impl Recorder {
+    pub fn start(path: std::path::PathBuf, interval: Duration) -> std::io::Result<Self> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        writer.write_all(CSV_HEADER.as_bytes())?;
+        let now = Instant::now();
+        Ok(Self { path, samples: 0, writer, interval, started: now, next_sample: now })
+    }
+
+    /// Whether it is time for the next sample; schedules the one after.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if now < self.next_sample {
+            return false;
+        }
+        self.next_sample = now + self.interval;
+        true
+    }
+
+    pub fn record(&mut self, now: Instant, position: (i32, i32), color: Color) -> std::io::Result<()> {
+        let row = sample_row(chrono::Utc::now(), now.duration_since(self.started), position, color);
+        self.writer.write_all(row.as_bytes())?;
+        self.samples += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn sample_row(
+    timestamp: chrono::DateTime<chrono::Utc>,
+    elapsed: Duration,
+    position: (i32, i32),
+    color: Color,
+) -> String {
+    let [r, g, b, _] = color.into_rgba8();
+    format!(
+        "{},{},{},{},{},{},{},{}\n",
+        timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        elapsed.as_millis(),
+        position.0,
+        position.1,
+        export::to_hex(color),
+        r,
+        g,
+        b
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sample_rows_match_header() {
+        let timestamp = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        let row = sample_row(timestamp, Duration::from_millis(250), (10, -4), Color::from_rgb8(0x3A, 0x7B, 0xD5));
+
+        assert_eq!(row, "2024-05-01T12:30:00.000Z,250,10,-4,#3a7bd5,58,123,213\n");
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+    }
+}
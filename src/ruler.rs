@@ -0,0 +1,136 @@
+//! The on-screen ruler: a transparent, click-through, always-on-top window
+//! covering the two measured points, run as its own `pixel-peeker ruler`
+//! process so the main window stays a normal single-window application.
+//! The process exits once its standard input closes, which happens when the
+//! main application stops it or goes away without doing so.
+
+use crate::measure::Span;
+use iced::widget::canvas::{self, Frame, Path, Stroke, Text};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Task, Theme, Vector, mouse, window};
+use std::io::Read;
+use xcap::Monitor;
+
+/// Room around the line for tick marks and the length label.
+const MARGIN: f32 = 40.0;
+const MINOR_TICK_SPACING: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy)]
+struct Ruler {
+    /// The measured points, in logical pixels from the window's corner.
+    a: Point,
+    b: Point,
+    /// The span in screen pixels, as measured.
+    span: Span,
+    /// Screen pixels per logical pixel on the monitor the ruler is on.
+    scale: f32,
+}
+
+/// Parses `x1 y1 x2 y2`, given in screen pixels, and runs the overlay until
+/// standard input closes or the process is killed.
+pub fn run(args: &[String]) -> iced::Result {
+    let coordinates: Vec<i32> = args.iter().filter_map(|arg| arg.parse().ok()).collect();
+    let [x1, y1, x2, y2] = coordinates[..] else {
+        eprintln!("usage: pixel-peeker ruler <x1> <y1> <x2> <y2>");
+        return Ok(());
+    };
+
+    std::thread::spawn(|| {
+        let _ = std::io::stdin().read_to_end(&mut Vec::new());
+        std::process::exit(0);
+    });
+
+    // Window positions and sizes are logical, the measured points physical.
+    let scale = Monitor::from_point(x1, y1).and_then(|monitor| monitor.scale_factor()).unwrap_or(1.0);
+    let logical = |x: i32, y: i32| Point::new(x as f32 / scale, y as f32 / scale);
+    let (a, b) = (logical(x1, y1), logical(x2, y2));
+    let origin = Point::new(a.x.min(b.x) - MARGIN, a.y.min(b.y) - MARGIN);
+    let size = Size::new((a.x - b.x).abs() + MARGIN * 2.0, (a.y - b.y).abs() + MARGIN * 2.0);
+    let ruler = Ruler {
+        a: a - Vector::new(origin.x, origin.y),
+        b: b - Vector::new(origin.x, origin.y),
+        span: Span::between((x1, y1), (x2, y2)),
+        scale,
+    };
+
+    iced::application(
+        move || (ruler, window::latest().and_then(window::enable_mouse_passthrough)),
+        |_: &mut Ruler, _: ()| Task::none(),
+        Ruler::view,
+    )
+    .title("Pixel Peeker Ruler")
+    .style(|_, _| iced::theme::Style { background_color: Color::TRANSPARENT, text_color: Color::WHITE })
+    .window(window::Settings {
+        size,
+        position: window::Position::Specific(origin),
+        decorations: false,
+        transparent: true,
+        resizable: false,
+        level: window::Level::AlwaysOnTop,
+        ..window::Settings::default()
+    })
+    .run()
+}
+
+impl Ruler {
+    fn view(&self) -> Element<'_, ()> {
+        canvas::Canvas::new(*self).width(Length::Fill).height(Length::Fill).into()
+    }
+
+    /// Draws a tick every `MINOR_TICK_SPACING` screen pixels along the line,
+    /// longer every 50 and 100.
+    fn draw_ticks(&self, frame: &mut Frame, stroke: Stroke) {
+        if self.span.distance < 1.0 {
+            return;
+        }
+        // One screen pixel along the line, in logical pixels.
+        let direction = Vector::new(self.b.x - self.a.x, self.b.y - self.a.y) * (1.0 / self.span.distance);
+        let normal = Vector::new(-direction.y, direction.x) * self.scale;
+
+        let mut step = 0;
+        while step as f32 * MINOR_TICK_SPACING <= self.span.distance {
+            let along = self.a + direction * (step as f32 * MINOR_TICK_SPACING);
+            let length = if step % 10 == 0 {
+                12.0
+            } else if step % 5 == 0 {
+                8.0
+            } else {
+                4.0
+            };
+            frame.stroke(&Path::line(along, along + normal * length), stroke);
+            step += 1;
+        }
+    }
+}
+
+impl canvas::Program<()> for Ruler {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let shadow = Stroke::default().with_color(Color::BLACK).with_width(3.0);
+        let line = Stroke::default().with_color(Color::from_rgb(1.0, 0.85, 0.3)).with_width(1.0);
+        for stroke in [shadow, line] {
+            frame.stroke(&Path::line(self.a, self.b), stroke);
+            self.draw_ticks(&mut frame, stroke);
+        }
+
+        let middle = Point::new((self.a.x + self.b.x) / 2.0 + 14.0, (self.a.y + self.b.y) / 2.0 - 14.0);
+        frame.fill_text(Text {
+            content: format!("{:.1}px", self.span.distance),
+            position: middle,
+            color: Color::WHITE,
+            size: 14.0.into(),
+            ..Text::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,194 @@
+use crate::error::PeekerError;
+use crate::export::{self, ExportOptions, PaletteFormat, Swatch};
+use crate::import::{self, ImportedColor};
+use iced::Color;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A palette file outside the config directory, such as one in a git
+/// repository or a synced folder, kept in step with a named palette. It is
+/// re-read when its modification time changes and written back as the
+/// palette changes.
+#[derive(Debug, Clone)]
+pub struct SharedPalette {
+    pub path: PathBuf,
+    modified: Option<SystemTime>,
+    /// The file's colors when it was last read or written, which outside
+    /// changes are found against.
+    synced: Option<Vec<ImportedColor>>,
+}
+
+/// What changed in the file since it was last synced, with colors compared
+/// at 8 bits per channel.
+#[derive(Debug, Default, PartialEq)]
+pub struct Changes {
+    pub added: Vec<ImportedColor>,
+    pub removed: Vec<[u8; 4]>,
+    /// Colors whose name changed, with their new name.
+    pub renamed: Vec<([u8; 4], Option<String>)>,
+}
+
+impl SharedPalette {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, modified: None, synced: None }
+    }
+
+    /// The name of the named palette the file is linked to.
+    pub fn palette_name(&self) -> String {
+        self.path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    /// What changed in the file since it was last read or written, if it
+    /// changed. The first read finds every color added; a file that does not
+    /// exist yet has no changes.
+    pub fn poll(&mut self) -> Result<Option<Changes>, PeekerError> {
+        let modified = modified(&self.path);
+        if (modified.is_some() && modified == self.modified) || !self.path.exists() {
+            return Ok(None);
+        }
+        let colors = import::read_palette(&self.path)?;
+        self.modified = modified;
+        let changes = changes(self.synced.as_deref().unwrap_or_default(), &colors);
+        self.synced = Some(colors);
+        Ok(Some(changes))
+    }
+
+    /// Replaces the file's contents with `swatches`, as plain text for a
+    /// `.txt` file and as a GIMP palette otherwise.
+    pub fn write(&mut self, swatches: &[Swatch]) -> Result<(), PeekerError> {
+        let format = match self.path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("txt") => PaletteFormat::PlainText,
+            _ => PaletteFormat::Gpl,
+        };
+        let contents = export::export_palette(format, swatches, &ExportOptions::default())?;
+        if !std::fs::read(&self.path).is_ok_and(|existing| existing == contents) {
+            std::fs::write(&self.path, contents)
+                .map_err(|source| PeekerError::WriteFile { path: self.path.clone(), source })?;
+        }
+        // Our own write is not an outside change to merge, and what the file
+        // holds now is what later changes are found against.
+        self.modified = modified(&self.path);
+        self.synced = Some(import::read_palette(&self.path)?);
+        Ok(())
+    }
+}
+
+/// How `current` differs from `synced`. A color listed twice counts once.
+pub fn changes(synced: &[ImportedColor], current: &[ImportedColor]) -> Changes {
+    let find = |rgba: [u8; 4]| synced.iter().find(|imported| imported.color.into_rgba8() == rgba);
+    let mut changes = Changes::default();
+    let mut seen = Vec::new();
+    for imported in current {
+        let rgba = imported.color.into_rgba8();
+        if seen.contains(&rgba) {
+            continue;
+        }
+        seen.push(rgba);
+        match find(rgba) {
+            None => changes.added.push(imported.clone()),
+            Some(old) if old.name != imported.name => changes.renamed.push((rgba, imported.name.clone())),
+            Some(_) => {},
+        }
+    }
+    for old in synced {
+        let rgba = old.color.into_rgba8();
+        if !seen.contains(&rgba) && !changes.removed.contains(&rgba) {
+            changes.removed.push(rgba);
+        }
+    }
+    changes
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// The colors of `incoming` that are not in `existing` yet, compared at 8
+/// bits per channel, each only once.
+pub fn new_colors(existing: &[Color], incoming: Vec<ImportedColor>) -> Vec<ImportedColor> {
+    let mut seen: Vec<[u8; 4]> = existing.iter().map(|color| color.into_rgba8()).collect();
+    incoming
+        .into_iter()
+        .filter(|imported| {
+            let rgba = imported.color.into_rgba8();
+            let new = !seen.contains(&rgba);
+            seen.push(rgba);
+            new
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn only_unknown_colors_are_merged() {
+        let red = Color::from_rgb8(0xFF, 0, 0);
+        let blue = Color::from_rgb8(0x3A, 0x7B, 0xD5);
+        let imported = |name: &str, color| ImportedColor { name: Some(name.into()), color, date: None };
+        let incoming = vec![imported("red", red), imported("blue", blue), imported("blue again", blue)];
+        assert_eq!(new_colors(&[red], incoming), vec![imported("blue", blue)]);
+    }
+
+    #[test]
+    fn own_writes_are_not_reported_as_changes() {
+        let path = std::env::temp_dir().join(format!("pixel-peeker-shared-{}.gpl", std::process::id()));
+        let swatch = |name: &str, r, g, b| Swatch {
+            name: name.into(),
+            variable: name.into(),
+            color: Color::from_rgb8(r, g, b),
+            date: None,
+        };
+        let mut shared = SharedPalette::new(path.clone());
+
+        assert_eq!(shared.poll().unwrap(), None);
+        shared.write(&[swatch("Brand", 0x3A, 0x7B, 0xD5)]).unwrap();
+        assert_eq!(shared.poll().unwrap(), None);
+
+        shared.modified = None;
+        shared.synced = None;
+        let changes = shared.poll().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            changes.added,
+            vec![ImportedColor { name: Some("Brand".into()), color: Color::from_rgb8(0x3A, 0x7B, 0xD5), date: None }]
+        );
+    }
+
+    #[test]
+    fn an_emptied_palette_still_syncs() {
+        let path = std::env::temp_dir().join(format!("pixel-peeker-shared-empty-{}.gpl", std::process::id()));
+        let mut shared = SharedPalette::new(path.clone());
+        shared.write(&[]).unwrap();
+
+        // As after a restart.
+        shared.modified = None;
+        shared.synced = None;
+        let changes = shared.poll();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(changes.unwrap(), Some(Changes::default()));
+    }
+
+    #[test]
+    fn changes_since_the_last_sync() {
+        let imported = |name: &str, r, g, b| ImportedColor {
+            name: Some(name.into()),
+            color: Color::from_rgb8(r, g, b),
+            date: None,
+        };
+        let synced = vec![imported("red", 255, 0, 0), imported("blue", 0, 0, 255), imported("gray", 128, 128, 128)];
+        let current =
+            vec![imported("crimson", 255, 0, 0), imported("gray", 128, 128, 128), imported("green", 0, 255, 0)];
+        assert_eq!(
+            changes(&synced, &current),
+            Changes {
+                added: vec![imported("green", 0, 255, 0)],
+                removed: vec![[0, 0, 255, 255]],
+                renamed: vec![([255, 0, 0, 255], Some("crimson".into()))],
+            }
+        );
+        assert_eq!(changes(&current, &current), Changes::default());
+    }
+}
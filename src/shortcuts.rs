@@ -0,0 +1,71 @@
+use iced::keyboard::{Key, Modifiers, key::Named};
+
+/// In-window keyboard actions. These only apply while no text input has
+/// focus; the global freeze and pause hotkeys are handled separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    /// Move the keyboard focus to the next copy button or history swatch.
+    FocusNext,
+    FocusPrevious,
+    /// Press the focused copy button or swatch.
+    Activate,
+    /// Copy the active color in the preferred format.
+    CopyPreferred,
+    /// Copy the active color in the visible format at this (zero-based) index.
+    CopyFormat(usize),
+    /// Focus the first history swatch.
+    JumpToHistory,
+    /// Open or close the command palette (Ctrl+K).
+    CommandPalette,
+    /// Move the frozen sample this many pixels within the frozen preview.
+    MoveSample(i32, i32),
+}
+
+pub fn from_key(key: &Key, modifiers: Modifiers) -> Option<Shortcut> {
+    if modifiers.control() && key.as_ref() == Key::Character("k") {
+        return Some(Shortcut::CommandPalette);
+    }
+    if modifiers.control() || modifiers.alt() || modifiers.logo() {
+        return None;
+    }
+
+    match key.as_ref() {
+        Key::Named(Named::Tab) if modifiers.shift() => Some(Shortcut::FocusPrevious),
+        Key::Named(Named::Tab) => Some(Shortcut::FocusNext),
+        Key::Named(Named::Enter) => Some(Shortcut::Activate),
+        Key::Named(Named::ArrowLeft) => Some(Shortcut::MoveSample(-1, 0)),
+        Key::Named(Named::ArrowRight) => Some(Shortcut::MoveSample(1, 0)),
+        Key::Named(Named::ArrowUp) => Some(Shortcut::MoveSample(0, -1)),
+        Key::Named(Named::ArrowDown) => Some(Shortcut::MoveSample(0, 1)),
+        Key::Character(character) => match character.to_ascii_lowercase().as_str() {
+            "c" => Some(Shortcut::CopyPreferred),
+            "h" => Some(Shortcut::JumpToHistory),
+            digit => match digit.parse::<usize>() {
+                Ok(number @ 1..=9) => Some(Shortcut::CopyFormat(number - 1)),
+                _ => None,
+            },
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keys_map_to_shortcuts() {
+        let character = |c: &str| Key::Character(c.into());
+
+        assert_eq!(from_key(&character("c"), Modifiers::empty()), Some(Shortcut::CopyPreferred));
+        assert_eq!(from_key(&character("H"), Modifiers::SHIFT), Some(Shortcut::JumpToHistory));
+        assert_eq!(from_key(&character("3"), Modifiers::empty()), Some(Shortcut::CopyFormat(2)));
+        assert_eq!(from_key(&character("0"), Modifiers::empty()), None);
+        assert_eq!(from_key(&Key::Named(Named::Tab), Modifiers::SHIFT), Some(Shortcut::FocusPrevious));
+        assert_eq!(from_key(&Key::Named(Named::Enter), Modifiers::empty()), Some(Shortcut::Activate));
+        assert_eq!(from_key(&Key::Named(Named::ArrowUp), Modifiers::empty()), Some(Shortcut::MoveSample(0, -1)));
+        assert_eq!(from_key(&character("c"), Modifiers::CTRL), None);
+        assert_eq!(from_key(&character("k"), Modifiers::CTRL), Some(Shortcut::CommandPalette));
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::PeekerError;
+use crate::import::parse_hex;
+use crate::metrics;
+use iced::Color;
+use serde_json::{Map, Value};
+
+/// How many alias hops (`"{color.base.blue}"`) are followed before giving up.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    /// Dot-separated path such as `color.surface.hover`.
+    pub name: String,
+    pub color: Color,
+}
+
+pub fn load(path: &std::path::Path) -> Result<Vec<Token>, PeekerError> {
+    let invalid = |reason: String| PeekerError::InvalidFile { path: path.to_path_buf(), reason };
+    let contents =
+        std::fs::read_to_string(path).map_err(|source| PeekerError::ReadFile { path: path.to_path_buf(), source })?;
+    let tokens = parse(&serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?);
+    if tokens.is_empty() {
+        return Err(invalid("No color tokens found".to_string()));
+    }
+    Ok(tokens)
+}
+
+/// Collects color tokens from a design-tokens file. Leaves may be W3C-style
+/// `{"$value": ...}`, Style Dictionary-style `{"value": ...}`, or bare
+/// strings; values may be hex colors, `{"hex": ...}` objects, or
+/// `{path.to.token}` aliases.
+pub fn parse(root: &Value) -> Vec<Token> {
+    let mut leaves = Vec::new();
+    collect_leaves(root, String::new(), &mut leaves);
+
+    leaves
+        .iter()
+        .filter_map(|(name, value)| Some(Token { name: name.clone(), color: resolve(root, value, 0)? }))
+        .collect()
+}
+
+fn collect_leaves<'a>(value: &'a Value, path: String, leaves: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(leaf) = object.get("$value").or_else(|| object.get("value")) {
+                leaves.push((path, leaf));
+                return;
+            }
+            for (key, child) in object.iter().filter(|(key, _)| !key.starts_with('$')) {
+                let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                collect_leaves(child, child_path, leaves);
+            }
+        },
+        Value::String(_) if !path.is_empty() => leaves.push((path, value)),
+        _ => {},
+    }
+}
+
+fn resolve(root: &Value, value: &Value, depth: usize) -> Option<Color> {
+    match value {
+        Value::String(text) => match text.trim().strip_prefix('{').and_then(|alias| alias.strip_suffix('}')) {
+            Some(alias) if depth < MAX_ALIAS_DEPTH => {
+                let target = alias.split('.').try_fold(root, |node, key| node.get(key))?;
+                let target = leaf_value(target.as_object()).unwrap_or(target);
+                resolve(root, target, depth + 1)
+            },
+            Some(_) => None,
+            None => parse_color(text),
+        },
+        Value::Object(object) => object.get("hex").and_then(Value::as_str).and_then(parse_color),
+        _ => None,
+    }
+}
+
+fn leaf_value(object: Option<&Map<String, Value>>) -> Option<&Value> {
+    object.and_then(|object| object.get("$value").or_else(|| object.get("value")))
+}
+
+/// Hex colors, ignoring the alpha byte of `#rrggbbaa`.
+fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.trim().trim_start_matches('#');
+    parse_hex(if hex.len() == 8 { hex.get(..6)? } else { hex })
+}
+
+/// The token closest to `color` and its CIEDE2000 distance.
+pub fn nearest(tokens: &[Token], color: Color) -> Option<(&Token, f32)> {
+    tokens.iter().map(|token| (token, metrics::delta_e(token.color, color))).min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn nested_tokens_with_aliases() {
+        let root = serde_json::json!({
+            "color": {
+                "$type": "color",
+                "base": { "blue": { "$value": "#3a7bd5" } },
+                "surface": {
+                    "hover": { "$value": "{color.base.blue}" },
+                    "overlay": { "value": "#00000080" },
+                },
+                "text": "#fff",
+            },
+            "spacing": { "small": { "$value": "4px" } },
+        });
+
+        assert_eq!(
+            parse(&root),
+            vec![
+                Token { name: "color.base.blue".to_string(), color: Color::from_rgb8(0x3A, 0x7B, 0xD5) },
+                Token { name: "color.surface.hover".to_string(), color: Color::from_rgb8(0x3A, 0x7B, 0xD5) },
+                Token { name: "color.surface.overlay".to_string(), color: Color::from_rgb8(0, 0, 0) },
+                Token { name: "color.text".to_string(), color: Color::from_rgb8(0xFF, 0xFF, 0xFF) },
+            ]
+        );
+    }
+
+    #[test]
+    fn alias_cycles_are_dropped() {
+        let root = serde_json::json!({ "a": { "$value": "{b}" }, "b": { "$value": "{a}" } });
+        assert_eq!(parse(&root), vec![]);
+    }
+
+    #[test]
+    fn nearest_token() {
+        let tokens = vec![
+            Token { name: "black".to_string(), color: Color::BLACK },
+            Token { name: "white".to_string(), color: Color::WHITE },
+        ];
+
+        let (token, distance) = nearest(&tokens, Color::from_rgb8(250, 250, 250)).unwrap();
+        assert_eq!(token.name, "white");
+        assert!(distance < 2.0);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error::PeekerError;
+use serde::Deserialize;
+use std::sync::mpsc;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/kdheepak/pixel-peeker/releases/latest";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    /// The release page, which carries the changelog.
+    pub html_url: String,
+}
+
+/// A check against the latest GitHub release, run on a background thread.
+/// Nothing is downloaded or installed.
+pub struct UpdateCheck {
+    /// Started by the user rather than at launch, so "up to date" is worth saying.
+    pub manual: bool,
+    result: mpsc::Receiver<Result<Release, PeekerError>>,
+}
+
+impl UpdateCheck {
+    pub fn start(manual: bool) -> Self {
+        let (sender, result) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(fetch_latest());
+        });
+        Self { manual, result }
+    }
+
+    /// The latest release once the request has finished.
+    pub fn poll(&self) -> Option<Result<Release, PeekerError>> {
+        self.result.try_recv().ok()
+    }
+}
+
+fn fetch_latest() -> Result<Release, PeekerError> {
+    let failed = |e: &dyn std::fmt::Display| PeekerError::UpdateCheck(e.to_string());
+    ureq::get(LATEST_RELEASE_URL)
+        .set("User-Agent", concat!("pixel-peeker/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .timeout(std::time::Duration::from_secs(10))
+        .call()
+        .map_err(|e| failed(&e))?
+        .into_json()
+        .map_err(|e| failed(&e))
+}
+
+/// Whether `tag` (such as `v0.4.0`) is a higher version than `current`.
+/// Pre-release suffixes are ignored; unparsable tags never count as newer.
+pub fn is_newer(tag: &str, current: &str) -> bool {
+    match (parse_version(tag), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn parse_version(text: &str) -> Option<(u64, u64, u64)> {
+    let text = text.trim().trim_start_matches('v');
+    let release = text.split(['-', '+']).next()?;
+    let mut parts = release.split('.').map(str::parse::<u64>);
+    let version = (parts.next()?.ok()?, parts.next().unwrap_or(Ok(0)).ok()?, parts.next().unwrap_or(Ok(0)).ok()?);
+    parts.next().is_none().then_some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert!(is_newer("v0.10.0", "0.3.6"));
+        assert!(is_newer("0.3.7", "0.3.6"));
+        assert!(is_newer("v1.0", "0.3.6"));
+        assert!(!is_newer("v0.3.6", "0.3.6"));
+        assert!(!is_newer("v0.3.5", "0.3.6"));
+        assert!(!is_newer("nightly", "0.3.6"));
+        assert_eq!(parse_version("v0.4.0-rc.1"), Some((0, 4, 0)));
+        assert_eq!(parse_version("1.2.3.4"), None);
+    }
+}
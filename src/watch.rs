@@ -0,0 +1,85 @@
+use crate::metrics;
+use iced::Color;
+use std::time::{Duration, Instant};
+
+/// How often the watched pixel is re-captured.
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Largest ΔE at which the watched pixel counts as matching the target.
+const MATCH_TOLERANCE: f32 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    /// The pixel's color differs from the previous sample.
+    Changed,
+    /// The pixel started matching the target color.
+    Matched,
+}
+
+/// A screen pixel sampled every [`WATCH_INTERVAL`]. Without a target every
+/// change fires; with one, only the transition into a match does.
+#[derive(Debug, Clone)]
+pub struct Watch {
+    pub position: (i32, i32),
+    pub target: Option<Color>,
+    pub last: Option<Color>,
+    matched: bool,
+    next_check: Instant,
+}
+
+impl Watch {
+    pub fn new(position: (i32, i32), target: Option<Color>) -> Self {
+        Self { position, target, last: None, matched: false, next_check: Instant::now() }
+    }
+
+    /// Whether it is time for the next sample; schedules the one after.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if now < self.next_check {
+            return false;
+        }
+        self.next_check = now + WATCH_INTERVAL;
+        true
+    }
+
+    pub fn observe(&mut self, color: Color) -> Option<Trigger> {
+        let previous = self.last.replace(color);
+
+        if let Some(target) = self.target {
+            let matched = metrics::delta_e(color, target) <= MATCH_TOLERANCE;
+            let started = matched && !self.matched;
+            self.matched = matched;
+            return started.then_some(Trigger::Matched);
+        }
+
+        let changed = previous.is_some_and(|previous| previous.into_rgba8() != color.into_rgba8());
+        changed.then_some(Trigger::Changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn changes_fire_after_the_first_sample() {
+        let mut watch = Watch::new((0, 0), None);
+        let (red, green) = (Color::from_rgb8(255, 0, 0), Color::from_rgb8(0, 255, 0));
+
+        assert_eq!(watch.observe(red), None);
+        assert_eq!(watch.observe(red), None);
+        assert_eq!(watch.observe(green), Some(Trigger::Changed));
+    }
+
+    #[test]
+    fn target_fires_once_per_match() {
+        let (red, green) = (Color::from_rgb8(255, 0, 0), Color::from_rgb8(0, 255, 0));
+        let mut watch = Watch::new((0, 0), Some(green));
+
+        assert_eq!(watch.observe(red), None);
+        assert_eq!(watch.observe(green), Some(Trigger::Matched));
+        assert_eq!(watch.observe(green), None);
+        assert_eq!(watch.observe(red), None);
+        assert_eq!(watch.observe(green), Some(Trigger::Matched));
+    }
+}
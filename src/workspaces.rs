@@ -0,0 +1,56 @@
+use crate::error::PeekerError;
+use crate::{SerializableColor, Settings, write_json};
+use serde::{Deserialize, Serialize};
+
+/// The workspace whose history lives in the history file rather than in a
+/// workspace file of its own.
+pub const DEFAULT_WORKSPACE: &str = "Default";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WorkspaceFile {
+    name: String,
+    color_history: Vec<SerializableColor>,
+}
+
+/// Names of all workspaces, starting with [`DEFAULT_WORKSPACE`].
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = get_dir()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let contents = std::fs::read_to_string(entry.ok()?.path()).ok()?;
+            serde_json::from_str::<WorkspaceFile>(&contents).ok().map(|file| file.name)
+        })
+        .filter(|name| name != DEFAULT_WORKSPACE)
+        .collect();
+    names.sort();
+    names.insert(0, DEFAULT_WORKSPACE.to_string());
+    names
+}
+
+pub fn load(name: &str) -> Vec<SerializableColor> {
+    get_path(name)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str::<WorkspaceFile>(&contents).ok())
+        .map(|file| file.color_history)
+        .unwrap_or_default()
+}
+
+pub fn save(name: &str, color_history: &[SerializableColor]) -> Result<(), PeekerError> {
+    let path = get_path(name).ok_or(PeekerError::NoConfigDir("workspaces"))?;
+    let file = WorkspaceFile { name: name.to_string(), color_history: color_history.to_vec() };
+    write_json(&path, &file)
+}
+
+fn get_dir() -> Option<std::path::PathBuf> {
+    Settings::get_settings_path().and_then(|path| path.parent().map(|parent| parent.join("workspaces")))
+}
+
+pub fn get_path(name: &str) -> Option<std::path::PathBuf> {
+    let file_name = crate::slugify(name);
+    if file_name.is_empty() {
+        return None;
+    }
+    get_dir().map(|dir| dir.join(format!("{}.json", file_name)))
+}